@@ -30,7 +30,13 @@ fn main() {
       .file("src/common/status.cc")
       .file("src/common/memory_pool.cc")
       .file("src/types/primitive.cc")
+      .file("src/types/string.cc")
+      .file("src/types/list.cc")
+      .file("src/types/struct.cc")
+      .file("src/types/union.cc")
+      .file("src/types/decimal.cc")
       .file("src/array.cc")
+      .file("src/array_builder.cc")
       .file("src/buffer.cc")
       .file("src/column.cc")
       .file("src/table.cc")
@@ -23,9 +23,24 @@ Type::type arr_type_enum(ArrayBox* arr) {
   return arr->p->type_enum();
 }
 
-// TODO: null_bitmap()
+BufferBox* arr_null_bitmap(ArrayBox* arr) {
+  if (!arr->p->null_bitmap()) {
+    return nullptr;
+  }
+  BufferBox* buf = new BufferBox;
+  buf->sp = arr->p->null_bitmap();
+  buf->p = buf->sp.get();
+  return buf;
+}
 
-// TODO: null_bitmap_data()
+const uint8_t* arr_null_bitmap_data(ArrayBox* arr) {
+  return arr->p->null_bitmap_data();
+}
+
+const uint8_t* arr_validity(ArrayBox* arr, int32_t* out_len) {
+  *out_len = arr->p->length();
+  return arr->p->null_bitmap_data();
+}
 
 bool arr_equals_exact(ArrayBox* arr1, ArrayBox* arr2) {
   return arr1->p->EqualsExact(*(arr2->p));
@@ -46,6 +61,13 @@ ArrayBox* new_null_arr(DataTypeBox* type, int32_t length) {
   return arr;
 }
 
+ArrayBox* arr_share(ArrayBox* arr) {
+  ArrayBox* shared = new ArrayBox;
+  shared->sp = arr->sp;
+  shared->p = shared->sp.get();
+  return shared;
+}
+
 void release_arr(ArrayBox* arr) {
   if (arr) {
     delete arr;
@@ -24,9 +24,12 @@ extern "C" {
 
   Type::type arr_type_enum(ArrayBox* arr);
 
-  // TODO: null_bitmap()
+  BufferBox* arr_null_bitmap(ArrayBox* arr);
 
-  // TODO: null_bitmap_data()
+  const uint8_t* arr_null_bitmap_data(ArrayBox* arr);
+
+  // Returns the validity bitmap, which is null if every slot is valid, and the length in out_len
+  const uint8_t* arr_validity(ArrayBox* arr, int32_t* out_len);
 
   bool arr_equals_exact(ArrayBox* arr1, ArrayBox* arr2);
 
@@ -36,6 +39,8 @@ extern "C" {
 
   ArrayBox* new_null_arr(DataTypeBox* type, int32_t length);
 
+  ArrayBox* arr_share(ArrayBox* arr);
+
   void release_arr(ArrayBox* arr);
 }
 
@@ -1,8 +1,14 @@
 use ty;
 use ty::{DataType, RawDataTypePtr, Ty};
-use buffer::{Buf, Buffer};
+use buffer::{RawBufferPtr, Buf, Buffer};
+use bitmap;
+use bitmap::BitmapRef;
 use types::primitive;
+use common::status::ArrowError;
 use std::mem;
+use std::ptr;
+use std::slice;
+use std::str;
 
 // TODO: inheritance relationship for Array and its sub classes
 
@@ -27,6 +33,92 @@ pub trait Array<Ty=Self> {
   fn new_null_array(length: i32) -> Ty;
 
   fn from_raw(raw_array: RawArrayPtr) -> Ty;
+
+  // Whether arrays of the type can be viewed as this array type, e.g. by typed accessors
+  fn accepts(ty: ty::Ty) -> bool;
+
+  // A view of the validity bitmap, which is absent if every slot is valid
+  fn validity(&self) -> Option<BitmapRef> {
+    let mut len = 0;
+    let bits = unsafe { arr_validity(self.raw_array(), &mut len) };
+    if bits.is_null() {
+      None
+    } else {
+      Some(unsafe { BitmapRef::from_raw(bits, len as usize) })
+    }
+  }
+}
+
+// Takes over the raw array as a T. Fails, releasing the raw array, if T does not accept its type,
+// since T would then misread its buffers.
+pub fn typed_array<T: Array>(raw_array: RawArrayPtr) -> Result<T, ArrowError> {
+  let array = BaseArray::from_raw(raw_array);
+  if T::accepts(array.ty()) {
+    Ok(T::from_raw(unsafe { arr_share(raw_array) }))
+  } else {
+    Err(ArrowError::type_error(format!("an array of {} cannot be viewed as the requested array type",
+                                       array.data_type().to_string())))
+  }
+}
+
+// Reads the validity bit directly instead of calling Array::IsNull, with a single call into the
+// array. raw_array must point to a live array. Panics if i is out of bounds.
+pub unsafe fn is_null(raw_array: RawArrayPtr, i: i32) -> bool {
+  let mut len = 0;
+  let bits = arr_validity(raw_array, &mut len);
+  assert!(i >= 0 && i < len, "index is out of the bounds of the array");
+  !bits.is_null() && !bitmap::get_bit_raw(bits, i as usize)
+}
+
+// The contents of the buffer, e.g. for validating it
+pub fn buf_bytes(buf: &Buf) -> &[u8] {
+  let data = buf.data();
+  if data.is_null() || buf.size() <= 0 {
+    &[]
+  } else {
+    unsafe { slice::from_raw_parts(data, buf.size() as usize) }
+  }
+}
+
+// Checks the length, the null count and the validity bitmap given to a from_buffers() constructor
+pub fn check_validity(length: i32, null_count: i32, null_bitmap: &Option<Buf>) -> Result<(), ArrowError> {
+  if length < 0 || null_count < 0 || null_count > length {
+    return Err(ArrowError::invalid("array has an invalid length or null count"));
+  }
+  match *null_bitmap {
+    Some(ref bitmap) if (bitmap.size() as usize) < (length as usize + 7) / 8 => {
+      Err(ArrowError::invalid("validity bitmap is too short"))
+    },
+    None if null_count > 0 => Err(ArrowError::invalid("array with nulls must have a validity bitmap")),
+    _ => Ok(())
+  }
+}
+
+// Reads the length + 1 offsets of a variable-size array
+pub fn read_offsets(offsets: &Buf, length: i32) -> Result<Vec<i32>, ArrowError> {
+  let bytes = buf_bytes(offsets);
+  let len = length as usize + 1;
+  if bytes.len() < 4 * len {
+    return Err(ArrowError::invalid("offsets buffer is too short"));
+  }
+  let raw_offsets = bytes.as_ptr() as *const i32;
+  Ok((0..len).map(|i| unsafe { ptr::read_unaligned(raw_offsets.offset(i as isize)) }).collect())
+}
+
+// Checks that the offsets increase within [0, limit], and returns the last one
+pub fn check_offsets(offsets: &[i32], limit: usize) -> Result<usize, ArrowError> {
+  if offsets[0] < 0 || offsets.windows(2).any(|w| w[0] > w[1]) || offsets[offsets.len() - 1] as usize > limit {
+    return Err(ArrowError::invalid("offsets are out of bounds"));
+  }
+  Ok(offsets[offsets.len() - 1] as usize)
+}
+
+// Checks that every value between the offsets is valid UTF-8
+pub fn check_utf8(offsets: &[i32], data: &[u8]) -> Result<(), ArrowError> {
+  if offsets.windows(2).any(|w| str::from_utf8(&data[w[0] as usize..w[1] as usize]).is_err()) {
+    return Err(ArrowError::invalid("string array has invalid UTF-8"));
+  }
+  Ok(())
 }
 
 #[derive(Debug)]
@@ -48,7 +140,7 @@ impl BaseArray {
 
 impl Array for BaseArray {
   fn is_null(&self, i: i32) -> bool {
-    unsafe { arr_is_null(self.raw_array, i) }
+    unsafe { is_null(self.raw_array, i) }
   }
 
   fn len(&self) -> i32 {
@@ -90,6 +182,9 @@ impl Array for BaseArray {
     }
   }
 
+  fn accepts(_: ty::Ty) -> bool {
+    true
+  }
 }
 
 impl PartialEq for BaseArray {
@@ -114,9 +209,13 @@ extern "C" {
   pub fn arr_null_count(arr: RawArrayPtr) -> i32;
   pub fn arr_type(arr: RawArrayPtr) -> RawDataTypePtr;
   pub fn arr_type_enum(arr: RawArrayPtr) -> ty::Ty;
+  pub fn arr_null_bitmap(arr: RawArrayPtr) -> RawBufferPtr;
+  pub fn arr_null_bitmap_data(arr: RawArrayPtr) -> *const u8;
+  pub fn arr_validity(arr: RawArrayPtr, out_len: *mut i32) -> *const u8;
   pub fn arr_equals_exact(arr1: RawArrayPtr, arr2: RawArrayPtr) -> bool;
   pub fn arr_equals(arr1: RawArrayPtr, arr2: RawArrayPtr) -> bool;
   pub fn arr_range_equals(arr1: RawArrayPtr, arr2: RawArrayPtr, start: i32, end: i32, other_start: i32) -> bool;
   pub fn new_null_arr(ty: RawDataTypePtr, length: i32) -> RawArrayPtr;
+  pub fn arr_share(arr: RawArrayPtr) -> RawArrayPtr;
   pub fn release_arr(arr: RawArrayPtr);
 }
\ No newline at end of file
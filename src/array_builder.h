@@ -1,6 +1,24 @@
 #ifndef ARRAY_BUILDER_H
 #define ARRAY_BUILDER_H
 
+#include "arrow/builder.h"
+#include "array.h"
 
+using namespace arrow;
+
+extern "C" {
+
+  int32_t arr_builder_len(ArrayBuilder* builder) {
+    return builder->length();
+  }
+
+  // Finish() is virtual, so this works for builders of any type
+  ArrayBox* finish_arr_builder(ArrayBuilder* builder) {
+    ArrayBox* arr = new ArrayBox;
+    arr->sp = builder->Finish();
+    arr->p = arr->sp.get();
+    return arr;
+  }
+}
 
 #endif
\ No newline at end of file
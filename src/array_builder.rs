@@ -0,0 +1,262 @@
+use array::{BaseArray, RawArrayPtr};
+use ty::{DataType, Ty};
+use types::primitive::*;
+use types::string::{BinaryArrayBuilder, StringArrayBuilder};
+use types::list::ListArrayBuilder;
+use types::structs::StructArrayBuilder;
+use types::union::UnionArrayBuilder;
+use types::decimal::DecimalArrayBuilder;
+use common::memory_pool::MemoryPool;
+use common::status::ArrowError;
+
+use std::any::Any;
+
+pub enum RawArrayBuilder {}
+
+pub type RawArrayBuilderMutPtr = *mut RawArrayBuilder;
+
+// Implemented by every builder wrapping an arrow::ArrayBuilder
+pub trait ArrayBuilder {
+  fn raw_builder(&self) -> RawArrayBuilderMutPtr;
+
+  fn append_null(&mut self) -> Result<(), ArrowError>;
+
+  // For downcasting a builder from make_builder() to call its typed append methods
+  fn as_any(&self) -> &Any;
+
+  fn as_any_mut(&mut self) -> &mut Any;
+
+  // The number of slots appended so far
+  fn len(&self) -> i32 {
+    unsafe { arr_builder_len(self.raw_builder()) }
+  }
+
+  // Returns the array built so far and resets the builder. Fails for builders of nested types
+  // whose children are out of step with the slots.
+  fn finish(&mut self) -> Result<BaseArray, ArrowError> {
+    Ok(BaseArray::from_raw(unsafe { finish_arr_builder(self.raw_builder()) }))
+  }
+}
+
+// Lets boxed builders, e.g. from make_builder(), be used as value builders of lists
+impl<B: ArrayBuilder + ?Sized> ArrayBuilder for Box<B> {
+  fn raw_builder(&self) -> RawArrayBuilderMutPtr {
+    (**self).raw_builder()
+  }
+
+  fn append_null(&mut self) -> Result<(), ArrowError> {
+    (**self).append_null()
+  }
+
+  fn as_any(&self) -> &Any {
+    (**self).as_any()
+  }
+
+  fn as_any_mut(&mut self) -> &mut Any {
+    (**self).as_any_mut()
+  }
+
+  fn len(&self) -> i32 {
+    (**self).len()
+  }
+
+  fn finish(&mut self) -> Result<BaseArray, ArrowError> {
+    (**self).finish()
+  }
+}
+
+// Creates a builder for arrays of the given type. The value builder of a list and the field
+// builders of a struct are created recursively, and can be reached by downcasting to
+// ListArrayBuilder<Box<ArrayBuilder>> and StructArrayBuilder. Temporal arrays are built by the
+// primitive builder of their storage type, e.g. I64ArrayBuilder for timestamps. libarrow has no
+// dictionary type, so dictionary-encoded arrays are built by a DictionaryBuilder instead.
+pub fn make_builder(data_type: &DataType, pool: &MemoryPool) -> Result<Box<ArrayBuilder>, ArrowError> {
+  let builder: Box<ArrayBuilder> = match data_type.ty() {
+    Ty::BOOL => Box::new(BooleanArrayBuilder::new(pool, data_type)),
+    Ty::UINT8 => Box::new(U8ArrayBuilder::new(pool, data_type)),
+    Ty::INT8 => Box::new(I8ArrayBuilder::new(pool, data_type)),
+    Ty::UINT16 => Box::new(U16ArrayBuilder::new(pool, data_type)),
+    Ty::INT16 => Box::new(I16ArrayBuilder::new(pool, data_type)),
+    Ty::UINT32 => Box::new(U32ArrayBuilder::new(pool, data_type)),
+    Ty::INT32 => Box::new(I32ArrayBuilder::new(pool, data_type)),
+    Ty::UINT64 => Box::new(U64ArrayBuilder::new(pool, data_type)),
+    Ty::INT64 => Box::new(I64ArrayBuilder::new(pool, data_type)),
+    Ty::FLOAT => Box::new(F32ArrayBuilder::new(pool, data_type)),
+    Ty::DOUBLE => Box::new(F64ArrayBuilder::new(pool, data_type)),
+    Ty::BINARY => Box::new(BinaryArrayBuilder::new(pool, data_type)),
+    Ty::STRING => Box::new(StringArrayBuilder::new(pool, data_type)),
+    Ty::DATE => Box::new(I32ArrayBuilder::new(pool, data_type)),
+    Ty::TIMESTAMP | Ty::TIME => Box::new(I64ArrayBuilder::new(pool, data_type)),
+    Ty::DECIMAL => {
+      match data_type.decimal_type() {
+        Some(decimal_type) => Box::new(DecimalArrayBuilder::new(pool, decimal_type)),
+        None => return Err(ArrowError::type_error(format!("{} has no precision and scale", data_type.to_string())))
+      }
+    },
+    Ty::LIST => {
+      let value_builder = try!(make_builder(&data_type.child(0).data_type(), pool));
+      Box::new(ListArrayBuilder::new(pool, data_type, value_builder))
+    },
+    Ty::STRUCT => {
+      let mut field_builders = Vec::with_capacity(data_type.num_children() as usize);
+      for i in 0..data_type.num_children() {
+        field_builders.push(try!(make_builder(&data_type.child(i).data_type(), pool)));
+      }
+      Box::new(try!(StructArrayBuilder::new(pool, data_type, field_builders)))
+    },
+    Ty::DENSE_UNION | Ty::SPARSE_UNION => Box::new(try!(UnionArrayBuilder::new(pool, data_type))),
+    _ => return Err(ArrowError::invalid(format!("no builder is available for {}", data_type.to_string())))
+  };
+  Ok(builder)
+}
+
+// A dynamically typed value of a single slot, e.g. a cell of a row
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value<'a> {
+  Null,
+  Bool(bool),
+  U8(u8),
+  I8(i8),
+  U16(u16),
+  I16(i16),
+  U32(u32),
+  I32(i32),
+  U64(u64),
+  I64(i64),
+  F32(f32),
+  F64(f64),
+  Binary(&'a [u8]),
+  Str(&'a str),
+  // Days since the UNIX epoch
+  Date(i32),
+  // Timestamps and times are in the unit of their type
+  Timestamp(i64),
+  Time(i64),
+  // Unscaled value, e.g. 1234 for 12.34 in a decimal with a scale of 2
+  Decimal(i128),
+  List(Vec<Value<'a>>),
+  // One value per field of the struct
+  Struct(Vec<Value<'a>>),
+  // Type id of the union field, and the value of that field
+  Union(i8, Box<Value<'a>>)
+}
+
+// Checks that the value, including every element of a list and field of a struct, can be stored
+// in arrays of the type
+pub fn check_value(data_type: &DataType, value: &Value) -> Result<(), ArrowError> {
+  let matches = match (data_type.ty(), value) {
+    (_, &Value::Null) => true,
+    (Ty::BOOL, &Value::Bool(_)) => true,
+    (Ty::UINT8, &Value::U8(_)) => true,
+    (Ty::INT8, &Value::I8(_)) => true,
+    (Ty::UINT16, &Value::U16(_)) => true,
+    (Ty::INT16, &Value::I16(_)) => true,
+    (Ty::UINT32, &Value::U32(_)) => true,
+    (Ty::INT32, &Value::I32(_)) => true,
+    (Ty::UINT64, &Value::U64(_)) => true,
+    (Ty::INT64, &Value::I64(_)) => true,
+    (Ty::FLOAT, &Value::F32(_)) => true,
+    (Ty::DOUBLE, &Value::F64(_)) => true,
+    (Ty::BINARY, &Value::Binary(_)) => true,
+    (Ty::STRING, &Value::Str(_)) => true,
+    (Ty::DATE, &Value::Date(_)) => true,
+    (Ty::TIMESTAMP, &Value::Timestamp(_)) => true,
+    (Ty::TIME, &Value::Time(_)) => true,
+    (Ty::DECIMAL, &Value::Decimal(_)) => true,
+    (Ty::LIST, &Value::List(ref values)) => {
+      let value_type = data_type.child(0).data_type();
+      for value in values {
+        try!(check_value(&value_type, value));
+      }
+      true
+    },
+    (Ty::STRUCT, &Value::Struct(ref values)) => {
+      if values.len() as i32 != data_type.num_children() {
+        return Err(ArrowError::invalid(format!("the struct value has {} fields but {} has {}", values.len(),
+                                               data_type.to_string(), data_type.num_children())));
+      }
+      for (i, value) in values.iter().enumerate() {
+        try!(check_value(&data_type.child(i as i32).data_type(), value));
+      }
+      true
+    },
+    (Ty::DENSE_UNION, &Value::Union(type_id, ref value)) | (Ty::SPARSE_UNION, &Value::Union(type_id, ref value)) => {
+      if type_id < 0 || type_id as i32 >= data_type.num_children() {
+        return Err(ArrowError::invalid(format!("type id {} does not refer to any field of {}", type_id,
+                                               data_type.to_string())));
+      }
+      try!(check_value(&data_type.child(type_id as i32).data_type(), value));
+      true
+    },
+    _ => false
+  };
+
+  if matches {
+    Ok(())
+  } else {
+    Err(ArrowError::invalid(format!("{:?} cannot be stored as {}", value, data_type.to_string())))
+  }
+}
+
+fn downcast<B: ArrayBuilder + 'static>(builder: &mut ArrayBuilder) -> Result<&mut B, ArrowError> {
+  match builder.as_any_mut().downcast_mut::<B>() {
+    Some(builder) => Ok(builder),
+    None => Err(ArrowError::invalid("the builder does not match the type of the value"))
+  }
+}
+
+fn append_checked(builder: &mut ArrayBuilder, value: &Value) -> Result<(), ArrowError> {
+  macro_rules! append {
+    ($builder_ty:ty, $value:expr) => ({ try!(try!(downcast::<$builder_ty>(builder)).append_value($value)); });
+  }
+
+  match *value {
+    Value::Null => { try!(builder.append_null()); },
+    Value::Bool(v) => append!(BooleanArrayBuilder, v),
+    Value::U8(v) => append!(U8ArrayBuilder, v),
+    Value::I8(v) => append!(I8ArrayBuilder, v),
+    Value::U16(v) => append!(U16ArrayBuilder, v),
+    Value::I16(v) => append!(I16ArrayBuilder, v),
+    Value::U32(v) => append!(U32ArrayBuilder, v),
+    Value::I32(v) => append!(I32ArrayBuilder, v),
+    Value::U64(v) => append!(U64ArrayBuilder, v),
+    Value::I64(v) => append!(I64ArrayBuilder, v),
+    Value::F32(v) => append!(F32ArrayBuilder, v),
+    Value::F64(v) => append!(F64ArrayBuilder, v),
+    Value::Binary(v) => append!(BinaryArrayBuilder, v),
+    Value::Str(v) => append!(StringArrayBuilder, v),
+    Value::Date(v) => append!(I32ArrayBuilder, v),
+    Value::Timestamp(v) | Value::Time(v) => append!(I64ArrayBuilder, v),
+    Value::Decimal(v) => append!(DecimalArrayBuilder, v),
+    Value::List(ref values) => {
+      let list_builder = try!(downcast::<ListArrayBuilder<Box<ArrayBuilder>>>(builder));
+      try!(list_builder.append(true));
+      for value in values {
+        try!(append_checked(&mut **list_builder.value_builder(), value));
+      }
+    },
+    Value::Struct(ref values) => {
+      let struct_builder = try!(downcast::<StructArrayBuilder>(builder));
+      try!(struct_builder.append(true));
+      for (i, value) in values.iter().enumerate() {
+        try!(append_checked(struct_builder.field_builder(i as i32), value));
+      }
+    },
+    Value::Union(type_id, ref value) => {
+      try!(try!(downcast::<UnionArrayBuilder>(builder)).append_value(type_id, value));
+    }
+  };
+  Ok(())
+}
+
+// Appends a value to a builder created by make_builder() for the data type. Nothing is appended
+// if the value does not match the type.
+pub fn append_value(builder: &mut ArrayBuilder, data_type: &DataType, value: &Value) -> Result<(), ArrowError> {
+  try!(check_value(data_type, value));
+  append_checked(builder, value)
+}
+
+extern "C" {
+  pub fn arr_builder_len(builder: RawArrayBuilderMutPtr) -> i32;
+  pub fn finish_arr_builder(builder: RawArrayBuilderMutPtr) -> RawArrayPtr;
+}
\ No newline at end of file
@@ -0,0 +1,350 @@
+use buffer::Buf;
+
+use std::ops::{BitAnd, BitOr, Not};
+use std::slice;
+
+#[inline]
+pub fn get_bit(bits: &[u8], i: usize) -> bool {
+  bits[i / 8] & (1 << (i % 8)) != 0
+}
+
+// The caller must guarantee that bits holds at least i + 1 bits
+#[inline]
+pub unsafe fn get_bit_raw(bits: *const u8, i: usize) -> bool {
+  *bits.offset((i / 8) as isize) & (1 << (i % 8)) != 0
+}
+
+#[inline]
+fn bytes_for(len: usize) -> usize {
+  (len + 7) / 8
+}
+
+/// A bit-packed sequence of flags in the least significant bit order of Arrow validity bitmaps.
+/// Bits beyond len() in the last byte are always unset.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bitmap {
+  bits: Vec<u8>,
+  len: usize
+}
+
+impl Bitmap {
+  // A bitmap of len unset bits
+  pub fn new(len: usize) -> Bitmap {
+    Bitmap {
+      bits: vec![0; bytes_for(len)],
+      len: len
+    }
+  }
+
+  // A bitmap of len set bits
+  pub fn new_set(len: usize) -> Bitmap {
+    !&Bitmap::new(len)
+  }
+
+  pub fn from_bools(flags: &[bool]) -> Bitmap {
+    let mut bitmap = Bitmap::new(flags.len());
+    for (i, flag) in flags.iter().enumerate() {
+      if *flag {
+        bitmap.set(i, true);
+      }
+    }
+    bitmap
+  }
+
+  // Sets the bit of every slot holding a value
+  pub fn from_options<T>(values: &[Option<T>]) -> Bitmap {
+    let mut bitmap = Bitmap::new(values.len());
+    for (i, value) in values.iter().enumerate() {
+      if value.is_some() {
+        bitmap.set(i, true);
+      }
+    }
+    bitmap
+  }
+
+  // Sets the bit of every non-zero byte, as valid_bytes of the array builders
+  pub fn from_valid_bytes(valid_bytes: &[u8]) -> Bitmap {
+    let mut bitmap = Bitmap::new(valid_bytes.len());
+    for (i, valid) in valid_bytes.iter().enumerate() {
+      if *valid != 0 {
+        bitmap.set(i, true);
+      }
+    }
+    bitmap
+  }
+
+  // Copies len bits which are already packed, starting at the given bit offset
+  pub fn from_bits(bits: &[u8], offset: usize, len: usize) -> Bitmap {
+    assert!(bytes_for(offset + len) <= bits.len(), "bits are shorter than the bitmap");
+    let mut bitmap = Bitmap::new(len);
+    if offset % 8 == 0 {
+      bitmap.bits.copy_from_slice(&bits[offset / 8..offset / 8 + bytes_for(len)]);
+      bitmap.clear_trailing_bits();
+    } else {
+      for i in 0..len {
+        if get_bit(bits, offset + i) {
+          bitmap.set(i, true);
+        }
+      }
+    }
+    bitmap
+  }
+
+  // The caller must guarantee that bits holds at least len bits
+  pub unsafe fn from_raw(bits: *const u8, len: usize) -> Bitmap {
+    Bitmap::from_bits(slice::from_raw_parts(bits, bytes_for(len)), 0, len)
+  }
+
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  // Panics if i is out of bounds
+  pub fn get(&self, i: usize) -> bool {
+    assert!(i < self.len, "bit index is out of the bounds of the bitmap");
+    get_bit(&self.bits, i)
+  }
+
+  pub fn set(&mut self, i: usize, flag: bool) {
+    assert!(i < self.len, "bit index is out of the bounds of the bitmap");
+    if flag {
+      self.bits[i / 8] |= 1 << (i % 8);
+    } else {
+      self.bits[i / 8] &= !(1 << (i % 8));
+    }
+  }
+
+  pub fn push(&mut self, flag: bool) {
+    if self.len % 8 == 0 {
+      self.bits.push(0);
+    }
+    self.len += 1;
+    let i = self.len - 1;
+    self.set(i, flag);
+  }
+
+  pub fn count_set_bits(&self) -> usize {
+    // trailing bits are unset, so whole bytes can be counted
+    self.bits.iter().map(|b| b.count_ones() as usize).sum()
+  }
+
+  pub fn count_unset_bits(&self) -> usize {
+    self.len - self.count_set_bits()
+  }
+
+  pub fn iter(&self) -> BitIter {
+    self.as_ref().iter()
+  }
+
+  // Iterates over the indices of set bits, skipping unset bytes at once
+  pub fn iter_set_bits(&self) -> SetBitIter {
+    self.as_ref().iter_set_bits()
+  }
+
+  pub fn as_ref(&self) -> BitmapRef {
+    BitmapRef {
+      bits: &self.bits,
+      len: self.len
+    }
+  }
+
+  /// Copies len bits from offset. Panics if the range is out of bounds.
+  pub fn slice(&self, offset: usize, len: usize) -> Bitmap {
+    assert!(offset + len <= self.len, "slice is out of the bounds of the bitmap");
+    Bitmap::from_bits(&self.bits, offset, len)
+  }
+
+  // Packed bytes, whose length is (len() + 7) / 8
+  pub fn as_bytes(&self) -> &[u8] {
+    &self.bits
+  }
+
+  // One byte per bit, as valid_bytes of the array builders
+  pub fn to_valid_bytes(&self) -> Vec<u8> {
+    self.iter().map(|flag| flag as u8).collect()
+  }
+
+  // Hands over the packed bytes to a buffer, which copies them only if they are not aligned
+  pub fn into_buf(self) -> Buf {
+    Buf::from(self.bits)
+  }
+
+  fn clear_trailing_bits(&mut self) {
+    if self.len % 8 != 0 {
+      let last = self.bits.len() - 1;
+      self.bits[last] &= (1 << (self.len % 8)) - 1;
+    }
+  }
+
+  fn zip_with<F: Fn(u8, u8) -> u8>(&self, other: &Bitmap, f: F) -> Bitmap {
+    assert_eq!(self.len, other.len, "bitmaps must have the same length");
+    Bitmap {
+      bits: self.bits.iter().zip(other.bits.iter()).map(|(a, b)| f(*a, *b)).collect(),
+      len: self.len
+    }
+  }
+}
+
+/// A borrowed bit-packed sequence of flags, e.g. the validity bitmap of an array. Unlike Bitmap,
+/// bits beyond len() in the last byte may be set.
+#[derive(Clone, Copy, Debug)]
+pub struct BitmapRef<'a> {
+  bits: &'a [u8],
+  len: usize
+}
+
+impl<'a> BitmapRef<'a> {
+  pub fn new(bits: &'a [u8], len: usize) -> BitmapRef<'a> {
+    assert!(bytes_for(len) <= bits.len(), "bits are shorter than the bitmap");
+    BitmapRef {
+      bits: &bits[..bytes_for(len)],
+      len: len
+    }
+  }
+
+  // The caller must guarantee that bits holds at least len bits, which live as long as 'a
+  pub unsafe fn from_raw(bits: *const u8, len: usize) -> BitmapRef<'a> {
+    BitmapRef::new(slice::from_raw_parts(bits, bytes_for(len)), len)
+  }
+
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  // Panics if i is out of bounds
+  pub fn get(&self, i: usize) -> bool {
+    assert!(i < self.len, "bit index is out of the bounds of the bitmap");
+    get_bit(self.bits, i)
+  }
+
+  pub fn count_set_bits(&self) -> usize {
+    self.iter_set_bits().count()
+  }
+
+  pub fn count_unset_bits(&self) -> usize {
+    self.len - self.count_set_bits()
+  }
+
+  pub fn iter(&self) -> BitIter<'a> {
+    BitIter {
+      bits: self.bits,
+      len: self.len,
+      i: 0
+    }
+  }
+
+  // Iterates over the indices of set bits, skipping unset bytes at once
+  pub fn iter_set_bits(&self) -> SetBitIter<'a> {
+    SetBitIter {
+      bits: self.bits,
+      len: self.len,
+      byte_idx: 0,
+      current: self.bits.first().cloned().unwrap_or(0)
+    }
+  }
+
+  // Packed bytes, whose length is (len() + 7) / 8
+  pub fn as_bytes(&self) -> &'a [u8] {
+    self.bits
+  }
+
+  pub fn to_bitmap(&self) -> Bitmap {
+    Bitmap::from_bits(self.bits, 0, self.len)
+  }
+}
+
+impl<'a> PartialEq<Bitmap> for BitmapRef<'a> {
+  fn eq(&self, other: &Bitmap) -> bool {
+    self.len == other.len && self.iter().eq(other.iter())
+  }
+}
+
+impl<'a> From<&'a [bool]> for Bitmap {
+  fn from(flags: &'a [bool]) -> Bitmap {
+    Bitmap::from_bools(flags)
+  }
+}
+
+impl<'a, 'b> BitAnd<&'b Bitmap> for &'a Bitmap {
+  type Output = Bitmap;
+
+  fn bitand(self, other: &'b Bitmap) -> Bitmap {
+    self.zip_with(other, |a, b| a & b)
+  }
+}
+
+impl<'a, 'b> BitOr<&'b Bitmap> for &'a Bitmap {
+  type Output = Bitmap;
+
+  fn bitor(self, other: &'b Bitmap) -> Bitmap {
+    self.zip_with(other, |a, b| a | b)
+  }
+}
+
+impl<'a> Not for &'a Bitmap {
+  type Output = Bitmap;
+
+  fn not(self) -> Bitmap {
+    let mut bitmap = Bitmap {
+      bits: self.bits.iter().map(|b| !b).collect(),
+      len: self.len
+    };
+    bitmap.clear_trailing_bits();
+    bitmap
+  }
+}
+
+pub struct BitIter<'a> {
+  bits: &'a [u8],
+  len: usize,
+  i: usize
+}
+
+impl<'a> Iterator for BitIter<'a> {
+  type Item = bool;
+
+  fn next(&mut self) -> Option<bool> {
+    if self.i < self.len {
+      self.i += 1;
+      Some(get_bit(self.bits, self.i - 1))
+    } else {
+      None
+    }
+  }
+}
+
+pub struct SetBitIter<'a> {
+  bits: &'a [u8],
+  len: usize,
+  byte_idx: usize,
+  // remaining set bits of the current byte
+  current: u8
+}
+
+impl<'a> Iterator for SetBitIter<'a> {
+  type Item = usize;
+
+  fn next(&mut self) -> Option<usize> {
+    while self.current == 0 {
+      self.byte_idx += 1;
+      if self.byte_idx >= self.bits.len() {
+        return None;
+      }
+      self.current = self.bits[self.byte_idx];
+    }
+
+    let bit = self.current.trailing_zeros() as usize;
+    self.current &= self.current - 1;
+    // the bits beyond len of a borrowed bitmap may be set
+    let i = self.byte_idx * 8 + bit;
+    if i < self.len { Some(i) } else { None }
+  }
+}
\ No newline at end of file
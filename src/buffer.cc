@@ -1,5 +1,12 @@
 #include "buffer.h"
 
+BufferBox* new_foreign_buf(const uint8_t* data, int64_t size, void* owner, ReleaseForeignFn release) {
+  BufferBox* buf = new BufferBox;
+  buf->sp = std::make_shared<ForeignBuffer>(data, size, owner, release);
+  buf->p = buf->sp.get();
+  return buf;
+}
+
 bool buf_part_equals(BufferBox* buf1, BufferBox* buf2, int64_t nbytes) {
   return buf1->p->Equals(*(buf2->p), nbytes);
 }
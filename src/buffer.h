@@ -11,6 +11,24 @@ struct BufferBox {
   Buffer* p;
 };
 
+// Buffer over memory allocated on the Rust side. The release callback is invoked with the owner
+// when the buffer is destroyed, so that Rust can free the allocation.
+typedef void (*ReleaseForeignFn)(void* owner);
+
+class ForeignBuffer : public Buffer {
+ public:
+  ForeignBuffer(const uint8_t* data, int64_t size, void* owner, ReleaseForeignFn release)
+      : Buffer(data, size), owner_(owner), release_(release) {}
+
+  ~ForeignBuffer() {
+    release_(owner_);
+  }
+
+ private:
+  void* owner_;
+  ReleaseForeignFn release_;
+};
+
 struct BufferBuilderBox {
   std::shared_ptr<BufferBuilder> sp;
   BufferBuilder* p;
@@ -18,6 +36,8 @@ struct BufferBuilderBox {
 
 extern "C" {
 
+  BufferBox* new_foreign_buf(const uint8_t* data, int64_t size, void* owner, ReleaseForeignFn release);
+
   bool buf_part_equals(BufferBox* buf1, BufferBox* buf2, int64_t nbytes);
 
   bool buf_equals(BufferBox* buf1, BufferBox* buf2);
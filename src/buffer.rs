@@ -1,5 +1,11 @@
-use common::memory_pool::{MemoryPool, RawMemoryPoolMutPtr};
+use common::memory_pool::{MemoryPool, PoolRef, RawMemoryPoolMutPtr};
 use common::status::{RawStatusPtr, ArrowError};
+use native::buffer::{NativeType, ALIGNMENT};
+use native;
+use libc;
+
+use std::mem;
+use std::slice;
 
 #[macro_use]
 use common::status;
@@ -72,7 +78,9 @@ pub struct Buf {
 
 pub struct MutableBuf {
   raw_buf: RawBufferMutPtr,
-  ty: BufType
+  ty: BufType,
+  // the pool of a buffer from a BufferBuilder, which resizing allocates from
+  pool: Option<PoolRef>
 }
 
 impl_buffer!(Buf, BASE);
@@ -86,20 +94,47 @@ impl Buf {
     }
   }
 
-  fn raw_buf(&self) -> RawBufferPtr {
+  pub fn raw_buf(&self) -> RawBufferPtr {
     self.raw_buf
   }
 }
 
+extern "C" fn release_owner<O>(owner: *mut libc::c_void) {
+  unsafe { drop(Box::from_raw(owner as *mut O)); }
+}
+
+// Hands over the owner of the size bytes at data, which is released with the buffer
+fn foreign_buf<O>(owner: O, data: *const u8, size: usize) -> Buf {
+  let owner = Box::into_raw(Box::new(owner));
+  Buf::from_raw( unsafe { new_foreign_buf(data, size as i64, owner as *mut libc::c_void, release_owner::<O>) } )
+}
+
+// Takes over the allocation of the vector if it is aligned like the allocations of the C++ pools.
+// Otherwise the values are copied into an aligned native buffer.
+impl<T: NativeType> From<Vec<T>> for Buf {
+  fn from(values: Vec<T>) -> Buf {
+    let size = values.len() * mem::size_of::<T>();
+    let data = values.as_ptr() as *const u8;
+    if data as usize % ALIGNMENT == 0 {
+      foreign_buf(values, data, size)
+    } else {
+      let buffer = native::buffer::Buffer::from_slice(unsafe { slice::from_raw_parts(data, size) });
+      let data = buffer.as_ptr();
+      foreign_buf(buffer, data, size)
+    }
+  }
+}
+
 impl MutableBuf {
   pub fn from_raw(raw_buf: RawBufferMutPtr) -> MutableBuf {
     MutableBuf {
       raw_buf: raw_buf,
-      ty: BufType::POOL
+      ty: BufType::POOL,
+      pool: None
     }
   }
 
-  fn raw_buf(&self) -> RawBufferMutPtr {
+  pub fn raw_buf(&self) -> RawBufferMutPtr {
     self.raw_buf
   }
 }
@@ -127,15 +162,16 @@ impl Resizable for MutableBuf {
 }
 
 pub struct BufferBuilder {
-  raw_builder: RawbufferBuilderMutPtr
+  raw_builder: RawbufferBuilderMutPtr,
+  pool: PoolRef
 }
 
 impl BufferBuilder {
   pub fn new(pool: &MemoryPool) -> BufferBuilder {
-    unsafe {
-      BufferBuilder {
-        raw_builder: new_buf_builder(pool.raw_memory_pool())
-      }
+    let pool = PoolRef::new(pool);
+    BufferBuilder {
+      raw_builder: unsafe { new_buf_builder(pool.raw_memory_pool()) },
+      pool: pool
     }
   }
 
@@ -154,7 +190,9 @@ impl BufferBuilder {
   }
 
   pub fn finish(&mut self) -> MutableBuf {
-    MutableBuf::from_raw( unsafe { finish_buf_builder(self.raw_builder) } )
+    let mut buf = MutableBuf::from_raw( unsafe { finish_buf_builder(self.raw_builder) } );
+    buf.pool = Some(self.pool.clone());
+    buf
   }
 
   pub fn capacity(&mut self) -> i32 {
@@ -187,6 +225,8 @@ pub type RawbufferBuilderMutPtr = *const RawBufferBuilder;
 
 extern "C" {
   // Buffer
+  pub fn new_foreign_buf(data: *const u8, size: i64, owner: *mut libc::c_void,
+                         release: extern "C" fn(*mut libc::c_void)) -> RawBufferPtr;
   pub fn release_buf(buf: RawBufferPtr);
 
   pub fn buf_part_equals(buf1: RawBufferPtr, buf2: RawBufferPtr, nbytes: i64) -> bool;
@@ -1,8 +1,8 @@
 #include "memory_pool.h"
 
-StatusBox* mem_alloc(MemoryPool * pool, uint8_t* buffer, int64_t size) {
+StatusBox* mem_alloc(MemoryPool * pool, uint8_t** buffer, int64_t size) {
   StatusBox* status = new StatusBox;
-  status->status = pool->Allocate(size, &buffer);
+  status->status = pool->Allocate(size, buffer);
   return status;
 }
 
@@ -17,4 +17,23 @@ int64_t num_bytes_alloc(MemoryPool* pool) {
 MemoryPool* default_mem_pool() {
   MemoryPool* p2 = default_memory_pool();
   return p2;
+}
+
+MemoryPool* new_foreign_mem_pool(void* pool, ForeignAllocateFn allocate, ForeignFreeFn free,
+                                 ForeignBytesAllocatedFn bytes_allocated, ForeignReleaseFn release) {
+  return new ForeignMemoryPool(pool, allocate, free, bytes_allocated, release);
+}
+
+void retain_mem_pool(MemoryPool* pool) {
+  ForeignMemoryPool* foreign = dynamic_cast<ForeignMemoryPool*>(pool);
+  if (foreign) {
+    foreign->Retain();
+  }
+}
+
+void release_mem_pool(MemoryPool* pool) {
+  ForeignMemoryPool* foreign = dynamic_cast<ForeignMemoryPool*>(pool);
+  if (foreign) {
+    foreign->Release();
+  }
 }
\ No newline at end of file
@@ -4,16 +4,84 @@
 #include "status.h"
 #include "arrow/util/memory-pool.h"
 
+#include <atomic>
+#include <sstream>
+
 using namespace arrow;
 
+// Memory pool implemented on the Rust side. Every call is forwarded to the callbacks with the
+// given pool. The adapter is reference-counted by its Rust owner, by the builders holding it and by
+// every allocation, and hands the pool back to the release callback once the last one is gone.
+typedef bool (*ForeignAllocateFn)(void* pool, int64_t size, uint8_t** out);
+typedef void (*ForeignFreeFn)(void* pool, uint8_t* buffer, int64_t size);
+typedef int64_t (*ForeignBytesAllocatedFn)(void* pool);
+typedef void (*ForeignReleaseFn)(void* pool);
+
+class ForeignMemoryPool : public MemoryPool {
+ public:
+  ForeignMemoryPool(void* pool, ForeignAllocateFn allocate, ForeignFreeFn free,
+                    ForeignBytesAllocatedFn bytes_allocated, ForeignReleaseFn release)
+      : pool_(pool), allocate_(allocate), free_(free), bytes_allocated_(bytes_allocated),
+        release_(release), refs_(1) {}
+
+  ~ForeignMemoryPool() {
+    release_(pool_);
+  }
+
+  Status Allocate(int64_t size, uint8_t** out) override {
+    if (!allocate_(pool_, size, out)) {
+      std::stringstream ss;
+      ss << "failed to allocate " << size << " bytes";
+      return Status::OutOfMemory(ss.str());
+    }
+    Retain();
+    return Status::OK();
+  }
+
+  void Free(uint8_t* buffer, int64_t size) override {
+    free_(pool_, buffer, size);
+    Release();
+  }
+
+  int64_t bytes_allocated() const override {
+    return bytes_allocated_(pool_);
+  }
+
+  void Retain() {
+    refs_.fetch_add(1);
+  }
+
+  void Release() {
+    if (refs_.fetch_sub(1) == 1) {
+      delete this;
+    }
+  }
+
+ private:
+  void* pool_;
+  ForeignAllocateFn allocate_;
+  ForeignFreeFn free_;
+  ForeignBytesAllocatedFn bytes_allocated_;
+  ForeignReleaseFn release_;
+  std::atomic<int64_t> refs_;
+};
+
 extern "C" {
-  StatusBox* mem_alloc(MemoryPool * pool, uint8_t* buffer, int64_t size);
+  StatusBox* mem_alloc(MemoryPool * pool, uint8_t** buffer, int64_t size);
 
   void mem_free(MemoryPool* pool, uint8_t* buffer, int64_t size);
 
   int64_t num_bytes_alloc(MemoryPool* pool);
 
   MemoryPool* default_mem_pool();
+
+  MemoryPool* new_foreign_mem_pool(void* pool, ForeignAllocateFn allocate, ForeignFreeFn free,
+                                   ForeignBytesAllocatedFn bytes_allocated, ForeignReleaseFn release);
+
+  // Only foreign pools are reference-counted. The others live as long as the library.
+  void retain_mem_pool(MemoryPool* pool);
+
+  void release_mem_pool(MemoryPool* pool);
 }
 
 #endif
\ No newline at end of file
@@ -1,6 +1,11 @@
 use common::status::{RawStatusPtr, ArrowError};
+use native::buffer::ALIGNMENT;
 use libc;
+use std::alloc::{self, Layout};
 use std::ptr;
+use std::slice;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicI64, Ordering};
 
 #[macro_use]
 use common::status;
@@ -9,41 +14,575 @@ pub enum RawMemoryPool {}
 
 pub type RawMemoryPoolMutPtr = *mut RawMemoryPool;
 
-pub struct MemoryPool {
+// Every builder and buffer builder accepts any pool. Pools implemented in Rust share an Allocator
+// with the C++ library through a PoolAdapter, which they return from raw_memory_pool(). Pools are
+// shared by builders on different threads, so they must synchronize themselves.
+pub trait MemoryPool: Send + Sync {
+  fn alloc(&self, size: i64) -> Result<*mut u8, ArrowError>;
+
+  // The buffer must have been returned by alloc() of this pool with the same size, and must not be
+  // used or freed again afterwards. PoolAllocation frees safely.
+  unsafe fn free(&self, buffer: *mut u8, size: i64);
+
+  // Bytes currently allocated from this pool
+  fn len(&self) -> i64;
+
+  // The arrow::MemoryPool passed to the C++ library
+  fn raw_memory_pool(&self) -> RawMemoryPoolMutPtr;
+}
+
+// Lets shared pools, e.g. from TrackingMemoryPool::new(), be passed as &MemoryPool
+impl<P: MemoryPool + ?Sized> MemoryPool for Arc<P> {
+  fn alloc(&self, size: i64) -> Result<*mut u8, ArrowError> {
+    (**self).alloc(size)
+  }
+
+  unsafe fn free(&self, buffer: *mut u8, size: i64) {
+    (**self).free(buffer, size)
+  }
+
+  fn len(&self) -> i64 {
+    (**self).len()
+  }
+
+  fn raw_memory_pool(&self) -> RawMemoryPoolMutPtr {
+    (**self).raw_memory_pool()
+  }
+}
+
+// An allocation from a pool, which is returned to the pool when dropped
+pub struct PoolAllocation<'a> {
+  pool: &'a MemoryPool,
+  ptr: *mut u8,
+  len: i64
+}
+
+impl<'a> PoolAllocation<'a> {
+  // The memory is zeroed
+  pub fn new(pool: &'a MemoryPool, len: i64) -> Result<PoolAllocation<'a>, ArrowError> {
+    let ptr = try!(pool.alloc(len));
+    if len > 0 {
+      unsafe { ptr::write_bytes(ptr, 0, len as usize); }
+    }
+    Ok(PoolAllocation {
+      pool: pool,
+      ptr: ptr,
+      len: len
+    })
+  }
+
+  pub fn len(&self) -> i64 {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  pub fn as_ptr(&self) -> *const u8 {
+    self.ptr
+  }
+
+  pub fn as_mut_ptr(&mut self) -> *mut u8 {
+    self.ptr
+  }
+
+  pub fn as_slice(&self) -> &[u8] {
+    if self.len == 0 {
+      // pools may return null for empty allocations
+      return &[];
+    }
+    unsafe { slice::from_raw_parts(self.ptr, self.len as usize) }
+  }
+
+  pub fn as_mut_slice(&mut self) -> &mut [u8] {
+    if self.len == 0 {
+      return &mut [];
+    }
+    unsafe { slice::from_raw_parts_mut(self.ptr, self.len as usize) }
+  }
+}
+
+impl<'a> Drop for PoolAllocation<'a> {
+  fn drop(&mut self) {
+    unsafe { self.pool.free(self.ptr, self.len); }
+  }
+}
+
+// The global pool of the C++ library
+pub struct DefaultMemoryPool {
   pool: RawMemoryPoolMutPtr
 }
 
-impl MemoryPool {
-  pub fn default() -> MemoryPool {
-    MemoryPool {
+impl DefaultMemoryPool {
+  pub fn default() -> DefaultMemoryPool {
+    DefaultMemoryPool {
       pool: unsafe { default_mem_pool() }
     }
   }
+}
 
-  pub fn raw_memory_pool(&self) -> RawMemoryPoolMutPtr {
-    self.pool
-  }
-  
-  pub fn alloc(&mut self, size: i64) -> Result<*mut u8, ArrowError> {
+// The C++ default pool synchronizes itself
+unsafe impl Send for DefaultMemoryPool {}
+unsafe impl Sync for DefaultMemoryPool {}
+
+impl MemoryPool for DefaultMemoryPool {
+  fn alloc(&self, size: i64) -> Result<*mut u8, ArrowError> {
     unsafe {
-      let buf: *mut u8 = ptr::null_mut();
-      let s = mem_alloc(self.pool, buf, size);
+      let mut buf: *mut u8 = ptr::null_mut();
+      let s = mem_alloc(self.pool, &mut buf, size);
       result_from_status!(s, buf)
     }
   }
 
-  pub fn free(&mut self, buf: *mut u8, size: i64) {
-    unsafe { mem_free(self.pool, buf, size) }
+  unsafe fn free(&self, buf: *mut u8, size: i64) {
+    mem_free(self.pool, buf, size)
   }
 
-  pub fn len(&self) -> i64 {
+  fn len(&self) -> i64 {
     unsafe { num_bytes_alloc(self.pool) }
   }
+
+  fn raw_memory_pool(&self) -> RawMemoryPoolMutPtr {
+    self.pool
+  }
+}
+
+// The allocation logic of a pool implemented in Rust
+pub trait Allocator: Send + Sync {
+  fn alloc(&self, size: i64) -> Result<*mut u8, ArrowError>;
+
+  // Same contract as MemoryPool::free()
+  unsafe fn free(&self, buffer: *mut u8, size: i64);
+
+  fn len(&self) -> i64;
+}
+
+// An arrow::MemoryPool forwarding every call to an allocator. The C++ pool is reference-counted by
+// the adapter, every PoolRef and every buffer allocated through it, and keeps the allocator alive
+// until the last of them is gone, so builders and arrays may outlive the Rust pool.
+pub struct PoolAdapter {
+  raw_pool: RawMemoryPoolMutPtr
+}
+
+impl PoolAdapter {
+  pub fn new(allocator: Arc<Allocator>) -> PoolAdapter {
+    // the callbacks receive a thin pointer to the Arc
+    let target = Box::into_raw(Box::new(allocator));
+    PoolAdapter {
+      raw_pool: unsafe {
+        new_foreign_mem_pool(target as *mut libc::c_void, alloc_foreign, free_foreign, len_foreign, release_foreign)
+      }
+    }
+  }
+
+  pub fn raw_memory_pool(&self) -> RawMemoryPoolMutPtr {
+    self.raw_pool
+  }
+}
+
+impl Drop for PoolAdapter {
+  fn drop(&mut self) {
+    unsafe { release_mem_pool(self.raw_pool); }
+  }
+}
+
+// The C++ pool is reference-counted atomically and the allocator is thread-safe
+unsafe impl Send for PoolAdapter {}
+unsafe impl Sync for PoolAdapter {}
+
+// A reference to the C++ pool of a pool, which builders hold so that the pool outlives them.
+// Builders never read it after creating their C++ builder, so they keep it in a _pool field only to
+// release it on drop. Pools of the C++ library are never released, so their references do nothing.
+pub struct PoolRef {
+  raw_pool: RawMemoryPoolMutPtr
+}
+
+impl PoolRef {
+  pub fn new(pool: &MemoryPool) -> PoolRef {
+    let raw_pool = pool.raw_memory_pool();
+    unsafe { retain_mem_pool(raw_pool); }
+    PoolRef {
+      raw_pool: raw_pool
+    }
+  }
+
+  pub fn raw_memory_pool(&self) -> RawMemoryPoolMutPtr {
+    self.raw_pool
+  }
+}
+
+impl Clone for PoolRef {
+  fn clone(&self) -> PoolRef {
+    unsafe { retain_mem_pool(self.raw_pool); }
+    PoolRef {
+      raw_pool: self.raw_pool
+    }
+  }
+}
+
+impl Drop for PoolRef {
+  fn drop(&mut self) {
+    unsafe { release_mem_pool(self.raw_pool); }
+  }
+}
+
+unsafe impl Send for PoolRef {}
+unsafe impl Sync for PoolRef {}
+
+unsafe fn target_allocator<'a>(target: *mut libc::c_void) -> &'a Allocator {
+  &**(target as *const Arc<Allocator>)
+}
+
+extern "C" fn alloc_foreign(target: *mut libc::c_void, size: i64, out: *mut *mut u8) -> bool {
+  match unsafe { target_allocator(target) }.alloc(size) {
+    Ok(buffer) => {
+      unsafe { *out = buffer; }
+      true
+    },
+    Err(_) => false
+  }
+}
+
+extern "C" fn free_foreign(target: *mut libc::c_void, buffer: *mut u8, size: i64) {
+  unsafe { target_allocator(target).free(buffer, size) }
+}
+
+extern "C" fn len_foreign(target: *mut libc::c_void) -> i64 {
+  unsafe { target_allocator(target) }.len()
+}
+
+// Called once the last reference to the C++ pool is gone
+extern "C" fn release_foreign(target: *mut libc::c_void) {
+  unsafe { drop(Box::from_raw(target as *mut Arc<Allocator>)); }
+}
+
+// Implements MemoryPool for a pool holding its allocator and the adapter sharing it
+macro_rules! impl_rust_pool {
+  ($pool:ty) => (
+    impl MemoryPool for $pool {
+      fn alloc(&self, size: i64) -> Result<*mut u8, ArrowError> {
+        self.allocator.alloc(size)
+      }
+
+      unsafe fn free(&self, buffer: *mut u8, size: i64) {
+        self.allocator.free(buffer, size)
+      }
+
+      fn len(&self) -> i64 {
+        self.allocator.len()
+      }
+
+      fn raw_memory_pool(&self) -> RawMemoryPoolMutPtr {
+        self.adapter.raw_memory_pool()
+      }
+    }
+  );
+}
+
+fn system_alloc(size: i64) -> Result<*mut u8, ArrowError> {
+  if size < 0 {
+    return Err(ArrowError::invalid("allocation size must not be negative"));
+  }
+  if size == 0 {
+    // a dangling but aligned pointer, which is never dereferenced
+    return Ok(ALIGNMENT as *mut u8);
+  }
+  let buffer = match Layout::from_size_align(size as usize, ALIGNMENT) {
+    Ok(layout) => unsafe { alloc::alloc(layout) },
+    Err(_) => ptr::null_mut()
+  };
+  if buffer.is_null() {
+    Err(ArrowError::out_of_memory(format!("failed to allocate {} bytes", size)))
+  } else {
+    Ok(buffer)
+  }
+}
+
+// The buffer must have been returned by system_alloc() with the same size
+unsafe fn system_free(buffer: *mut u8, size: i64) {
+  if size > 0 {
+    alloc::dealloc(buffer, Layout::from_size_align_unchecked(size as usize, ALIGNMENT));
+  }
+}
+
+struct SystemAllocator {
+  allocated: AtomicI64
+}
+
+impl Allocator for SystemAllocator {
+  fn alloc(&self, size: i64) -> Result<*mut u8, ArrowError> {
+    let buffer = try!(system_alloc(size));
+    self.allocated.fetch_add(size, Ordering::SeqCst);
+    Ok(buffer)
+  }
+
+  unsafe fn free(&self, buffer: *mut u8, size: i64) {
+    system_free(buffer, size);
+    self.allocated.fetch_sub(size, Ordering::SeqCst);
+  }
+
+  fn len(&self) -> i64 {
+    self.allocated.load(Ordering::SeqCst)
+  }
+}
+
+// Allocates from the Rust global allocator
+pub struct SystemMemoryPool {
+  allocator: Arc<SystemAllocator>,
+  adapter: PoolAdapter
+}
+
+impl SystemMemoryPool {
+  pub fn new() -> Arc<SystemMemoryPool> {
+    let allocator = Arc::new(SystemAllocator {
+      allocated: AtomicI64::new(0)
+    });
+    Arc::new(SystemMemoryPool {
+      adapter: PoolAdapter::new(allocator.clone()),
+      allocator: allocator
+    })
+  }
+}
+
+impl_rust_pool!(SystemMemoryPool);
+
+struct TrackingAllocator {
+  current: AtomicI64,
+  peak: AtomicI64
+}
+
+impl Allocator for TrackingAllocator {
+  fn alloc(&self, size: i64) -> Result<*mut u8, ArrowError> {
+    let buffer = try!(system_alloc(size));
+    let current = self.current.fetch_add(size, Ordering::SeqCst) + size;
+    self.peak.fetch_max(current, Ordering::SeqCst);
+    Ok(buffer)
+  }
+
+  unsafe fn free(&self, buffer: *mut u8, size: i64) {
+    system_free(buffer, size);
+    self.current.fetch_sub(size, Ordering::SeqCst);
+  }
+
+  fn len(&self) -> i64 {
+    self.current.load(Ordering::SeqCst)
+  }
+}
+
+// Records the current and the peak number of allocated bytes
+pub struct TrackingMemoryPool {
+  allocator: Arc<TrackingAllocator>,
+  adapter: PoolAdapter
+}
+
+impl TrackingMemoryPool {
+  pub fn new() -> Arc<TrackingMemoryPool> {
+    let allocator = Arc::new(TrackingAllocator {
+      current: AtomicI64::new(0),
+      peak: AtomicI64::new(0)
+    });
+    Arc::new(TrackingMemoryPool {
+      adapter: PoolAdapter::new(allocator.clone()),
+      allocator: allocator
+    })
+  }
+
+  pub fn current(&self) -> i64 {
+    self.allocator.current.load(Ordering::SeqCst)
+  }
+
+  pub fn peak(&self) -> i64 {
+    self.allocator.peak.load(Ordering::SeqCst)
+  }
+}
+
+impl_rust_pool!(TrackingMemoryPool);
+
+struct BoundedAllocator {
+  limit: i64,
+  allocated: AtomicI64
+}
+
+impl Allocator for BoundedAllocator {
+  fn alloc(&self, size: i64) -> Result<*mut u8, ArrowError> {
+    // reserve first, so that concurrent allocations cannot exceed the limit together
+    let allocated = self.allocated.fetch_add(size, Ordering::SeqCst) + size;
+    if allocated > self.limit {
+      self.allocated.fetch_sub(size, Ordering::SeqCst);
+      return Err(ArrowError::out_of_memory(format!("allocating {} bytes exceeds the limit of {} bytes", size, self.limit)));
+    }
+
+    match system_alloc(size) {
+      Ok(buffer) => Ok(buffer),
+      Err(e) => {
+        self.allocated.fetch_sub(size, Ordering::SeqCst);
+        Err(e)
+      }
+    }
+  }
+
+  unsafe fn free(&self, buffer: *mut u8, size: i64) {
+    system_free(buffer, size);
+    self.allocated.fetch_sub(size, Ordering::SeqCst);
+  }
+
+  fn len(&self) -> i64 {
+    self.allocated.load(Ordering::SeqCst)
+  }
+}
+
+// Fails with StatusCode::OutOfMemory once an allocation would exceed the limit
+pub struct BoundedMemoryPool {
+  allocator: Arc<BoundedAllocator>,
+  adapter: PoolAdapter
+}
+
+impl BoundedMemoryPool {
+  pub fn new(limit: i64) -> Arc<BoundedMemoryPool> {
+    let allocator = Arc::new(BoundedAllocator {
+      limit: limit,
+      allocated: AtomicI64::new(0)
+    });
+    Arc::new(BoundedMemoryPool {
+      adapter: PoolAdapter::new(allocator.clone()),
+      allocator: allocator
+    })
+  }
+
+  pub fn limit(&self) -> i64 {
+    self.allocator.limit
+  }
+}
+
+impl_rust_pool!(BoundedMemoryPool);
+
+// A snapshot of the memory used by a pool
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MemoryUsage {
+  // bytes currently allocated
+  pub current: i64,
+  // the largest value of current so far
+  pub peak: i64,
+  // the number of successful allocations so far
+  pub allocations: i64
+}
+
+struct ChildAllocator {
+  parent: Arc<MemoryPool>,
+  limit: Option<i64>,
+  current: AtomicI64,
+  peak: AtomicI64,
+  allocations: AtomicI64,
+  threshold: Mutex<Option<(i64, Arc<Fn(&MemoryUsage) + Send + Sync>)>>
+}
+
+impl ChildAllocator {
+  fn usage(&self) -> MemoryUsage {
+    MemoryUsage {
+      current: self.current.load(Ordering::SeqCst),
+      peak: self.peak.load(Ordering::SeqCst),
+      allocations: self.allocations.load(Ordering::SeqCst)
+    }
+  }
 }
 
+impl Allocator for ChildAllocator {
+  fn alloc(&self, size: i64) -> Result<*mut u8, ArrowError> {
+    // reserve first, so that concurrent allocations cannot exceed the limit together
+    let previous = self.current.fetch_add(size, Ordering::SeqCst);
+    let current = previous + size;
+    if let Some(limit) = self.limit {
+      if current > limit {
+        self.current.fetch_sub(size, Ordering::SeqCst);
+        return Err(ArrowError::out_of_memory(format!("allocating {} bytes exceeds the limit of {} bytes", size, limit)));
+      }
+    }
+
+    let buffer = match self.parent.alloc(size) {
+      Ok(buffer) => buffer,
+      Err(e) => {
+        self.current.fetch_sub(size, Ordering::SeqCst);
+        return Err(e);
+      }
+    };
+    self.peak.fetch_max(current, Ordering::SeqCst);
+    self.allocations.fetch_add(1, Ordering::SeqCst);
+
+    // the lock is not held by the callback, which may set another one
+    let threshold = self.threshold.lock().unwrap().clone();
+    if let Some((threshold, callback)) = threshold {
+      if previous < threshold && current >= threshold {
+        callback(&self.usage());
+      }
+    }
+    Ok(buffer)
+  }
+
+  unsafe fn free(&self, buffer: *mut u8, size: i64) {
+    self.parent.free(buffer, size);
+    self.current.fetch_sub(size, Ordering::SeqCst);
+  }
+
+  fn len(&self) -> i64 {
+    self.current.load(Ordering::SeqCst)
+  }
+}
+
+// Allocates from a parent pool, so that the parent also accounts for the memory of its children,
+// e.g. one child per query under a process-wide pool. A child with a limit fails with
+// StatusCode::OutOfMemory past the limit, and so does an allocation past any limit of its ancestors.
+// The child keeps its parent alive.
+pub struct ChildMemoryPool {
+  allocator: Arc<ChildAllocator>,
+  adapter: PoolAdapter
+}
+
+impl ChildMemoryPool {
+  pub fn new<P: MemoryPool + 'static>(parent: Arc<P>, limit: Option<i64>) -> Arc<ChildMemoryPool> {
+    let allocator = Arc::new(ChildAllocator {
+      parent: parent,
+      limit: limit,
+      current: AtomicI64::new(0),
+      peak: AtomicI64::new(0),
+      allocations: AtomicI64::new(0),
+      threshold: Mutex::new(None)
+    });
+    Arc::new(ChildMemoryPool {
+      adapter: PoolAdapter::new(allocator.clone()),
+      allocator: allocator
+    })
+  }
+
+  // The callback is invoked with the usage after every allocation which raises the current usage
+  // from below the threshold to the threshold or above. It must not allocate from this pool.
+  pub fn set_threshold_callback<F>(&self, threshold: i64, callback: F)
+      where F: Fn(&MemoryUsage) + Send + Sync + 'static {
+    *self.allocator.threshold.lock().unwrap() = Some((threshold, Arc::new(callback)));
+  }
+
+  pub fn limit(&self) -> Option<i64> {
+    self.allocator.limit
+  }
+
+  pub fn usage(&self) -> MemoryUsage {
+    self.allocator.usage()
+  }
+}
+
+impl_rust_pool!(ChildMemoryPool);
+
 extern "C" {
-  pub fn mem_alloc(pool: RawMemoryPoolMutPtr, buffer: *mut u8, size: i64) -> RawStatusPtr;
+  pub fn mem_alloc(pool: RawMemoryPoolMutPtr, buffer: *mut *mut u8, size: i64) -> RawStatusPtr;
   pub fn mem_free(pool: RawMemoryPoolMutPtr, buffer: *mut u8, size: i64);
   pub fn num_bytes_alloc(pool: *const RawMemoryPool) -> i64;
   pub fn default_mem_pool() -> RawMemoryPoolMutPtr;
+
+  pub fn new_foreign_mem_pool(pool: *mut libc::c_void,
+                              alloc: extern "C" fn(*mut libc::c_void, i64, *mut *mut u8) -> bool,
+                              free: extern "C" fn(*mut libc::c_void, *mut u8, i64),
+                              len: extern "C" fn(*mut libc::c_void) -> i64,
+                              release: extern "C" fn(*mut libc::c_void)) -> RawMemoryPoolMutPtr;
+  pub fn retain_mem_pool(pool: RawMemoryPoolMutPtr);
+  pub fn release_mem_pool(pool: RawMemoryPoolMutPtr);
 }
\ No newline at end of file
@@ -29,13 +29,14 @@ mod tests {
 
     unsafe {
       let pool = memory_pool::default_mem_pool();
-      let buffer: *mut u8 = ptr::null_mut();
+      let mut buffer: *mut u8 = ptr::null_mut();
 
       let init_mem_bytes = memory_pool::num_bytes_alloc(pool);
 
-      let status = memory_pool::mem_alloc(pool, buffer, 64);
+      let status = memory_pool::mem_alloc(pool, &mut buffer, 64);
       assert!(status::ok(status));
       status::release_status(status);
+      assert!(!buffer.is_null());
       // FIXME: using the single memory pool makes difficult to verify the amount of allocated memory
 //      assert_eq!(init_mem_bytes + 64, memory_pool::num_bytes_alloc(pool));
 
@@ -45,22 +46,206 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_arrow_error() {
+    use common::status::{StatusCode, ArrowError};
+    use std::error::Error;
+    use std::fs::File;
+    use std::io;
+
+    let error = ArrowError::invalid(format!("length {} is negative", -1));
+    assert_eq!(StatusCode::Invalid, *error.code());
+    assert_eq!(-1, error.posix_code());
+    assert_eq!("Invalid: length -1 is negative", error.to_string());
+    assert_eq!(error, error.clone());
+
+    let error = ArrowError::with_posix_code(StatusCode::IOError, "read failed", 5);
+    assert_eq!("IOError: read failed (error 5)", error.to_string());
+
+    let error: ArrowError = io::Error::from_raw_os_error(2).into();
+    assert_eq!(StatusCode::IOError, *error.code());
+    assert_eq!(2, error.posix_code());
+
+    assert_eq!(StatusCode::TypeError, StatusCode::from_i32(3));
+    assert_eq!(StatusCode::UnknownError, StatusCode::from_i32(42));
+
+    // ArrowError works with ? and boxed errors
+    fn open(path: &str) -> Result<File, ArrowError> {
+      let file = File::open(path)?;
+      Ok(file)
+    }
+    fn boxed(path: &str) -> Result<File, Box<Error>> {
+      Ok(open(path)?)
+    }
+    match boxed("/nonexistent/arrow") {
+      Ok(_) => panic!("opening a missing file must fail"),
+      Err(e) => assert!(e.to_string().starts_with("IOError: "))
+    };
+  }
+
   #[test]
   fn test_mem_pool() {
-    use common::memory_pool::MemoryPool;
+    use common::memory_pool::{MemoryPool, DefaultMemoryPool};
 
-    let mut pool = MemoryPool::default();
+    let pool = DefaultMemoryPool::default();
     let init_len = pool.len();
 
     let buf = match pool.alloc(64) {
       Ok(buf) => buf,
       Err(e) => panic!("allocation failed: {}", e.message())
     };
+    assert!(!buf.is_null());
     // FIXME: using the single memory pool makes difficult to verify the amount of allocated memory
 //    assert_eq!(init_len + 64, pool.len());
 
-    pool.free(buf, 64);
+    unsafe { pool.free(buf, 64); }
     // FIXME: using the single memory pool makes difficult to verify the amount of allocated memory
 //    assert_eq!(init_len, pool.len());
   }
+
+  #[test]
+  fn test_pool_allocation() {
+    use common::memory_pool::{MemoryPool, DefaultMemoryPool, SystemMemoryPool, BoundedMemoryPool, PoolAllocation};
+
+    let pool = SystemMemoryPool::new();
+    {
+      let mut allocation = match PoolAllocation::new(&pool, 64) {
+        Ok(allocation) => allocation,
+        Err(e) => panic!("allocation failed: {}", e.message())
+      };
+      assert_eq!(64, allocation.len());
+      assert_eq!(64, pool.len());
+      assert!(allocation.as_slice().iter().all(|b| *b == 0));
+
+      allocation.as_mut_slice()[63] = 7;
+      assert_eq!(7, allocation.as_slice()[63]);
+
+      let empty = match PoolAllocation::new(&pool, 0) {
+        Ok(allocation) => allocation,
+        Err(e) => panic!("allocation failed: {}", e.message())
+      };
+      assert!(empty.is_empty());
+      assert_eq!(64, pool.len());
+    }
+    assert_eq!(0, pool.len());
+
+    let pool = BoundedMemoryPool::new(32);
+    assert!(PoolAllocation::new(&pool, 64).is_err());
+    assert_eq!(0, pool.len());
+
+    let pool = DefaultMemoryPool::default();
+    let allocation = match PoolAllocation::new(&pool, 16) {
+      Ok(allocation) => allocation,
+      Err(e) => panic!("allocation failed: {}", e.message())
+    };
+    assert!(!allocation.as_ptr().is_null());
+    assert_eq!(16, allocation.as_slice().len());
+  }
+
+  #[test]
+  fn test_tracking_mem_pool() {
+    use common::memory_pool::{MemoryPool, TrackingMemoryPool};
+
+    let pool = TrackingMemoryPool::new();
+    let buf1 = match pool.alloc(64) {
+      Ok(buf) => buf,
+      Err(e) => panic!("allocation failed: {}", e.message())
+    };
+    let buf2 = match pool.alloc(128) {
+      Ok(buf) => buf,
+      Err(e) => panic!("allocation failed: {}", e.message())
+    };
+    assert_eq!(0, buf1 as usize % 64);
+    assert_eq!(192, pool.len());
+
+    unsafe { pool.free(buf1, 64); }
+    assert_eq!(128, pool.current());
+    assert_eq!(192, pool.peak());
+
+    unsafe { pool.free(buf2, 128); }
+    assert_eq!(0, pool.len());
+    assert_eq!(192, pool.peak());
+  }
+
+  #[test]
+  fn test_bounded_mem_pool() {
+    use common::memory_pool::{MemoryPool, BoundedMemoryPool};
+    use common::status::StatusCode;
+
+    let pool = BoundedMemoryPool::new(100);
+    let buf = match pool.alloc(64) {
+      Ok(buf) => buf,
+      Err(e) => panic!("allocation failed: {}", e.message())
+    };
+    match pool.alloc(64) {
+      Ok(_) => panic!("allocation beyond the limit must fail"),
+      Err(e) => match *e.code() {
+        StatusCode::OutOfMemory => {},
+        _ => panic!("unexpected error: {}", e.message())
+      }
+    };
+    assert_eq!(64, pool.len());
+
+    unsafe { pool.free(buf, 64); }
+    assert_eq!(0, pool.len());
+  }
+  #[test]
+  fn test_child_mem_pool() {
+    use common::memory_pool::{MemoryPool, MemoryUsage, TrackingMemoryPool, BoundedMemoryPool, ChildMemoryPool};
+    use common::status::StatusCode;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let root = TrackingMemoryPool::new();
+    let query1 = ChildMemoryPool::new(root.clone(), Some(256));
+    let query2 = ChildMemoryPool::new(root.clone(), None);
+
+    let crossed = Arc::new(AtomicUsize::new(0));
+    {
+      let crossed = crossed.clone();
+      query1.set_threshold_callback(128, move |usage| {
+        assert!(usage.current >= 128);
+        crossed.fetch_add(1, Ordering::SeqCst);
+      });
+    }
+
+    let buf1 = match query1.alloc(100) {
+      Ok(buf) => buf,
+      Err(e) => panic!("allocation failed: {}", e.message())
+    };
+    let buf2 = match query1.alloc(100) {
+      Ok(buf) => buf,
+      Err(e) => panic!("allocation failed: {}", e.message())
+    };
+    assert_eq!(1, crossed.load(Ordering::SeqCst));
+    match query1.alloc(100) {
+      Ok(_) => panic!("allocation beyond the limit must fail"),
+      Err(e) => match *e.code() {
+        StatusCode::OutOfMemory => {},
+        _ => panic!("unexpected error: {}", e.message())
+      }
+    };
+
+    let buf3 = match query2.alloc(1000) {
+      Ok(buf) => buf,
+      Err(e) => panic!("allocation failed: {}", e.message())
+    };
+    assert_eq!(MemoryUsage { current: 200, peak: 200, allocations: 2 }, query1.usage());
+    assert_eq!(1200, root.len());
+
+    unsafe { query1.free(buf1, 100); }
+    unsafe { query1.free(buf2, 100); }
+    unsafe { query2.free(buf3, 1000); }
+    assert_eq!(MemoryUsage { current: 0, peak: 200, allocations: 2 }, query1.usage());
+    assert_eq!(0, root.len());
+    assert_eq!(1200, root.peak());
+
+    // a child without a limit still fails past the limit of its parent
+    let bounded = BoundedMemoryPool::new(64);
+    let child = ChildMemoryPool::new(bounded.clone(), None);
+    let grandchild = ChildMemoryPool::new(child.clone(), Some(1024));
+    assert!(grandchild.alloc(128).is_err());
+    assert_eq!(0, grandchild.len());
+    assert_eq!(0, child.len());
+  }
 }
\ No newline at end of file
@@ -54,8 +54,8 @@ extern "C" {
     return status->status.posix_code();
   }
 
-  StatusCode code(StatusBox* status) {
-    return status->status.code();
+  int code(StatusBox* status) {
+    return static_cast<int>(status->status.code());
   }
 
   const char* message(StatusBox* status) {
@@ -32,7 +32,8 @@ extern "C" {
 
   int16_t posix_code(StatusBox* status);
 
-  StatusCode code(StatusBox* status);
+  // StatusCode is a char, so it is widened for the Rust side
+  int code(StatusBox* status);
 
   const char* message(StatusBox* status);
 }
@@ -1,5 +1,7 @@
 use libc;
-use std::ops::Drop;
+use std::error::Error;
+use std::fmt;
+use std::io;
 use std::string::String;
 use std::ffi::CStr;
 
@@ -10,56 +12,151 @@ macro_rules! result_from_status {
       unsafe { status::release_status($s) };
       Ok($result)
     } else {
-      Err(ArrowError::new($s))
+      Err(ArrowError::from_status($s))
     }
   );
 }
 
 // Status code of arrow
 // See arrow::StatusCode
-#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum StatusCode {
-    OK = 0,
-    OutOfMemory = 1,
-    KeyError = 2,
-    Invalid = 3,
-    IOError = 4,
+  OK = 0,
+  OutOfMemory = 1,
+  KeyError = 2,
+  TypeError = 3,
+  Invalid = 4,
+  IOError = 5,
 
-    NotImplemented = 10,
+  UnknownError = 9,
+  NotImplemented = 10,
 }
 
+impl StatusCode {
+  // Codes unknown to this library map to UnknownError
+  pub fn from_i32(code: i32) -> StatusCode {
+    match code {
+      0 => StatusCode::OK,
+      1 => StatusCode::OutOfMemory,
+      2 => StatusCode::KeyError,
+      3 => StatusCode::TypeError,
+      4 => StatusCode::Invalid,
+      5 => StatusCode::IOError,
+      10 => StatusCode::NotImplemented,
+      _ => StatusCode::UnknownError
+    }
+  }
+
+  // Same as arrow::Status::CodeAsString()
+  pub fn as_str(&self) -> &'static str {
+    match *self {
+      StatusCode::OK => "OK",
+      StatusCode::OutOfMemory => "Out of memory",
+      StatusCode::KeyError => "Key error",
+      StatusCode::TypeError => "Type error",
+      StatusCode::Invalid => "Invalid",
+      StatusCode::IOError => "IOError",
+      StatusCode::UnknownError => "Unknown error",
+      StatusCode::NotImplemented => "NotImplemented"
+    }
+  }
+}
+
+// The error of every fallible operation in this crate, whether it fails in the C++ library or in Rust
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ArrowError {
   code: StatusCode,
-//  posix_code: i16,
+  // -1 if the error does not come from a system call
+  posix_code: i16,
   message: String
 }
 
 impl ArrowError {
-  pub fn new(status: RawStatusPtr) -> ArrowError {
+  pub fn new<S: Into<String>>(code: StatusCode, message: S) -> ArrowError {
+    ArrowError::with_posix_code(code, message, -1)
+  }
+
+  pub fn with_posix_code<S: Into<String>>(code: StatusCode, message: S, posix_code: i16) -> ArrowError {
+    ArrowError {
+      code: code,
+      posix_code: posix_code,
+      message: message.into()
+    }
+  }
+
+  // Takes over the status, which must not be ok, and releases it
+  pub fn from_status(status: RawStatusPtr) -> ArrowError {
     unsafe {
-      let code = code(status);
-//      let posix_code = posix_code(status);
       let bytes = CStr::from_ptr(message(status)).to_bytes();
-      let msg = String::from_utf8(Vec::from(bytes)).unwrap();
-      release_status(status); // TODO: consider more reliable way to handle the raw status pointer
-
-      ArrowError {
-        code: code,
-//        posix_code: posix_code,
-        message: msg
-      }
+      let error = ArrowError::with_posix_code(StatusCode::from_i32(code(status)),
+                                              String::from_utf8_lossy(bytes).into_owned(),
+                                              posix_code(status));
+      release_status(status);
+      error
     }
   }
 
+  pub fn out_of_memory<S: Into<String>>(message: S) -> ArrowError {
+    ArrowError::new(StatusCode::OutOfMemory, message)
+  }
+
+  pub fn key_error<S: Into<String>>(message: S) -> ArrowError {
+    ArrowError::new(StatusCode::KeyError, message)
+  }
+
+  pub fn type_error<S: Into<String>>(message: S) -> ArrowError {
+    ArrowError::new(StatusCode::TypeError, message)
+  }
+
+  pub fn invalid<S: Into<String>>(message: S) -> ArrowError {
+    ArrowError::new(StatusCode::Invalid, message)
+  }
+
+  pub fn io_error<S: Into<String>>(message: S) -> ArrowError {
+    ArrowError::new(StatusCode::IOError, message)
+  }
+
+  pub fn unknown_error<S: Into<String>>(message: S) -> ArrowError {
+    ArrowError::new(StatusCode::UnknownError, message)
+  }
+
+  pub fn not_implemented<S: Into<String>>(message: S) -> ArrowError {
+    ArrowError::new(StatusCode::NotImplemented, message)
+  }
+
   pub fn code(&self) -> &StatusCode {
     &self.code
   }
 
+  pub fn posix_code(&self) -> i16 {
+    self.posix_code
+  }
+
   pub fn message(&self) -> &String {
     &self.message
   }
 }
 
+// Formatted like arrow::Status::ToString()
+impl fmt::Display for ArrowError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    try!(write!(f, "{}: {}", self.code.as_str(), self.message));
+    if self.posix_code != -1 {
+      try!(write!(f, " (error {})", self.posix_code));
+    }
+    Ok(())
+  }
+}
+
+impl Error for ArrowError {}
+
+impl From<io::Error> for ArrowError {
+  fn from(error: io::Error) -> ArrowError {
+    let posix_code = error.raw_os_error().map_or(-1, |code| code as i16);
+    ArrowError::with_posix_code(StatusCode::IOError, error.to_string(), posix_code)
+  }
+}
+
 pub enum RawStatus {}
 
 pub type RawStatusPtr = *const RawStatus;
@@ -76,7 +173,7 @@ extern "C" {
   pub fn is_not_implemented(status: *const RawStatus) -> bool;
   pub fn status_to_str(status: *const RawStatus) -> *const libc::c_char;
   pub fn code_to_str(status: *const RawStatus) -> *const libc::c_char;
-//  pub fn posix_code(status: *const RawStatus) -> i16;
-  pub fn code(status: *const RawStatus) -> StatusCode;
+  pub fn posix_code(status: *const RawStatus) -> i16;
+  pub fn code(status: *const RawStatus) -> i32;
   pub fn message(status: RawStatusPtr) -> *const libc::c_char;
 }
\ No newline at end of file
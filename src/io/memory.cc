@@ -1,9 +1,13 @@
 #include "memory.h"
 
-MemorySourceBox* open_mmap_src(const char* path, FileMode::type mode) {
+MemorySourceBox* open_mmap_src(const char* path, FileMode::type mode, StatusBox** out_status) {
   std::shared_ptr<MemoryMappedFile> out;
-  Status s = MemoryMappedFile::Open(std::string(path), mode, &out);
-//  assert(s.ok());
+  StatusBox* status = new StatusBox;
+  status->status = MemoryMappedFile::Open(std::string(path), mode, &out);
+  *out_status = status;
+  if (!status->status.ok()) {
+    return nullptr;
+  }
 
   MemorySourceBox* mm_src = new MemorySourceBox;
   mm_src->sp = out;
@@ -15,7 +15,8 @@ struct MemorySourceBox {
 };
 
 extern "C" {
-  MemorySourceBox* open_mmap_src(const char* path, FileMode::type mode);
+  // Returns null if the file cannot be mapped, and the status of opening it in out_status
+  MemorySourceBox* open_mmap_src(const char* path, FileMode::type mode, StatusBox** out_status);
 
   void release_mmap_src(MemorySourceBox* src);
 
@@ -4,6 +4,11 @@ use common::status::{RawStatusPtr, ArrowError};
 use buffer::{RawBufferPtr, Buf, Buffer};
 use libc;
 use std::ffi::CString;
+use std::cmp;
+use std::ptr;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::Write;
 
 #[repr(C)]
 pub enum AccessMode {
@@ -21,10 +26,17 @@ pub struct MemoryMappedSource {
 }
 
 impl MemoryMappedSource {
-  pub fn open(path: String, mode: AccessMode) -> MemoryMappedSource {
-    MemoryMappedSource {
-      raw_source: unsafe { open_mmap_src(string_to_cstr!(path), mode) }
-    }
+  // Fails if the file cannot be mapped, e.g. because it does not exist or is empty
+  pub fn open(path: String, mode: AccessMode) -> Result<MemoryMappedSource, ArrowError> {
+    let path = match CString::new(path) {
+      Ok(path) => path,
+      Err(_) => return Err(ArrowError::invalid("the path contains a NUL character"))
+    };
+    let mut s: RawStatusPtr = ptr::null();
+    let raw_source = unsafe { open_mmap_src(path.as_ptr(), mode, &mut s) };
+    result_from_status!(s, MemoryMappedSource {
+      raw_source: raw_source
+    })
   }
 
   pub fn raw_source(&self) -> RawMemoryMappedSourceMutPtr {
@@ -56,12 +68,120 @@ impl Drop for MemoryMappedSource {
   }
 }
 
+// Capacity of a sink after its first write
+const MIN_SINK_CAPACITY: i64 = 4096;
+
+// A file written through a memory map, which grows as needed. The file is extended by doubling its
+// size and then remapped, and flushing truncates it to the bytes written.
+pub struct MemoryMappedSink {
+  path: String,
+  // absent while the file is empty, since an empty file cannot be mapped
+  src: Option<MemoryMappedSource>,
+  len: i64,
+  capacity: i64
+}
+
+impl MemoryMappedSink {
+  // Creates the file, or truncates it if it exists
+  pub fn create(path: String) -> Result<MemoryMappedSink, ArrowError> {
+    try!(File::create(&path));
+    Ok(MemoryMappedSink {
+      path: path,
+      src: None,
+      len: 0,
+      capacity: 0
+    })
+  }
+
+  pub fn path(&self) -> &str {
+    &self.path
+  }
+
+  // Number of bytes written
+  pub fn len(&self) -> i64 {
+    self.len
+  }
+
+  // Size of the file, which is at least len()
+  pub fn capacity(&self) -> i64 {
+    self.capacity
+  }
+
+  // Resizes the file and maps it again
+  fn remap(&mut self, capacity: i64) -> Result<(), ArrowError> {
+    if let Some(src) = self.src.take() {
+      try!(src.close());
+    }
+    let file = try!(OpenOptions::new().write(true).open(&self.path));
+    try!(file.set_len(capacity as u64));
+
+    if capacity > 0 {
+      self.src = Some(try!(MemoryMappedSource::open(self.path.clone(), AccessMode::READWRITE)));
+    }
+    self.capacity = capacity;
+    Ok(())
+  }
+
+  // Makes room for additional bytes after the written ones
+  pub fn reserve(&mut self, additional: i64) -> Result<(), ArrowError> {
+    let required = self.len + additional;
+    if required > self.capacity {
+      let capacity = cmp::max(cmp::max(self.capacity * 2, required), MIN_SINK_CAPACITY);
+      try!(self.remap(capacity));
+    }
+    Ok(())
+  }
+
+  // Appends the bytes to the file
+  pub fn append(&mut self, data: &[u8]) -> Result<(), ArrowError> {
+    if data.is_empty() {
+      return Ok(());
+    }
+    try!(self.reserve(data.len() as i64));
+    try!(self.src.as_ref().unwrap().write(self.len, data.as_ptr(), data.len() as i64));
+    self.len += data.len() as i64;
+    Ok(())
+  }
+
+  // Truncates the file to the bytes written
+  pub fn sync(&mut self) -> Result<(), ArrowError> {
+    if self.capacity != self.len {
+      let len = self.len;
+      try!(self.remap(len));
+    }
+    Ok(())
+  }
+}
+
+impl Write for MemoryMappedSink {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    match self.append(buf) {
+      Ok(_) => Ok(buf.len()),
+      Err(e) => Err(io::Error::new(io::ErrorKind::Other, e))
+    }
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.sync().map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+  }
+}
+
+impl Drop for MemoryMappedSink {
+  fn drop(&mut self) {
+    // errors cannot be reported here, so callers who need them flush first
+    let _ = self.sync();
+    if let Some(src) = self.src.take() {
+      let _ = src.close();
+    }
+  }
+}
+
 pub enum RawMemoryMappedSource {}
 
 pub type RawMemoryMappedSourceMutPtr = *mut RawMemoryMappedSource;
 
 extern "C" {
-  pub fn open_mmap_src(path: *const libc::c_char, mode: AccessMode) -> RawMemoryMappedSourceMutPtr;
+  pub fn open_mmap_src(path: *const libc::c_char, mode: AccessMode, out_status: *mut RawStatusPtr) -> RawMemoryMappedSourceMutPtr;
   pub fn release_mmap_src(src: RawMemoryMappedSourceMutPtr);
   pub fn close_mmap_src(src: RawMemoryMappedSourceMutPtr) -> RawStatusPtr;
   pub fn read_at_mmap_src(src: RawMemoryMappedSourceMutPtr, pos: i64, nbytes: i64) -> RawBufferPtr;
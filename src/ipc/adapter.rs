@@ -1,10 +1,18 @@
+use array::{Array, BaseArray, arr_share, buf_bytes, check_offsets, check_utf8};
 use io::memory::{MemoryMappedSource, MemorySource};
 use table::RowBatch;
-use ty::Schema;
+use ty::{Schema, Ty};
+use types::string::StringArray;
+use types::list::ListArray;
+use types::structs::StructArray;
+use types::union::UnionArray;
 use common::status::{ArrowError, RawStatusPtr};
 use common::status;
+use buffer::Buffer;
+use ipc::metadata;
 
 use std::mem;
+use std::slice;
 
 //pub struct RowBatchWriter {
 //  mem_src: RawMemoryMappedSourceMutPtr,
@@ -30,6 +38,36 @@ pub fn write_row_batch(src: &MemoryMappedSource, batch: &RowBatch, pos: i64) ->
   unsafe { c_api::write_row_batch(src.raw_source(), batch.raw_batch(), pos, 64) }
 }
 
+// Size of the schema once written by write_schema
+pub fn get_schema_size(schema: &Schema) -> Result<i64, ArrowError> {
+  let message = try!(metadata::schema_to_message(schema));
+  Ok(8 + message.len() as i64)
+}
+
+// Writes the schema at pos as an encapsulated Schema message, and returns the number of bytes written
+pub fn write_schema(src: &MemoryMappedSource, schema: &Schema, pos: i64) -> Result<i64, ArrowError> {
+  let message = metadata::encapsulate(&try!(metadata::schema_to_message(schema)));
+  try!(src.write(pos, message.as_ptr(), message.len() as i64));
+  Ok(message.len() as i64)
+}
+
+// Reads the schema written by write_schema at pos
+pub fn read_schema(src: &MemoryMappedSource, pos: i64) -> Result<Schema, ArrowError> {
+  if pos < 0 || pos + 8 > src.size() {
+    return Err(ArrowError::invalid("no schema message at the position"));
+  }
+  let prefix = src.read(pos, 8);
+  let prefix = unsafe { slice::from_raw_parts(prefix.data(), 8) };
+  let marker = u32::from_le_bytes([prefix[0], prefix[1], prefix[2], prefix[3]]);
+  let len = i32::from_le_bytes([prefix[4], prefix[5], prefix[6], prefix[7]]) as i64;
+  if marker != metadata::CONTINUATION_MARKER || len <= 0 || pos + 8 + len > src.size() {
+    return Err(ArrowError::invalid("no schema message at the position"));
+  }
+
+  let message = src.read(pos + 8, len);
+  metadata::schema_from_message(unsafe { slice::from_raw_parts(message.data(), len as usize) })
+}
+
 pub struct RowBatchReader {
   raw_reader: c_api::RawRowBatchReaderPtr
 }
@@ -49,13 +87,49 @@ impl RowBatchReader {
           }
         )
       } else {
-        Err(ArrowError::new(raw_status))
+        Err(ArrowError::from_status(raw_status))
       }
     }
   }
 
-  pub fn read(&self, schema: &Schema) -> RowBatch {
-    RowBatch::from_raw( unsafe { c_api::get_row_batch(self.raw_reader, schema.raw_schema()) } )
+  // Fails if a string array of the batch holds invalid UTF-8
+  pub fn read(&self, schema: &Schema) -> Result<RowBatch, ArrowError> {
+    let batch = RowBatch::from_raw( unsafe { c_api::get_row_batch(self.raw_reader, schema.raw_schema()) } );
+    for i in 0..batch.column_num() {
+      try!(check_strings(&batch.column::<BaseArray>(i)));
+    }
+    Ok(batch)
+  }
+}
+
+// The C++ reader does not validate string values, so every string array, including the nested
+// ones, is checked before the batch is returned
+fn check_strings(array: &BaseArray) -> Result<(), ArrowError> {
+  let share = || unsafe { arr_share(array.raw_array()) };
+  match array.ty() {
+    Ty::STRING if array.len() > 0 => {
+      let strings = StringArray::from_raw(share());
+      let data = strings.values();
+      let bytes = buf_bytes(&data);
+      try!(check_offsets(strings.value_offsets(), bytes.len()));
+      check_utf8(strings.value_offsets(), bytes)
+    },
+    Ty::LIST => check_strings(&ListArray::from_raw(share()).values()),
+    Ty::STRUCT => {
+      let array = StructArray::from_raw(share());
+      for i in 0..array.num_columns() {
+        try!(check_strings(&array.column(i)));
+      }
+      Ok(())
+    },
+    Ty::DENSE_UNION | Ty::SPARSE_UNION => {
+      let array = UnionArray::from_raw(share());
+      for i in 0..array.num_children() {
+        try!(check_strings(&array.child(i as i8)));
+      }
+      Ok(())
+    },
+    _ => Ok(())
   }
 }
 
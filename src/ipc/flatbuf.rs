@@ -0,0 +1,391 @@
+// A minimal flatbuffer writer and reader, covering what the Arrow IPC metadata needs: tables of
+// scalars, strings, nested tables, vectors of tables and vectors of structs.
+//
+// The writer lays out the buffer front to back, with every referenced object after its referrer,
+// so that offsets are simply patched once the object is written. The reader checks every offset
+// against the bounds of the buffer, since the metadata comes from files and sockets.
+
+use common::status::ArrowError;
+
+enum Value {
+  Bool(bool),
+  U8(u8),
+  I16(i16),
+  I32(i32),
+  I64(i64),
+  Str(String),
+  Table(TableWriter),
+  Tables(Vec<TableWriter>),
+  // the bytes of count structs, aligned to 8 bytes
+  Structs(Vec<u8>, usize)
+}
+
+impl Value {
+  // Size of the inline part, which is also its alignment
+  fn inline_size(&self) -> usize {
+    match *self {
+      Value::Bool(_) | Value::U8(_) => 1,
+      Value::I16(_) => 2,
+      Value::I32(_) => 4,
+      Value::I64(_) => 8,
+      _ => 4
+    }
+  }
+}
+
+// A table to be written, whose fields are indexed by their ids in the schema
+pub struct TableWriter {
+  fields: Vec<Option<Value>>
+}
+
+impl TableWriter {
+  pub fn new() -> TableWriter {
+    TableWriter {
+      fields: Vec::new()
+    }
+  }
+
+  fn set(&mut self, id: usize, value: Value) -> &mut TableWriter {
+    while self.fields.len() <= id {
+      self.fields.push(None);
+    }
+    self.fields[id] = Some(value);
+    self
+  }
+
+  pub fn add_bool(&mut self, id: usize, value: bool) -> &mut TableWriter {
+    self.set(id, Value::Bool(value))
+  }
+
+  pub fn add_u8(&mut self, id: usize, value: u8) -> &mut TableWriter {
+    self.set(id, Value::U8(value))
+  }
+
+  pub fn add_i16(&mut self, id: usize, value: i16) -> &mut TableWriter {
+    self.set(id, Value::I16(value))
+  }
+
+  pub fn add_i32(&mut self, id: usize, value: i32) -> &mut TableWriter {
+    self.set(id, Value::I32(value))
+  }
+
+  pub fn add_i64(&mut self, id: usize, value: i64) -> &mut TableWriter {
+    self.set(id, Value::I64(value))
+  }
+
+  pub fn add_str(&mut self, id: usize, value: &str) -> &mut TableWriter {
+    self.set(id, Value::Str(String::from(value)))
+  }
+
+  pub fn add_table(&mut self, id: usize, table: TableWriter) -> &mut TableWriter {
+    self.set(id, Value::Table(table))
+  }
+
+  pub fn add_tables(&mut self, id: usize, tables: Vec<TableWriter>) -> &mut TableWriter {
+    self.set(id, Value::Tables(tables))
+  }
+
+  // bytes holds count structs whose alignment is at most 8 bytes
+  pub fn add_structs(&mut self, id: usize, bytes: Vec<u8>, count: usize) -> &mut TableWriter {
+    self.set(id, Value::Structs(bytes, count))
+  }
+
+  // Serializes the table as the root of a new buffer, padded to a multiple of 8 bytes
+  pub fn finish(&self) -> Vec<u8> {
+    let mut writer = Writer {
+      buf: vec![0; 4]
+    };
+    let root = writer.write_table(self);
+    writer.patch_offset(0, root);
+    writer.align(8);
+    writer.buf
+  }
+}
+
+struct Writer {
+  buf: Vec<u8>
+}
+
+impl Writer {
+  fn align(&mut self, alignment: usize) {
+    while self.buf.len() % alignment != 0 {
+      self.buf.push(0);
+    }
+  }
+
+  fn push_u16(&mut self, value: u16) {
+    self.buf.extend_from_slice(&value.to_le_bytes());
+  }
+
+  fn push_u32(&mut self, value: u32) {
+    self.buf.extend_from_slice(&value.to_le_bytes());
+  }
+
+  fn put(&mut self, pos: usize, bytes: &[u8]) {
+    self.buf[pos..pos + bytes.len()].copy_from_slice(bytes);
+  }
+
+  // Offsets are relative to their own position and always point forward
+  fn patch_offset(&mut self, pos: usize, target: usize) {
+    let offset = (target - pos) as u32;
+    self.put(pos, &offset.to_le_bytes());
+  }
+
+  fn write_table(&mut self, table: &TableWriter) -> usize {
+    // lay out the inline fields after the vtable offset, largest first to minimize the padding
+    let mut ids = (0..table.fields.len()).filter(|id| table.fields[*id].is_some()).collect::<Vec<usize>>();
+    ids.sort_by_key(|id| -(table.fields[*id].as_ref().unwrap().inline_size() as i32));
+    let mut field_offsets = vec![0u16; table.fields.len()];
+    let mut table_size = 4;
+    for id in &ids {
+      let size = table.fields[*id].as_ref().unwrap().inline_size();
+      table_size = (table_size + size - 1) / size * size;
+      field_offsets[*id] = table_size as u16;
+      table_size += size;
+    }
+
+    self.align(2);
+    let vtable_pos = self.buf.len();
+    self.push_u16((4 + 2 * table.fields.len()) as u16);
+    self.push_u16(table_size as u16);
+    for offset in &field_offsets {
+      self.push_u16(*offset);
+    }
+
+    // the table starts at a multiple of 8, so that aligning fields within it aligns them in the buffer
+    self.align(8);
+    let table_pos = self.buf.len();
+    self.buf.resize(table_pos + table_size, 0);
+    let vtable_offset = (table_pos - vtable_pos) as i32;
+    self.put(table_pos, &vtable_offset.to_le_bytes());
+
+    let mut children = Vec::new();
+    for id in &ids {
+      let pos = table_pos + field_offsets[*id] as usize;
+      match *table.fields[*id].as_ref().unwrap() {
+        Value::Bool(v) => self.put(pos, &[v as u8]),
+        Value::U8(v) => self.put(pos, &[v]),
+        Value::I16(v) => self.put(pos, &v.to_le_bytes()),
+        Value::I32(v) => self.put(pos, &v.to_le_bytes()),
+        Value::I64(v) => self.put(pos, &v.to_le_bytes()),
+        ref value => children.push((pos, value))
+      }
+    }
+
+    for (pos, value) in children {
+      let target = self.write_object(value);
+      self.patch_offset(pos, target);
+    }
+    table_pos
+  }
+
+  fn write_object(&mut self, value: &Value) -> usize {
+    match *value {
+      Value::Str(ref s) => {
+        self.align(4);
+        let pos = self.buf.len();
+        self.push_u32(s.len() as u32);
+        self.buf.extend_from_slice(s.as_bytes());
+        self.buf.push(0);
+        pos
+      },
+      Value::Table(ref table) => self.write_table(table),
+      Value::Tables(ref tables) => {
+        self.align(4);
+        let pos = self.buf.len();
+        self.push_u32(tables.len() as u32);
+        for _ in tables {
+          self.push_u32(0);
+        }
+        for (i, table) in tables.iter().enumerate() {
+          let target = self.write_table(table);
+          self.patch_offset(pos + 4 + 4 * i, target);
+        }
+        pos
+      },
+      Value::Structs(ref bytes, count) => {
+        // the elements follow the length, and must start at a multiple of 8
+        self.align(8);
+        self.push_u32(0);
+        let pos = self.buf.len();
+        self.push_u32(count as u32);
+        self.buf.extend_from_slice(bytes);
+        pos
+      },
+      _ => unreachable!()
+    }
+  }
+}
+
+fn out_of_bounds() -> ArrowError {
+  ArrowError::invalid("flatbuffer offset is out of bounds")
+}
+
+fn read_bytes(buf: &[u8], pos: usize, len: usize) -> Result<&[u8], ArrowError> {
+  match pos.checked_add(len) {
+    Some(end) if end <= buf.len() => Ok(&buf[pos..end]),
+    _ => Err(out_of_bounds())
+  }
+}
+
+pub fn read_u16(buf: &[u8], pos: usize) -> Result<u16, ArrowError> {
+  let bytes = try!(read_bytes(buf, pos, 2));
+  Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+pub fn read_u32(buf: &[u8], pos: usize) -> Result<u32, ArrowError> {
+  let bytes = try!(read_bytes(buf, pos, 4));
+  Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+pub fn read_i32(buf: &[u8], pos: usize) -> Result<i32, ArrowError> {
+  Ok(try!(read_u32(buf, pos)) as i32)
+}
+
+pub fn read_i64(buf: &[u8], pos: usize) -> Result<i64, ArrowError> {
+  let bytes = try!(read_bytes(buf, pos, 8));
+  let mut value = [0u8; 8];
+  value.copy_from_slice(bytes);
+  Ok(i64::from_le_bytes(value))
+}
+
+// A table in a serialized buffer
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+  buf: &'a [u8],
+  pos: usize,
+  vtable: usize,
+  vtable_size: usize
+}
+
+impl<'a> Table<'a> {
+  pub fn root(buf: &'a [u8]) -> Result<Table<'a>, ArrowError> {
+    let pos = try!(read_u32(buf, 0)) as usize;
+    Table::at(buf, pos)
+  }
+
+  fn at(buf: &'a [u8], pos: usize) -> Result<Table<'a>, ArrowError> {
+    let vtable = pos as i64 - try!(read_i32(buf, pos)) as i64;
+    if vtable < 0 {
+      return Err(out_of_bounds());
+    }
+    let vtable = vtable as usize;
+    let vtable_size = try!(read_u16(buf, vtable)) as usize;
+    if vtable_size < 4 || vtable_size % 2 != 0 {
+      return Err(ArrowError::invalid("malformed flatbuffer vtable"));
+    }
+    try!(read_bytes(buf, vtable, vtable_size));
+
+    Ok(Table {
+      buf: buf,
+      pos: pos,
+      vtable: vtable,
+      vtable_size: vtable_size
+    })
+  }
+
+  // Position of the field in the buffer, or None if the field is absent
+  fn field_pos(&self, id: usize) -> Option<usize> {
+    let entry = 4 + 2 * id;
+    if entry + 2 > self.vtable_size {
+      return None;
+    }
+    // the vtable was checked to be within the buffer
+    match read_u16(self.buf, self.vtable + entry).unwrap() {
+      0 => None,
+      offset => Some(self.pos + offset as usize)
+    }
+  }
+
+  pub fn get_bool(&self, id: usize, default: bool) -> Result<bool, ArrowError> {
+    match self.field_pos(id) {
+      Some(pos) => Ok(try!(read_bytes(self.buf, pos, 1))[0] != 0),
+      None => Ok(default)
+    }
+  }
+
+  pub fn get_u8(&self, id: usize, default: u8) -> Result<u8, ArrowError> {
+    match self.field_pos(id) {
+      Some(pos) => Ok(try!(read_bytes(self.buf, pos, 1))[0]),
+      None => Ok(default)
+    }
+  }
+
+  pub fn get_i16(&self, id: usize, default: i16) -> Result<i16, ArrowError> {
+    match self.field_pos(id) {
+      Some(pos) => Ok(try!(read_u16(self.buf, pos)) as i16),
+      None => Ok(default)
+    }
+  }
+
+  pub fn get_i32(&self, id: usize, default: i32) -> Result<i32, ArrowError> {
+    match self.field_pos(id) {
+      Some(pos) => read_i32(self.buf, pos),
+      None => Ok(default)
+    }
+  }
+
+  pub fn get_i64(&self, id: usize, default: i64) -> Result<i64, ArrowError> {
+    match self.field_pos(id) {
+      Some(pos) => read_i64(self.buf, pos),
+      None => Ok(default)
+    }
+  }
+
+  // Follows the offset stored in the field
+  fn target(&self, id: usize) -> Result<Option<usize>, ArrowError> {
+    match self.field_pos(id) {
+      Some(pos) => Ok(Some(pos + try!(read_u32(self.buf, pos)) as usize)),
+      None => Ok(None)
+    }
+  }
+
+  pub fn get_str(&self, id: usize) -> Result<Option<&'a str>, ArrowError> {
+    match try!(self.target(id)) {
+      Some(pos) => {
+        let len = try!(read_u32(self.buf, pos)) as usize;
+        let bytes = try!(read_bytes(self.buf, pos + 4, len));
+        match ::std::str::from_utf8(bytes) {
+          Ok(s) => Ok(Some(s)),
+          Err(_) => Err(ArrowError::invalid("flatbuffer string is not valid UTF-8"))
+        }
+      },
+      None => Ok(None)
+    }
+  }
+
+  pub fn get_table(&self, id: usize) -> Result<Option<Table<'a>>, ArrowError> {
+    match try!(self.target(id)) {
+      Some(pos) => Ok(Some(try!(Table::at(self.buf, pos)))),
+      None => Ok(None)
+    }
+  }
+
+  // An absent vector is read as an empty one
+  pub fn get_tables(&self, id: usize) -> Result<Vec<Table<'a>>, ArrowError> {
+    let pos = match try!(self.target(id)) {
+      Some(pos) => pos,
+      None => return Ok(Vec::new())
+    };
+    let len = try!(read_u32(self.buf, pos)) as usize;
+    try!(read_bytes(self.buf, pos + 4, len.saturating_mul(4)));
+
+    let mut tables = Vec::with_capacity(len);
+    for i in 0..len {
+      let elem = pos + 4 + 4 * i;
+      tables.push(try!(Table::at(self.buf, elem + try!(read_u32(self.buf, elem)) as usize)));
+    }
+    Ok(tables)
+  }
+
+  // Returns the bytes of each struct of the given size. An absent vector is read as an empty one.
+  pub fn get_structs(&self, id: usize, size: usize) -> Result<Vec<&'a [u8]>, ArrowError> {
+    let pos = match try!(self.target(id)) {
+      Some(pos) => pos,
+      None => return Ok(Vec::new())
+    };
+    let len = try!(read_u32(self.buf, pos)) as usize;
+    let bytes = try!(read_bytes(self.buf, pos + 4, len.saturating_mul(size)));
+    Ok(bytes.chunks(size).collect())
+  }
+}
\ No newline at end of file
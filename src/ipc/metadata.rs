@@ -0,0 +1,603 @@
+// The flatbuffer metadata of the Arrow IPC format, see format/Schema.fbs, format/Message.fbs and
+// format/File.fbs of the Arrow project
+
+use ty::{DataType, DecimalType, Field, Schema, TemporalType, TimeUnit, Ty, UnionMode};
+use common::status::ArrowError;
+use ipc::flatbuf::{self, Table, TableWriter};
+
+// MetadataVersion.V5
+pub const METADATA_VERSION: i16 = 4;
+
+// MetadataVersion.V4, the oldest version sharing the current layout
+const MIN_METADATA_VERSION: i16 = 3;
+
+// Starts and ends files of the file format
+pub const MAGIC: &[u8] = b"ARROW1";
+
+// Marks the start of an encapsulated message. A zero length after it marks the end of a stream.
+pub const CONTINUATION_MARKER: u32 = 0xFFFFFFFF;
+
+// See MessageHeader
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MessageHeader {
+  Schema = 1,
+  DictionaryBatch = 2,
+  RecordBatch = 3
+}
+
+// See Type
+mod type_ids {
+  pub const NULL: u8 = 1;
+  pub const INT: u8 = 2;
+  pub const FLOATING_POINT: u8 = 3;
+  pub const BINARY: u8 = 4;
+  pub const UTF8: u8 = 5;
+  pub const BOOL: u8 = 6;
+  pub const DECIMAL: u8 = 7;
+  pub const DATE: u8 = 8;
+  pub const TIME: u8 = 9;
+  pub const TIMESTAMP: u8 = 10;
+  pub const LIST: u8 = 12;
+  pub const STRUCT: u8 = 13;
+  pub const UNION: u8 = 14;
+}
+
+// See Precision
+const PRECISION_SINGLE: i16 = 1;
+const PRECISION_DOUBLE: i16 = 2;
+
+// See DateUnit. Date arrays hold int32 days, so only DAY is supported.
+const DATE_UNIT_DAY: i16 = 0;
+const DATE_UNIT_MILLISECOND: i16 = 1;
+
+// See UnionMode
+const UNION_MODE_SPARSE: i16 = 0;
+const UNION_MODE_DENSE: i16 = 1;
+
+// A message read from a buffer, whose header is left to the reader of the matching type
+pub struct Message<'a> {
+  header_type: MessageHeader,
+  header: Table<'a>,
+  body_len: i64
+}
+
+impl<'a> Message<'a> {
+  pub fn read(buf: &'a [u8]) -> Result<Message<'a>, ArrowError> {
+    let message = try!(Table::root(buf));
+    let version = try!(message.get_i16(0, 0));
+    if version < MIN_METADATA_VERSION {
+      return Err(ArrowError::not_implemented(format!("metadata version {} is not supported", version)));
+    }
+
+    let header_type = match try!(message.get_u8(1, 0)) {
+      1 => MessageHeader::Schema,
+      2 => MessageHeader::DictionaryBatch,
+      3 => MessageHeader::RecordBatch,
+      ty => return Err(ArrowError::not_implemented(format!("message header {} is not supported", ty)))
+    };
+    let header = match try!(message.get_table(2)) {
+      Some(header) => header,
+      None => return Err(ArrowError::invalid("message has no header"))
+    };
+    let body_len = try!(message.get_i64(3, 0));
+    if body_len < 0 {
+      return Err(ArrowError::invalid("message has a negative body length"));
+    }
+
+    Ok(Message {
+      header_type: header_type,
+      header: header,
+      body_len: body_len
+    })
+  }
+
+  pub fn header_type(&self) -> MessageHeader {
+    self.header_type
+  }
+
+  pub fn header(&self) -> Table<'a> {
+    self.header
+  }
+
+  pub fn body_len(&self) -> i64 {
+    self.body_len
+  }
+}
+
+pub fn write_message(header_type: MessageHeader, header: TableWriter, body_len: i64) -> Vec<u8> {
+  let mut message = TableWriter::new();
+  message.add_i16(0, METADATA_VERSION)
+    .add_u8(1, header_type as u8)
+    .add_table(2, header)
+    .add_i64(3, body_len);
+  message.finish()
+}
+
+// Prefixes the metadata with the continuation marker and its length. The metadata is already
+// padded to 8 bytes, so the body that follows stays aligned.
+pub fn encapsulate(metadata: &[u8]) -> Vec<u8> {
+  let mut buf = Vec::with_capacity(8 + metadata.len());
+  buf.extend_from_slice(&CONTINUATION_MARKER.to_le_bytes());
+  buf.extend_from_slice(&(metadata.len() as i32).to_le_bytes());
+  buf.extend_from_slice(metadata);
+  buf
+}
+
+// A top-level column whose row batches hold integer keys into a dictionary of values. The values
+// are sent in dictionary batches with the id, which several columns may share.
+//
+// The libarrow DataType has no dictionary type, so the schema column keeps the type of its keys and
+// the dictionaries are described beside the schema. native::common::ty models dictionaries in the
+// type itself (Field::with_dic, DataType::Dictionary) but is not used by the ipc readers and writers.
+// DictionaryArray pairs a batch column with its values on both sides.
+pub struct DictionaryField {
+  pub column: i32,
+  pub id: i64,
+  pub value_type: DataType
+}
+
+// Serializes the schema as a Schema message
+pub fn schema_to_message(schema: &Schema) -> Result<Vec<u8>, ArrowError> {
+  let header = try!(schema_to_fb(schema, &[]));
+  Ok(write_message(MessageHeader::Schema, header, 0))
+}
+
+// Reads a Schema message. Dictionary-encoded columns have the type of their keys.
+pub fn schema_from_message(buf: &[u8]) -> Result<Schema, ArrowError> {
+  let message = try!(Message::read(buf));
+  if message.header_type() != MessageHeader::Schema {
+    return Err(ArrowError::invalid(format!("expected a schema message, but got {:?}", message.header_type())));
+  }
+  let (schema, _) = try!(schema_from_fb(message.header()));
+  Ok(schema)
+}
+
+// Checks that the dictionary fields are integer columns of the schema, and that columns sharing a
+// dictionary have the same value type
+pub fn check_dictionaries(schema: &Schema, dictionaries: &[DictionaryField]) -> Result<(), ArrowError> {
+  for (i, dictionary) in dictionaries.iter().enumerate() {
+    if dictionary.column < 0 || dictionary.column >= schema.len() {
+      return Err(ArrowError::invalid(format!("the schema has no column {}", dictionary.column)));
+    }
+    match schema.field(dictionary.column).data_type().ty() {
+      Ty::UINT8 | Ty::INT8 | Ty::UINT16 | Ty::INT16 | Ty::UINT32 | Ty::INT32 | Ty::UINT64 | Ty::INT64 => {},
+      _ => return Err(ArrowError::type_error(format!("dictionary keys of column {} must be integers", dictionary.column)))
+    }
+    for other in &dictionaries[..i] {
+      if other.column == dictionary.column {
+        return Err(ArrowError::invalid(format!("column {} has several dictionaries", dictionary.column)));
+      }
+      if other.id == dictionary.id && other.value_type != dictionary.value_type {
+        return Err(ArrowError::type_error(format!("dictionary {} has several value types", dictionary.id)));
+      }
+    }
+  }
+  Ok(())
+}
+
+pub fn schema_to_fb(schema: &Schema, dictionaries: &[DictionaryField]) -> Result<TableWriter, ArrowError> {
+  try!(check_dictionaries(schema, dictionaries));
+
+  let mut fields = Vec::with_capacity(schema.len() as usize);
+  for i in 0..schema.len() {
+    let field = schema.field(i);
+    let table = match dictionaries.iter().find(|dictionary| dictionary.column == i) {
+      Some(dictionary) => {
+        // the field has the type of the values, and the keys are described by the encoding
+        let (_, index_type, _) = try!(type_to_fb(&field.data_type()));
+        let mut encoding = TableWriter::new();
+        encoding.add_i64(0, dictionary.id)
+          .add_table(1, index_type)
+          .add_bool(2, false);
+        try!(field_to_fb(&field.name(), field.nullable(), &dictionary.value_type, Some(encoding)))
+      },
+      None => try!(field_to_fb(&field.name(), field.nullable(), &field.data_type(), None))
+    };
+    fields.push(table);
+  }
+
+  let mut table = TableWriter::new();
+  // Endianness.Little
+  table.add_i16(0, 0)
+    .add_tables(1, fields);
+  Ok(table)
+}
+
+// Returns the schema, in which dictionary-encoded columns have the type of their keys, and the
+// dictionary fields
+pub fn schema_from_fb(table: Table) -> Result<(Schema, Vec<DictionaryField>), ArrowError> {
+  if try!(table.get_i16(0, 0)) != 0 {
+    return Err(ArrowError::not_implemented("big-endian data is not supported"));
+  }
+
+  let mut fields = Vec::new();
+  let mut dictionaries = Vec::new();
+  for (i, field) in try!(table.get_tables(1)).into_iter().enumerate() {
+    match try!(field.get_table(4)) {
+      Some(encoding) => {
+        let name = try!(field.get_str(0)).unwrap_or("");
+        let index_type = match try!(encoding.get_table(1)) {
+          Some(index_type) => try!(int_type_from_fb(name, Some(index_type))),
+          // keys are int32 unless stated otherwise
+          None => DataType::new_primitive(Ty::INT32)
+        };
+        let value_field = try!(field_from_fb(field, false));
+        fields.push(Field::new(String::from(name), &index_type, value_field.nullable()));
+        dictionaries.push(DictionaryField {
+          column: i as i32,
+          id: try!(encoding.get_i64(0, 0)),
+          value_type: value_field.data_type()
+        });
+      },
+      None => fields.push(try!(field_from_fb(field, true)))
+    }
+  }
+  let field_refs = fields.iter().collect::<Vec<&Field>>();
+  Ok((Schema::new(&field_refs), dictionaries))
+}
+
+// Returns the type id, the type table and the children of the type
+fn type_to_fb(data_type: &DataType) -> Result<(u8, TableWriter, Vec<TableWriter>), ArrowError> {
+  let mut type_table = TableWriter::new();
+  let mut children = Vec::new();
+
+  let type_id = match data_type.ty() {
+    Ty::NA => type_ids::NULL,
+    Ty::BOOL => type_ids::BOOL,
+    Ty::UINT8 => { type_table.add_i32(0, 8).add_bool(1, false); type_ids::INT },
+    Ty::INT8 => { type_table.add_i32(0, 8).add_bool(1, true); type_ids::INT },
+    Ty::UINT16 => { type_table.add_i32(0, 16).add_bool(1, false); type_ids::INT },
+    Ty::INT16 => { type_table.add_i32(0, 16).add_bool(1, true); type_ids::INT },
+    Ty::UINT32 => { type_table.add_i32(0, 32).add_bool(1, false); type_ids::INT },
+    Ty::INT32 => { type_table.add_i32(0, 32).add_bool(1, true); type_ids::INT },
+    Ty::UINT64 => { type_table.add_i32(0, 64).add_bool(1, false); type_ids::INT },
+    Ty::INT64 => { type_table.add_i32(0, 64).add_bool(1, true); type_ids::INT },
+    Ty::FLOAT => { type_table.add_i16(0, PRECISION_SINGLE); type_ids::FLOATING_POINT },
+    Ty::DOUBLE => { type_table.add_i16(0, PRECISION_DOUBLE); type_ids::FLOATING_POINT },
+    Ty::BINARY => type_ids::BINARY,
+    Ty::STRING => type_ids::UTF8,
+    Ty::DATE | Ty::TIMESTAMP | Ty::TIME => match data_type.temporal_type() {
+      Some(TemporalType::Date) => { type_table.add_i16(0, DATE_UNIT_DAY); type_ids::DATE },
+      Some(TemporalType::Timestamp { unit, timezone }) => {
+        type_table.add_i16(0, unit as i16);
+        if let Some(timezone) = timezone {
+          type_table.add_str(1, &timezone);
+        }
+        type_ids::TIMESTAMP
+      },
+      // time values are always int64, whatever the unit
+      Some(TemporalType::Time { unit }) => { type_table.add_i16(0, unit as i16).add_i32(1, 64); type_ids::TIME },
+      None => return Err(ArrowError::invalid(format!("{} has no time unit", data_type.to_string())))
+    },
+    Ty::DECIMAL => match data_type.decimal_type() {
+      Some(decimal_type) => {
+        type_table.add_i32(0, decimal_type.precision())
+          .add_i32(1, decimal_type.scale())
+          .add_i32(2, decimal_type.bit_width());
+        type_ids::DECIMAL
+      },
+      None => return Err(ArrowError::invalid(format!("{} has no precision", data_type.to_string())))
+    },
+    Ty::LIST | Ty::STRUCT | Ty::DENSE_UNION | Ty::SPARSE_UNION => {
+      for i in 0..data_type.num_children() {
+        let child = data_type.child(i);
+        children.push(try!(field_to_fb(&child.name(), child.nullable(), &child.data_type(), None)));
+      }
+      match data_type.ty() {
+        Ty::LIST => type_ids::LIST,
+        Ty::STRUCT => type_ids::STRUCT,
+        ty => {
+          let mode = if let Ty::DENSE_UNION = ty { UNION_MODE_DENSE } else { UNION_MODE_SPARSE };
+          // the type id of each child is its position
+          let mut ids = Vec::with_capacity(4 * children.len());
+          for i in 0..children.len() as i32 {
+            ids.extend_from_slice(&i.to_le_bytes());
+          }
+          type_table.add_i16(0, mode)
+            .add_structs(1, ids, children.len());
+          type_ids::UNION
+        }
+      }
+    },
+    _ => return Err(ArrowError::not_implemented(format!("{} cannot be written to IPC metadata", data_type.to_string())))
+  };
+  Ok((type_id, type_table, children))
+}
+
+fn field_to_fb(name: &str, nullable: bool, data_type: &DataType, dictionary: Option<TableWriter>) -> Result<TableWriter, ArrowError> {
+  let (type_id, type_table, children) = try!(type_to_fb(data_type));
+
+  let mut table = TableWriter::new();
+  table.add_str(0, name)
+    .add_bool(1, nullable)
+    .add_u8(2, type_id)
+    .add_table(3, type_table)
+    .add_tables(5, children);
+  if let Some(dictionary) = dictionary {
+    table.add_table(4, dictionary);
+  }
+  Ok(table)
+}
+
+fn int_type_from_fb(name: &str, type_table: Option<Table>) -> Result<DataType, ArrowError> {
+  let (bit_width, signed) = match type_table {
+    Some(t) => (try!(t.get_i32(0, 0)), try!(t.get_bool(1, false))),
+    None => (0, false)
+  };
+  let ty = match (bit_width, signed) {
+    (8, false) => Ty::UINT8,
+    (8, true) => Ty::INT8,
+    (16, false) => Ty::UINT16,
+    (16, true) => Ty::INT16,
+    (32, false) => Ty::UINT32,
+    (32, true) => Ty::INT32,
+    (64, false) => Ty::UINT64,
+    (64, true) => Ty::INT64,
+    _ => return Err(ArrowError::invalid(format!("field {} has an invalid integer width {}", name, bit_width)))
+  };
+  Ok(DataType::new_primitive(ty))
+}
+
+fn time_unit_from_fb(name: &str, unit: i16) -> Result<TimeUnit, ArrowError> {
+  match unit {
+    0 => Ok(TimeUnit::SECOND),
+    1 => Ok(TimeUnit::MILLI),
+    2 => Ok(TimeUnit::MICRO),
+    3 => Ok(TimeUnit::NANO),
+    _ => Err(ArrowError::invalid(format!("field {} has an invalid time unit {}", name, unit)))
+  }
+}
+
+// Reads the field with the type of its values. Only top-level fields may be dictionary-encoded.
+fn field_from_fb(table: Table, check_dictionary: bool) -> Result<Field, ArrowError> {
+  let name = try!(table.get_str(0)).unwrap_or("");
+  let nullable = try!(table.get_bool(1, false));
+  let type_id = try!(table.get_u8(2, 0));
+  let type_table = try!(table.get_table(3));
+  let mut children = Vec::new();
+  for child in try!(table.get_tables(5)) {
+    children.push(try!(field_from_fb(child, true)));
+  }
+
+  if check_dictionary && try!(table.get_table(4)).is_some() {
+    return Err(ArrowError::not_implemented(format!("nested field {} is dictionary-encoded", name)));
+  }
+
+  let data_type = match type_id {
+    type_ids::NULL => DataType::new_primitive(Ty::NA),
+    type_ids::BOOL => DataType::new_primitive(Ty::BOOL),
+    type_ids::INT => try!(int_type_from_fb(name, type_table)),
+    type_ids::FLOATING_POINT => {
+      let precision = match type_table {
+        Some(t) => try!(t.get_i16(0, 0)),
+        None => 0
+      };
+      match precision {
+        PRECISION_SINGLE => DataType::new_primitive(Ty::FLOAT),
+        PRECISION_DOUBLE => DataType::new_primitive(Ty::DOUBLE),
+        _ => return Err(ArrowError::not_implemented(format!("field {} is a half-precision float", name)))
+      }
+    },
+    type_ids::BINARY => DataType::new_binary(),
+    type_ids::UTF8 => DataType::new_string(),
+    type_ids::DATE => {
+      let unit = match type_table {
+        Some(t) => try!(t.get_i16(0, DATE_UNIT_MILLISECOND)),
+        None => DATE_UNIT_MILLISECOND
+      };
+      if unit != DATE_UNIT_DAY {
+        return Err(ArrowError::not_implemented(format!("field {} holds dates in milliseconds", name)));
+      }
+      try!(TemporalType::Date.data_type())
+    },
+    type_ids::TIMESTAMP => {
+      let (unit, timezone) = match type_table {
+        Some(t) => (try!(t.get_i16(0, 0)), try!(t.get_str(1)).map(String::from)),
+        None => (0, None)
+      };
+      try!(try!(TemporalType::timestamp(try!(time_unit_from_fb(name, unit)), timezone)).data_type())
+    },
+    type_ids::TIME => {
+      let (unit, bit_width) = match type_table {
+        Some(t) => (try!(t.get_i16(0, 1)), try!(t.get_i32(1, 32))),
+        None => (1, 32)
+      };
+      if bit_width != 64 {
+        return Err(ArrowError::not_implemented(format!("field {} holds {}-bit times", name, bit_width)));
+      }
+      try!(TemporalType::Time { unit: try!(time_unit_from_fb(name, unit)) }.data_type())
+    },
+    type_ids::DECIMAL => {
+      let (precision, scale, bit_width) = match type_table {
+        Some(t) => (try!(t.get_i32(0, 0)), try!(t.get_i32(1, 0)), try!(t.get_i32(2, 128))),
+        None => (0, 0, 128)
+      };
+      if bit_width != 128 {
+        return Err(ArrowError::not_implemented(format!("field {} holds {}-bit decimals", name, bit_width)));
+      }
+      try!(DecimalType::new(precision, scale)).data_type()
+    },
+    type_ids::LIST => {
+      if children.len() != 1 {
+        return Err(ArrowError::invalid(format!("list field {} must have exactly one child", name)));
+      }
+      DataType::new_list_with_field(&children[0])
+    },
+    type_ids::STRUCT => DataType::new_struct(children.len() as i32, &children),
+    type_ids::UNION => {
+      let (mode, ids) = match type_table {
+        Some(t) => (try!(t.get_i16(0, UNION_MODE_SPARSE)), try!(t.get_structs(1, 4))),
+        None => (UNION_MODE_SPARSE, Vec::new())
+      };
+      // the type id of each child must be its position, as in the union types of libarrow
+      if !ids.is_empty() {
+        if ids.len() != children.len() {
+          return Err(ArrowError::invalid(format!("union field {} must have one type id per child", name)));
+        }
+        for (i, id) in ids.into_iter().enumerate() {
+          if try!(flatbuf::read_i32(id, 0)) != i as i32 {
+            return Err(ArrowError::not_implemented(format!("union field {} has type ids other than its child positions", name)));
+          }
+        }
+      }
+      match mode {
+        UNION_MODE_SPARSE => DataType::new_union(&children, UnionMode::SPARSE),
+        UNION_MODE_DENSE => DataType::new_union(&children, UnionMode::DENSE),
+        _ => return Err(ArrowError::invalid(format!("union field {} has an invalid mode {}", name, mode)))
+      }
+    },
+    _ => return Err(ArrowError::not_implemented(format!("field {} has an unsupported type {}", name, type_id)))
+  };
+
+  Ok(Field::new(String::from(name), &data_type, nullable))
+}
+
+// Length and null count of an array in a record batch, see FieldNode
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FieldNode {
+  pub length: i64,
+  pub null_count: i64
+}
+
+// Location of a buffer within the message body, see Buffer
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BufferRegion {
+  pub offset: i64,
+  pub length: i64
+}
+
+fn push_i64_pair(bytes: &mut Vec<u8>, first: i64, second: i64) {
+  bytes.extend_from_slice(&first.to_le_bytes());
+  bytes.extend_from_slice(&second.to_le_bytes());
+}
+
+pub fn record_batch_to_fb(length: i64, nodes: &[FieldNode], buffers: &[BufferRegion]) -> TableWriter {
+  let mut node_bytes = Vec::with_capacity(16 * nodes.len());
+  for node in nodes {
+    push_i64_pair(&mut node_bytes, node.length, node.null_count);
+  }
+  let mut buffer_bytes = Vec::with_capacity(16 * buffers.len());
+  for buffer in buffers {
+    push_i64_pair(&mut buffer_bytes, buffer.offset, buffer.length);
+  }
+
+  let mut table = TableWriter::new();
+  table.add_i64(0, length)
+    .add_structs(1, node_bytes, nodes.len())
+    .add_structs(2, buffer_bytes, buffers.len());
+  table
+}
+
+// Returns the number of rows, the field nodes and the buffers of a RecordBatch
+pub fn record_batch_from_fb(table: Table) -> Result<(i64, Vec<FieldNode>, Vec<BufferRegion>), ArrowError> {
+  let length = try!(table.get_i64(0, 0));
+  if try!(table.get_table(3)).is_some() {
+    return Err(ArrowError::not_implemented("compressed record batches are not supported"));
+  }
+
+  let mut nodes = Vec::new();
+  for node in try!(table.get_structs(1, 16)) {
+    nodes.push(FieldNode {
+      length: try!(flatbuf::read_i64(node, 0)),
+      null_count: try!(flatbuf::read_i64(node, 8))
+    });
+  }
+  let mut buffers = Vec::new();
+  for buffer in try!(table.get_structs(2, 16)) {
+    buffers.push(BufferRegion {
+      offset: try!(flatbuf::read_i64(buffer, 0)),
+      length: try!(flatbuf::read_i64(buffer, 8))
+    });
+  }
+  Ok((length, nodes, buffers))
+}
+
+pub fn dictionary_batch_to_fb(id: i64, data: TableWriter, is_delta: bool) -> TableWriter {
+  let mut table = TableWriter::new();
+  table.add_i64(0, id)
+    .add_table(1, data)
+    .add_bool(2, is_delta);
+  table
+}
+
+// Returns the id, the RecordBatch holding the values and whether the values extend the dictionary
+pub fn dictionary_batch_from_fb<'a>(table: Table<'a>) -> Result<(i64, Table<'a>, bool), ArrowError> {
+  let data = match try!(table.get_table(1)) {
+    Some(data) => data,
+    None => return Err(ArrowError::invalid("dictionary batch has no data"))
+  };
+  Ok((try!(table.get_i64(0, 0)), data, try!(table.get_bool(2, false))))
+}
+
+// Number of bits per value of fixed-width types
+pub fn bit_width(ty: &Ty) -> Option<usize> {
+  match *ty {
+    Ty::BOOL => Some(1),
+    Ty::UINT8 | Ty::INT8 => Some(8),
+    Ty::UINT16 | Ty::INT16 => Some(16),
+    Ty::UINT32 | Ty::INT32 | Ty::FLOAT => Some(32),
+    Ty::UINT64 | Ty::INT64 | Ty::DOUBLE => Some(64),
+    Ty::DATE => Some(32),
+    Ty::TIMESTAMP | Ty::TIME => Some(64),
+    Ty::DECIMAL => Some(128),
+    _ => None
+  }
+}
+
+// Location of a message in a file, see Block
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Block {
+  pub offset: i64,
+  // includes the prefix and the padding of the message
+  pub metadata_len: i32,
+  pub body_len: i64
+}
+
+fn blocks_to_bytes(blocks: &[Block]) -> Vec<u8> {
+  let mut bytes = Vec::with_capacity(24 * blocks.len());
+  for block in blocks {
+    bytes.extend_from_slice(&block.offset.to_le_bytes());
+    bytes.extend_from_slice(&block.metadata_len.to_le_bytes());
+    bytes.extend_from_slice(&[0; 4]);
+    bytes.extend_from_slice(&block.body_len.to_le_bytes());
+  }
+  bytes
+}
+
+fn blocks_from_fb(table: &Table, id: usize) -> Result<Vec<Block>, ArrowError> {
+  let mut blocks = Vec::new();
+  for block in try!(table.get_structs(id, 24)) {
+    blocks.push(Block {
+      offset: try!(flatbuf::read_i64(block, 0)),
+      metadata_len: try!(flatbuf::read_i32(block, 8)),
+      body_len: try!(flatbuf::read_i64(block, 16))
+    });
+  }
+  Ok(blocks)
+}
+
+// Serializes the footer of the file format, see Footer
+pub fn footer_to_bytes(schema: &Schema, dictionary_fields: &[DictionaryField], dictionaries: &[Block],
+                       batches: &[Block]) -> Result<Vec<u8>, ArrowError> {
+  let mut footer = TableWriter::new();
+  footer.add_i16(0, METADATA_VERSION)
+    .add_table(1, try!(schema_to_fb(schema, dictionary_fields)))
+    .add_structs(2, blocks_to_bytes(dictionaries), dictionaries.len())
+    .add_structs(3, blocks_to_bytes(batches), batches.len());
+  Ok(footer.finish())
+}
+
+// Returns the schema, the dictionary fields, the dictionary blocks and the record batch blocks of a footer
+pub fn footer_from_bytes(buf: &[u8]) -> Result<(Schema, Vec<DictionaryField>, Vec<Block>, Vec<Block>), ArrowError> {
+  let footer = try!(Table::root(buf));
+  let version = try!(footer.get_i16(0, 0));
+  if version < MIN_METADATA_VERSION {
+    return Err(ArrowError::not_implemented(format!("metadata version {} is not supported", version)));
+  }
+
+  let (schema, dictionary_fields) = match try!(footer.get_table(1)) {
+    Some(schema) => try!(schema_from_fb(schema)),
+    None => return Err(ArrowError::invalid("footer has no schema"))
+  };
+  Ok((schema, dictionary_fields, try!(blocks_from_fb(&footer, 2)), try!(blocks_from_fb(&footer, 3))))
+}
\ No newline at end of file
@@ -1 +1,6 @@
-pub mod adapter;
\ No newline at end of file
+pub mod adapter;
+pub mod metadata;
+pub mod reader;
+pub mod writer;
+
+mod flatbuf;
\ No newline at end of file
@@ -0,0 +1,669 @@
+use array::{Array, BaseArray, RawArrayPtr, arr_null_bitmap_data, arr_share, new_null_arr, check_offsets, check_utf8};
+use bitmap::{Bitmap, get_bit};
+use buffer::{Buf, Buffer, RawBufferPtr};
+use table::RowBatch;
+use ty::{DataType, RawDataTypePtr, Schema, Ty};
+use types::primitive;
+use types::primitive::{I8Array, I32Array, arr_data};
+use types::string::{binary_arr_data, binary_arr_raw_offsets, new_binary_arr, new_string_arr};
+use types::list::new_list_arr;
+use types::structs::new_struct_arr;
+use types::union::UnionArray;
+use types::decimal::new_decimal_arr;
+use types::dictionary::{DictionaryArray, KeyArray};
+use common::status::ArrowError;
+use ipc::flatbuf::{self, Table};
+use ipc::metadata;
+use ipc::metadata::{Message, MessageHeader, FieldNode, BufferRegion, Block, DictionaryField, CONTINUATION_MARKER};
+use ipc::writer::{raw_bytes, raw_offsets};
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::i32;
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
+use std::ptr;
+use std::slice;
+
+// Reads 4 bytes, or returns None if the reader is at its end
+fn read_u32<R: Read>(reader: &mut R) -> Result<Option<u32>, ArrowError> {
+  let mut buf = [0u8; 4];
+  let mut read = 0;
+  while read < buf.len() {
+    match reader.read(&mut buf[read..]) {
+      Ok(0) => break,
+      Ok(n) => read += n,
+      Err(ref e) if e.kind() == ErrorKind::Interrupted => {},
+      Err(e) => return Err(ArrowError::from(e))
+    }
+  }
+
+  match read {
+    0 => Ok(None),
+    4 => Ok(Some(try!(flatbuf::read_u32(&buf, 0)))),
+    _ => Err(ArrowError::io_error("unexpected end of the message prefix"))
+  }
+}
+
+// Reads exactly len bytes, without trusting len for the allocation
+fn read_bytes<R: Read>(reader: &mut R, len: i64) -> Result<Vec<u8>, ArrowError> {
+  let mut bytes = Vec::new();
+  try!(reader.take(len as u64).read_to_end(&mut bytes));
+  if (bytes.len() as i64) < len {
+    return Err(ArrowError::io_error("unexpected end of the message"));
+  }
+  Ok(bytes)
+}
+
+// Reads the next message and its body. Returns None at the end-of-stream marker or at the end of
+// the reader. Messages prefixed only with their length, as written before Arrow 0.15, are read too.
+pub fn read_message<R: Read>(reader: &mut R) -> Result<Option<(Vec<u8>, Vec<u8>)>, ArrowError> {
+  let len = match try!(read_u32(reader)) {
+    Some(CONTINUATION_MARKER) => match try!(read_u32(reader)) {
+      Some(len) => len as i32,
+      None => return Err(ArrowError::io_error("unexpected end of the message prefix"))
+    },
+    Some(len) => len as i32,
+    None => return Ok(None)
+  };
+  if len == 0 {
+    return Ok(None);
+  }
+  if len < 0 {
+    return Err(ArrowError::invalid("message has a negative length"));
+  }
+
+  let message = try!(read_bytes(reader, len as i64));
+  let body_len = try!(Message::read(&message)).body_len();
+  let body = try!(read_bytes(reader, body_len));
+  Ok(Some((message, body)))
+}
+
+fn to_buf(bytes: &[u8]) -> Buf {
+  Buf::from(bytes.to_vec())
+}
+
+fn raw_buf(buf: &Option<Buf>) -> RawBufferPtr {
+  match *buf {
+    Some(ref buf) => buf.raw_buf(),
+    None => ptr::null()
+  }
+}
+
+// Rebuilds arrays from the buffers of a message body, in the order of the Arrow columnar format
+struct BodyReader<'a> {
+  body: &'a [u8],
+  nodes: Vec<FieldNode>,
+  buffers: Vec<BufferRegion>,
+  next_node: usize,
+  next_buffer: usize
+}
+
+impl<'a> BodyReader<'a> {
+  fn node(&mut self) -> Result<FieldNode, ArrowError> {
+    let node = match self.nodes.get(self.next_node) {
+      Some(node) => *node,
+      None => return Err(ArrowError::invalid("record batch has fewer field nodes than the schema"))
+    };
+    self.next_node += 1;
+    if node.length < 0 || node.length > i32::MAX as i64 || node.null_count < 0 || node.null_count > node.length {
+      return Err(ArrowError::invalid("field node has an invalid length or null count"));
+    }
+    Ok(node)
+  }
+
+  fn buffer(&mut self) -> Result<&'a [u8], ArrowError> {
+    let region = match self.buffers.get(self.next_buffer) {
+      Some(region) => *region,
+      None => return Err(ArrowError::invalid("record batch has fewer buffers than the schema"))
+    };
+    self.next_buffer += 1;
+    if region.offset < 0 || region.length < 0 || region.offset.saturating_add(region.length) > self.body.len() as i64 {
+      return Err(ArrowError::invalid("buffer is out of the message body"));
+    }
+    Ok(&self.body[region.offset as usize..(region.offset + region.length) as usize])
+  }
+
+  fn validity(&mut self, node: &FieldNode) -> Result<Option<Buf>, ArrowError> {
+    let bytes = try!(self.buffer());
+    if node.null_count == 0 {
+      return Ok(None);
+    }
+    let len = (node.length as usize + 7) / 8;
+    if bytes.len() < len {
+      return Err(ArrowError::invalid("validity bitmap is too short"));
+    }
+    Ok(Some(to_buf(&bytes[..len])))
+  }
+
+  fn offsets(&mut self, len: usize) -> Result<Vec<i32>, ArrowError> {
+    let bytes = try!(self.buffer());
+    // the offsets of empty arrays may be omitted
+    if len == 0 && bytes.is_empty() {
+      return Ok(vec![0]);
+    }
+    if bytes.len() < 4 * (len + 1) {
+      return Err(ArrowError::invalid("offsets buffer is too short"));
+    }
+    let mut offsets = Vec::with_capacity(len + 1);
+    for i in 0..len + 1 {
+      offsets.push(try!(flatbuf::read_i32(bytes, 4 * i)));
+    }
+    Ok(offsets)
+  }
+
+  fn array(&mut self, data_type: &DataType) -> Result<BaseArray, ArrowError> {
+    let node = try!(self.node());
+    let len = node.length as usize;
+    let length = node.length as i32;
+    let null_count = node.null_count as i32;
+    let raw_type = data_type.raw_data_type();
+
+    let ty = data_type.ty();
+    match ty {
+      Ty::NA => return Ok(BaseArray::from_raw( unsafe { new_null_arr(raw_type, length) } )),
+      Ty::DENSE_UNION | Ty::SPARSE_UNION => return self.union_array(data_type, &node),
+      _ => {}
+    }
+
+    let null_bitmap = try!(self.validity(&node));
+    let raw_array = match ty {
+      Ty::BINARY | Ty::STRING => {
+        let offsets = try!(self.offsets(len));
+        let data = try!(self.buffer());
+        let data = &data[..try!(check_offsets(&offsets, data.len()))];
+        let is_string = if let Ty::STRING = ty { true } else { false };
+        if is_string {
+          try!(check_utf8(&offsets, data));
+        }
+
+        let offsets = Buf::from(offsets);
+        let data = to_buf(data);
+        unsafe {
+          if is_string {
+            new_string_arr(length, offsets.raw_buf(), data.raw_buf(), null_count, raw_buf(&null_bitmap))
+          } else {
+            new_binary_arr(length, offsets.raw_buf(), data.raw_buf(), null_count, raw_buf(&null_bitmap))
+          }
+        }
+      },
+      Ty::LIST => {
+        let offsets = try!(self.offsets(len));
+        let values = try!(self.array(&data_type.child(0).data_type()));
+        try!(check_offsets(&offsets, values.len() as usize));
+
+        let offsets = Buf::from(offsets);
+        unsafe {
+          new_list_arr(raw_type, length, offsets.raw_buf(), values.raw_array(), null_count, raw_buf(&null_bitmap))
+        }
+      },
+      Ty::STRUCT => {
+        let mut fields = Vec::new();
+        for i in 0..data_type.num_children() {
+          let field = try!(self.array(&data_type.child(i).data_type()));
+          if field.len() != length {
+            return Err(ArrowError::invalid("struct fields must have the length of the struct array"));
+          }
+          fields.push(field);
+        }
+
+        let raw_fields = fields.iter().map(|field| field.raw_array()).collect::<Vec<RawArrayPtr>>();
+        unsafe {
+          new_struct_arr(raw_type, length, raw_fields.as_ptr(), raw_fields.len() as i32, null_count, raw_buf(&null_bitmap))
+        }
+      },
+      ref ty => {
+        let new_arr: unsafe extern "C" fn(RawDataTypePtr, i32, RawBufferPtr, i32, RawBufferPtr) -> RawArrayPtr = match *ty {
+          Ty::BOOL => primitive::new_bool_arr,
+          Ty::UINT8 => primitive::new_u8_arr,
+          Ty::INT8 => primitive::new_i8_arr,
+          Ty::UINT16 => primitive::new_u16_arr,
+          Ty::INT16 => primitive::new_i16_arr,
+          Ty::UINT32 => primitive::new_u32_arr,
+          Ty::INT32 => primitive::new_i32_arr,
+          Ty::UINT64 => primitive::new_u64_arr,
+          Ty::INT64 => primitive::new_i64_arr,
+          Ty::FLOAT => primitive::new_f32_arr,
+          Ty::DOUBLE => primitive::new_f64_arr,
+          // temporal arrays are primitive arrays of their temporal type
+          Ty::DATE => primitive::new_i32_arr,
+          Ty::TIMESTAMP | Ty::TIME => primitive::new_i64_arr,
+          Ty::DECIMAL => new_decimal_arr,
+          _ => {
+            return Err(ArrowError::not_implemented(format!("{} arrays cannot be read from IPC messages",
+                                                           data_type.to_string())));
+          }
+        };
+
+        let data = try!(self.buffer());
+        let data_len = (len * metadata::bit_width(ty).unwrap() + 7) / 8;
+        if data.len() < data_len {
+          return Err(ArrowError::invalid("data buffer is too short"));
+        }
+        let data = to_buf(&data[..data_len]);
+        unsafe { new_arr(raw_type, length, data.raw_buf(), null_count, raw_buf(&null_bitmap)) }
+      }
+    };
+    Ok(BaseArray::from_raw(raw_array))
+  }
+
+  // Unions have no validity bitmap, so every slot is valid. UnionArray checks the type ids and
+  // the value offsets against the children.
+  fn union_array(&mut self, data_type: &DataType, node: &FieldNode) -> Result<BaseArray, ArrowError> {
+    if node.null_count > 0 {
+      return Err(ArrowError::invalid("union arrays cannot have null slots"));
+    }
+    let len = node.length as usize;
+    let length = node.length as i32;
+
+    let type_ids = try!(self.buffer());
+    if type_ids.len() < len {
+      return Err(ArrowError::invalid("type ids buffer is too short"));
+    }
+    let type_ids = to_buf(&type_ids[..len]);
+    let type_ids = I8Array::from_raw( unsafe {
+      primitive::new_i8_arr(DataType::new_primitive(Ty::INT8).raw_data_type(), length, type_ids.raw_buf(), 0, ptr::null())
+    } );
+    let value_offsets = match data_type.ty() {
+      Ty::DENSE_UNION => {
+        let offsets = try!(self.buffer());
+        if offsets.len() < 4 * len {
+          return Err(ArrowError::invalid("value offsets buffer is too short"));
+        }
+        let offsets = to_buf(&offsets[..4 * len]);
+        Some(I32Array::from_raw( unsafe {
+          primitive::new_i32_arr(DataType::new_primitive(Ty::INT32).raw_data_type(), length, offsets.raw_buf(), 0, ptr::null())
+        } ))
+      },
+      _ => None
+    };
+
+    let mut children = Vec::new();
+    for i in 0..data_type.num_children() {
+      children.push(try!(self.array(&data_type.child(i).data_type())));
+    }
+    let array = match value_offsets {
+      Some(value_offsets) => try!(UnionArray::new_dense(data_type, type_ids, value_offsets, children)),
+      None => try!(UnionArray::new_sparse(data_type, type_ids, children))
+    };
+    Ok(BaseArray::from_raw( unsafe { arr_share(array.raw_array()) } ))
+  }
+}
+
+// Rebuilds arrays of the types from a RecordBatch table and the body. Returns the length of the
+// record batch, which every array has.
+fn decode_arrays(types: &[DataType], header: Table, body: &[u8]) -> Result<(i32, Vec<BaseArray>), ArrowError> {
+  let (length, nodes, buffers) = try!(metadata::record_batch_from_fb(header));
+  if length < 0 || length > i32::MAX as i64 {
+    return Err(ArrowError::invalid("record batch has an invalid length"));
+  }
+
+  let mut body_reader = BodyReader {
+    body: body,
+    nodes: nodes,
+    buffers: buffers,
+    next_node: 0,
+    next_buffer: 0
+  };
+  let mut arrays = Vec::with_capacity(types.len());
+  for data_type in types {
+    let array = try!(body_reader.array(data_type));
+    if array.len() as i64 != length {
+      return Err(ArrowError::invalid("columns must have the length of the record batch"));
+    }
+    arrays.push(array);
+  }
+  Ok((length as i32, arrays))
+}
+
+// Rebuilds a row batch of the schema from a RecordBatch message and its body
+pub fn decode_row_batch(schema: &Schema, message: &Message, body: &[u8]) -> Result<RowBatch, ArrowError> {
+  if message.header_type() != MessageHeader::RecordBatch {
+    return Err(ArrowError::invalid(format!("expected a record batch message, but got {:?}", message.header_type())));
+  }
+  let types = (0..schema.len()).map(|i| schema.field(i).data_type()).collect::<Vec<DataType>>();
+  let (length, arrays) = try!(decode_arrays(&types, message.header(), body));
+  Ok(RowBatch::new(schema, length, &arrays))
+}
+
+// Appends the second array to the first. Only the value types of dictionaries whose deltas can be
+// read are supported.
+fn concat_values(first: &BaseArray, second: &BaseArray) -> Result<BaseArray, ArrowError> {
+  let (first_len, second_len) = (first.len() as usize, second.len() as usize);
+  let length = (first_len + second_len) as i32;
+  let null_count = first.null_count() + second.null_count();
+
+  // the validity bitmap can be omitted when there is no null
+  let null_bitmap = if null_count > 0 {
+    let mut bitmap = Bitmap::new(0);
+    for &(array, len) in &[(first, first_len), (second, second_len)] {
+      let bits = unsafe { raw_bytes(arr_null_bitmap_data(array.raw_array()), (len + 7) / 8) };
+      for i in 0..len {
+        bitmap.push(array.null_count() == 0 || get_bit(bits, i));
+      }
+    }
+    Some(bitmap.into_buf())
+  } else {
+    None
+  };
+
+  let data_type = first.data_type();
+  let raw_array = match data_type.ty() {
+    Ty::BINARY | Ty::STRING => {
+      let mut offsets = unsafe { raw_offsets(binary_arr_raw_offsets(first.raw_array()), first_len) };
+      let mut data = Vec::new();
+      let first_data = Buf::from_raw( unsafe { binary_arr_data(first.raw_array()) } );
+      data.extend_from_slice(unsafe { raw_bytes(first_data.data(), offsets[first_len] as usize) });
+
+      let second_offsets = unsafe { raw_offsets(binary_arr_raw_offsets(second.raw_array()), second_len) };
+      let second_data = Buf::from_raw( unsafe { binary_arr_data(second.raw_array()) } );
+      data.extend_from_slice(unsafe { raw_bytes(second_data.data(), second_offsets[second_len] as usize) });
+      let shift = offsets[first_len];
+      offsets.extend(second_offsets[1..].iter().map(|offset| offset + shift));
+
+      let offsets = Buf::from(offsets);
+      let data = Buf::from(data);
+      unsafe {
+        if let Ty::STRING = data_type.ty() {
+          new_string_arr(length, offsets.raw_buf(), data.raw_buf(), null_count, raw_buf(&null_bitmap))
+        } else {
+          new_binary_arr(length, offsets.raw_buf(), data.raw_buf(), null_count, raw_buf(&null_bitmap))
+        }
+      }
+    },
+    Ty::BOOL => {
+      let mut bitmap = Bitmap::new(0);
+      for &(array, len) in &[(first, first_len), (second, second_len)] {
+        let data = Buf::from_raw( unsafe { arr_data(array.raw_array()) } );
+        let bits = unsafe { raw_bytes(data.data(), (len + 7) / 8) };
+        for i in 0..len {
+          bitmap.push(get_bit(bits, i));
+        }
+      }
+      let data = bitmap.into_buf();
+      unsafe { primitive::new_bool_arr(data_type.raw_data_type(), length, data.raw_buf(), null_count, raw_buf(&null_bitmap)) }
+    },
+    ref ty => {
+      let new_arr: unsafe extern "C" fn(RawDataTypePtr, i32, RawBufferPtr, i32, RawBufferPtr) -> RawArrayPtr = match *ty {
+        Ty::UINT8 => primitive::new_u8_arr,
+        Ty::INT8 => primitive::new_i8_arr,
+        Ty::UINT16 => primitive::new_u16_arr,
+        Ty::INT16 => primitive::new_i16_arr,
+        Ty::UINT32 => primitive::new_u32_arr,
+        Ty::INT32 => primitive::new_i32_arr,
+        Ty::UINT64 => primitive::new_u64_arr,
+        Ty::INT64 => primitive::new_i64_arr,
+        Ty::FLOAT => primitive::new_f32_arr,
+        Ty::DOUBLE => primitive::new_f64_arr,
+        _ => {
+          return Err(ArrowError::not_implemented(format!("deltas of {} dictionaries cannot be read",
+                                                         data_type.to_string())));
+        }
+      };
+
+      let width = metadata::bit_width(ty).unwrap() / 8;
+      let mut data = Vec::with_capacity(width * length as usize);
+      for &(array, len) in &[(first, first_len), (second, second_len)] {
+        let buf = Buf::from_raw( unsafe { arr_data(array.raw_array()) } );
+        data.extend_from_slice(unsafe { raw_bytes(buf.data(), width * len) });
+      }
+      let data = Buf::from(data);
+      unsafe { new_arr(data_type.raw_data_type(), length, data.raw_buf(), null_count, raw_buf(&null_bitmap)) }
+    }
+  };
+  Ok(BaseArray::from_raw(raw_array))
+}
+
+// The dictionaries received so far, by id
+struct Dictionaries {
+  fields: Vec<DictionaryField>,
+  values: HashMap<i64, BaseArray>
+}
+
+impl Dictionaries {
+  fn new(fields: Vec<DictionaryField>) -> Dictionaries {
+    Dictionaries {
+      fields: fields,
+      values: HashMap::new()
+    }
+  }
+
+  fn get(&self, id: i64) -> Option<BaseArray> {
+    self.values.get(&id).map(|values| BaseArray::from_raw( unsafe { arr_share(values.raw_array()) } ))
+  }
+
+  // Appends the values to the dictionary if the batch is a delta, and replaces them otherwise.
+  // Files may only extend a dictionary, so a replacement there is an error.
+  fn read_batch(&mut self, message: &Message, body: &[u8], allow_replacement: bool) -> Result<(), ArrowError> {
+    let (id, data, is_delta) = try!(metadata::dictionary_batch_from_fb(message.header()));
+    let (_, mut arrays) = match self.fields.iter().find(|field| field.id == id) {
+      Some(field) => try!(decode_arrays(slice::from_ref(&field.value_type), data, body)),
+      None => return Err(ArrowError::key_error(format!("the schema has no dictionary {}", id)))
+    };
+    let values = arrays.pop().unwrap();
+
+    if is_delta {
+      let merged = match self.values.get(&id) {
+        Some(previous) => try!(concat_values(previous, &values)),
+        None => return Err(ArrowError::invalid(format!("delta of dictionary {} precedes its values", id)))
+      };
+      self.values.insert(id, merged);
+    } else {
+      if !allow_replacement && self.values.contains_key(&id) {
+        return Err(ArrowError::invalid(format!("dictionary {} is replaced, which files do not support", id)));
+      }
+      self.values.insert(id, values);
+    }
+    Ok(())
+  }
+
+  // Pairs the keys in the column of the batch with the current values of their dictionary
+  fn column<K: KeyArray + Any>(&self, batch: &RowBatch, column: i32) -> Result<DictionaryArray<K>, ArrowError> {
+    let id = match self.fields.iter().find(|field| field.column == column) {
+      Some(field) => field.id,
+      None => return Err(ArrowError::key_error(format!("column {} is not dictionary-encoded", column)))
+    };
+    let keys = batch.column::<BaseArray>(column);
+    if !K::accepts(keys.ty()) {
+      return Err(ArrowError::type_error(format!("column {} holds {} keys, which the requested key array cannot hold",
+                                                column, keys.data_type().to_string())));
+    }
+    match self.get(id) {
+      Some(values) => DictionaryArray::new(K::from_raw( unsafe { arr_share(keys.raw_array()) } ), values),
+      None => Err(ArrowError::invalid(format!("dictionary {} has not been read", id)))
+    }
+  }
+
+  fn check_batch(&self) -> Result<(), ArrowError> {
+    match self.fields.iter().find(|field| !self.values.contains_key(&field.id)) {
+      Some(field) => Err(ArrowError::invalid(format!("row batch precedes dictionary {}", field.id))),
+      None => Ok(())
+    }
+  }
+}
+
+// Reads the Arrow streaming format: the schema, and then row batches until the end-of-stream marker
+pub struct StreamReader<R: Read> {
+  reader: R,
+  schema: Schema,
+  dictionaries: Dictionaries,
+  finished: bool
+}
+
+impl<R: Read> StreamReader<R> {
+  // Reads the schema at the start of the stream
+  pub fn open(mut reader: R) -> Result<StreamReader<R>, ArrowError> {
+    let (schema, dictionary_fields) = match try!(read_message(&mut reader)) {
+      Some((message, _)) => try!(read_schema(&message)),
+      None => return Err(ArrowError::invalid("the stream has no schema"))
+    };
+
+    Ok(StreamReader {
+      reader: reader,
+      schema: schema,
+      dictionaries: Dictionaries::new(dictionary_fields),
+      finished: false
+    })
+  }
+
+  // The dictionary-encoded columns have the type of their keys
+  pub fn schema(&self) -> &Schema {
+    &self.schema
+  }
+
+  pub fn dictionary_fields(&self) -> &[DictionaryField] {
+    &self.dictionaries.fields
+  }
+
+  // The values of the dictionary as of the last row batch read
+  pub fn dictionary(&self, id: i64) -> Option<BaseArray> {
+    self.dictionaries.get(id)
+  }
+
+  // The dictionary-encoded column of a batch, with the current values of its dictionary
+  pub fn dictionary_column<K: KeyArray + Any>(&self, batch: &RowBatch, column: i32) -> Result<DictionaryArray<K>, ArrowError> {
+    self.dictionaries.column(batch, column)
+  }
+
+  // Reads the next row batch, or returns None at the end of the stream. The dictionary batches
+  // before it are applied on the way.
+  pub fn read_next(&mut self) -> Result<Option<RowBatch>, ArrowError> {
+    while !self.finished {
+      match try!(read_message(&mut self.reader)) {
+        Some((message, body)) => {
+          let message = try!(Message::read(&message));
+          if message.header_type() == MessageHeader::DictionaryBatch {
+            try!(self.dictionaries.read_batch(&message, &body, true));
+          } else {
+            try!(self.dictionaries.check_batch());
+            return Ok(Some(try!(decode_row_batch(&self.schema, &message, &body))));
+          }
+        },
+        None => self.finished = true
+      }
+    }
+    Ok(None)
+  }
+}
+
+// Iteration stops after the end of the stream or the first error
+impl<R: Read> Iterator for StreamReader<R> {
+  type Item = Result<RowBatch, ArrowError>;
+
+  fn next(&mut self) -> Option<Result<RowBatch, ArrowError>> {
+    match self.read_next() {
+      Ok(Some(batch)) => Some(Ok(batch)),
+      Ok(None) => None,
+      Err(e) => {
+        self.finished = true;
+        Some(Err(e))
+      }
+    }
+  }
+}
+
+fn read_schema(message: &[u8]) -> Result<(Schema, Vec<DictionaryField>), ArrowError> {
+  let message = try!(Message::read(message));
+  if message.header_type() != MessageHeader::Schema {
+    return Err(ArrowError::invalid(format!("expected a schema message, but got {:?}", message.header_type())));
+  }
+  metadata::schema_from_fb(message.header())
+}
+
+// Reads the message and the body located by the block
+fn read_block<R: Read + Seek>(reader: &mut R, block: &Block) -> Result<(Vec<u8>, Vec<u8>), ArrowError> {
+  if block.offset < 0 {
+    return Err(ArrowError::invalid("block has a negative offset"));
+  }
+  try!(reader.seek(SeekFrom::Start(block.offset as u64)));
+  match try!(read_message(reader)) {
+    Some(message) => Ok(message),
+    None => Err(ArrowError::invalid("block points to the end of the stream"))
+  }
+}
+
+// Reads the Arrow file format, whose footer locates the dictionaries and the row batches for
+// random access
+pub struct FileReader<R: Read + Seek> {
+  reader: R,
+  schema: Schema,
+  dictionaries: Dictionaries,
+  batches: Vec<Block>
+}
+
+impl<R: Read + Seek> FileReader<R> {
+  // Checks the magic and reads the footer
+  pub fn open(mut reader: R) -> Result<FileReader<R>, ArrowError> {
+    let magic_len = metadata::MAGIC.len() as i64;
+    let file_len = try!(reader.seek(SeekFrom::End(0))) as i64;
+    // the leading magic is padded to 8 bytes
+    if file_len < 8 + 4 + magic_len {
+      return Err(ArrowError::invalid("the file is too short to be an Arrow file"));
+    }
+
+    try!(reader.seek(SeekFrom::Start(0)));
+    let head = try!(read_bytes(&mut reader, magic_len));
+    try!(reader.seek(SeekFrom::End(-4 - magic_len)));
+    let tail = try!(read_bytes(&mut reader, 4 + magic_len));
+    if &head[..] != metadata::MAGIC || &tail[4..] != metadata::MAGIC {
+      return Err(ArrowError::invalid("the file does not start and end with the Arrow magic"));
+    }
+
+    let footer_len = try!(flatbuf::read_i32(&tail, 0)) as i64;
+    if footer_len <= 0 || footer_len > file_len - 8 - 4 - magic_len {
+      return Err(ArrowError::invalid("the file has an invalid footer length"));
+    }
+    try!(reader.seek(SeekFrom::End(-4 - magic_len - footer_len)));
+    let footer = try!(read_bytes(&mut reader, footer_len));
+    let (schema, dictionary_fields, dictionary_blocks, batches) = try!(metadata::footer_from_bytes(&footer));
+
+    let mut dictionaries = Dictionaries::new(dictionary_fields);
+    for block in &dictionary_blocks {
+      let (message, body) = try!(read_block(&mut reader, block));
+      let message = try!(Message::read(&message));
+      if message.header_type() != MessageHeader::DictionaryBatch {
+        return Err(ArrowError::invalid(format!("expected a dictionary batch message, but got {:?}", message.header_type())));
+      }
+      try!(dictionaries.read_batch(&message, &body, false));
+    }
+    try!(dictionaries.check_batch());
+
+    Ok(FileReader {
+      reader: reader,
+      schema: schema,
+      dictionaries: dictionaries,
+      batches: batches
+    })
+  }
+
+  // The dictionary-encoded columns have the type of their keys
+  pub fn schema(&self) -> &Schema {
+    &self.schema
+  }
+
+  pub fn dictionary_fields(&self) -> &[DictionaryField] {
+    &self.dictionaries.fields
+  }
+
+  pub fn dictionary(&self, id: i64) -> Option<BaseArray> {
+    self.dictionaries.get(id)
+  }
+
+  // The dictionary-encoded column of a batch, with the current values of its dictionary
+  pub fn dictionary_column<K: KeyArray + Any>(&self, batch: &RowBatch, column: i32) -> Result<DictionaryArray<K>, ArrowError> {
+    self.dictionaries.column(batch, column)
+  }
+
+  pub fn num_batches(&self) -> i32 {
+    self.batches.len() as i32
+  }
+
+  pub fn read_batch(&mut self, i: i32) -> Result<RowBatch, ArrowError> {
+    let block = match self.batches.get(i as usize) {
+      Some(block) if i >= 0 => *block,
+      _ => return Err(ArrowError::key_error(format!("the file has no row batch {}", i)))
+    };
+    let (message, body) = try!(read_block(&mut self.reader, &block));
+    let message = try!(Message::read(&message));
+    decode_row_batch(&self.schema, &message, &body)
+  }
+}
\ No newline at end of file
@@ -0,0 +1,487 @@
+use array::{Array, BaseArray, arr_null_bitmap_data};
+use buffer::{Buf, Buffer};
+use table::RowBatch;
+use ty::{Schema, Ty};
+use types::primitive::arr_data;
+use types::string::{binary_arr_raw_offsets, binary_arr_data};
+use types::list::{list_arr_raw_offsets, list_arr_values};
+use types::structs::{struct_arr_num_fields, struct_arr_field};
+use types::decimal::decimal_arr_data;
+use types::union::{union_arr_type_ids, union_arr_value_offsets, union_arr_num_children, union_arr_child};
+use types::dictionary::{DictionaryArray, KeyArray};
+use common::status::ArrowError;
+use io::memory::MemoryMappedSink;
+use ipc::flatbuf::TableWriter;
+use ipc::metadata;
+use ipc::metadata::{MessageHeader, FieldNode, BufferRegion, Block, DictionaryField};
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::slice;
+
+// Buffers in a message body are padded to a multiple of this
+pub const ALIGNMENT: usize = 8;
+
+fn pad(bytes: &mut Vec<u8>) {
+  while bytes.len() % ALIGNMENT != 0 {
+    bytes.push(0);
+  }
+}
+
+pub unsafe fn raw_bytes<'a>(data: *const u8, len: usize) -> &'a [u8] {
+  if len == 0 || data.is_null() {
+    &[]
+  } else {
+    slice::from_raw_parts(data, len)
+  }
+}
+
+// Copies the len + 1 offsets, which may be absent for empty arrays
+pub unsafe fn raw_offsets(offsets: *const i32, len: usize) -> Vec<i32> {
+  if offsets.is_null() {
+    vec![0; len + 1]
+  } else {
+    slice::from_raw_parts(offsets, len + 1).to_vec()
+  }
+}
+
+fn offset_bytes(offsets: &[i32]) -> Vec<u8> {
+  let mut bytes = Vec::with_capacity(4 * offsets.len());
+  for offset in offsets {
+    bytes.extend_from_slice(&offset.to_le_bytes());
+  }
+  bytes
+}
+
+// Lays out the buffers of arrays in a message body, in the order of the Arrow columnar format
+struct BodyWriter {
+  nodes: Vec<FieldNode>,
+  buffers: Vec<BufferRegion>,
+  body: Vec<u8>
+}
+
+impl BodyWriter {
+  fn add_buffer(&mut self, bytes: &[u8]) {
+    self.buffers.push(BufferRegion {
+      offset: self.body.len() as i64,
+      length: bytes.len() as i64
+    });
+    self.body.extend_from_slice(bytes);
+    pad(&mut self.body);
+  }
+
+  fn add_array(&mut self, array: &BaseArray) -> Result<(), ArrowError> {
+    let raw_array = array.raw_array();
+    let len = array.len() as usize;
+    let null_count = array.null_count();
+    self.nodes.push(FieldNode {
+      length: len as i64,
+      null_count: null_count as i64
+    });
+
+    let ty = array.ty();
+    match ty {
+      // null arrays have no buffers
+      Ty::NA => return Ok(()),
+      Ty::DENSE_UNION | Ty::SPARSE_UNION => return self.add_union(array),
+      _ => {}
+    }
+
+    // the validity bitmap can be omitted when there is no null
+    if null_count > 0 {
+      self.add_buffer(unsafe { raw_bytes(arr_null_bitmap_data(raw_array), (len + 7) / 8) });
+    } else {
+      self.add_buffer(&[]);
+    }
+
+    match ty {
+      Ty::BINARY | Ty::STRING => {
+        let offsets = unsafe { raw_offsets(binary_arr_raw_offsets(raw_array), len) };
+        self.add_buffer(&offset_bytes(&offsets));
+        let data = Buf::from_raw( unsafe { binary_arr_data(raw_array) } );
+        self.add_buffer(unsafe { raw_bytes(data.data(), offsets[len] as usize) });
+      },
+      Ty::LIST => {
+        let offsets = unsafe { raw_offsets(list_arr_raw_offsets(raw_array), len) };
+        self.add_buffer(&offset_bytes(&offsets));
+        let values = BaseArray::from_raw( unsafe { list_arr_values(raw_array) } );
+        try!(self.add_array(&values));
+      },
+      Ty::STRUCT => {
+        for i in 0..unsafe { struct_arr_num_fields(raw_array) } {
+          let field = BaseArray::from_raw( unsafe { struct_arr_field(raw_array, i) } );
+          try!(self.add_array(&field));
+        }
+      },
+      Ty::DECIMAL => {
+        let data = Buf::from_raw( unsafe { decimal_arr_data(raw_array) } );
+        self.add_buffer(unsafe { raw_bytes(data.data(), len * 16) });
+      },
+      ref ty => match metadata::bit_width(ty) {
+        Some(bits) => {
+          let data = Buf::from_raw( unsafe { arr_data(raw_array) } );
+          self.add_buffer(unsafe { raw_bytes(data.data(), (len * bits + 7) / 8) });
+        },
+        None => {
+          return Err(ArrowError::not_implemented(format!("{} arrays cannot be written to IPC messages",
+                                                         array.data_type().to_string())));
+        }
+      }
+    }
+    Ok(())
+  }
+
+  // Unions have no validity bitmap since metadata version 5, so only unions without null slots can
+  // be written. The type ids are followed by the value offsets of dense unions and the children.
+  fn add_union(&mut self, array: &BaseArray) -> Result<(), ArrowError> {
+    if array.null_count() > 0 {
+      return Err(ArrowError::not_implemented("union arrays with null slots cannot be written to IPC messages"));
+    }
+    let raw_array = array.raw_array();
+    let len = array.len() as usize;
+
+    let type_ids = BaseArray::from_raw( unsafe { union_arr_type_ids(raw_array) } );
+    let data = Buf::from_raw( unsafe { arr_data(type_ids.raw_array()) } );
+    self.add_buffer(unsafe { raw_bytes(data.data(), len) });
+    let raw_offsets = unsafe { union_arr_value_offsets(raw_array) };
+    if !raw_offsets.is_null() {
+      let offsets = BaseArray::from_raw(raw_offsets);
+      let data = Buf::from_raw( unsafe { arr_data(offsets.raw_array()) } );
+      self.add_buffer(unsafe { raw_bytes(data.data(), 4 * len) });
+    }
+
+    for i in 0..unsafe { union_arr_num_children(raw_array) } {
+      let child = BaseArray::from_raw( unsafe { union_arr_child(raw_array, i) } );
+      try!(self.add_array(&child));
+    }
+    Ok(())
+  }
+}
+
+// Returns the RecordBatch table of the arrays and the body holding their buffers
+fn encode_arrays(length: i64, arrays: &[&BaseArray]) -> Result<(TableWriter, Vec<u8>), ArrowError> {
+  let mut body_writer = BodyWriter {
+    nodes: Vec::new(),
+    buffers: Vec::new(),
+    body: Vec::new()
+  };
+  for array in arrays {
+    try!(body_writer.add_array(array));
+  }
+
+  let header = metadata::record_batch_to_fb(length, &body_writer.nodes, &body_writer.buffers);
+  Ok((header, body_writer.body))
+}
+
+// Encodes the batch as a RecordBatch message and its body
+pub fn encode_row_batch(batch: &RowBatch) -> Result<(Vec<u8>, Vec<u8>), ArrowError> {
+  let columns = (0..batch.column_num()).map(|i| batch.column::<BaseArray>(i)).collect::<Vec<BaseArray>>();
+  let (header, body) = try!(encode_arrays(batch.row_num() as i64, &columns.iter().collect::<Vec<&BaseArray>>()));
+  let message = metadata::write_message(MessageHeader::RecordBatch, header, body.len() as i64);
+  Ok((message, body))
+}
+
+// Encodes the values of a dictionary as a DictionaryBatch message and its body. A delta is appended
+// to the values sent before with the same id.
+pub fn encode_dictionary_batch(id: i64, values: &BaseArray, is_delta: bool) -> Result<(Vec<u8>, Vec<u8>), ArrowError> {
+  let (data, body) = try!(encode_arrays(values.len() as i64, &[values]));
+  let header = metadata::dictionary_batch_to_fb(id, data, is_delta);
+  let message = metadata::write_message(MessageHeader::DictionaryBatch, header, body.len() as i64);
+  Ok((message, body))
+}
+
+// Tracks which dictionaries have been written, since every row batch refers to them
+struct Dictionaries {
+  fields: Vec<DictionaryField>,
+  written: HashSet<i64>
+}
+
+impl Dictionaries {
+  fn new(schema: &Schema, fields: Vec<DictionaryField>) -> Result<Dictionaries, ArrowError> {
+    try!(metadata::check_dictionaries(schema, &fields));
+    Ok(Dictionaries {
+      fields: fields,
+      written: HashSet::new()
+    })
+  }
+
+  fn check_values(&self, id: i64, values: &BaseArray, is_delta: bool) -> Result<(), ArrowError> {
+    let field = match self.fields.iter().find(|field| field.id == id) {
+      Some(field) => field,
+      None => return Err(ArrowError::key_error(format!("the schema has no dictionary {}", id)))
+    };
+    if values.data_type() != field.value_type {
+      return Err(ArrowError::type_error(format!("the values of dictionary {} must be {}", id,
+                                                field.value_type.to_string())));
+    }
+    if is_delta && !self.written.contains(&id) {
+      return Err(ArrowError::invalid(format!("dictionary {} must be written before its deltas", id)));
+    }
+    Ok(())
+  }
+
+  fn check_batch(&self) -> Result<(), ArrowError> {
+    match self.fields.iter().find(|field| !self.written.contains(&field.id)) {
+      Some(field) => Err(ArrowError::invalid(format!("dictionary {} must be written before the row batches", field.id))),
+      None => Ok(())
+    }
+  }
+}
+
+// Writes the message, prefixed with the continuation marker and its length, and then the body.
+// Returns the size of the prefixed message.
+pub fn write_message<W: Write>(writer: &mut W, message: &[u8], body: &[u8]) -> Result<i32, ArrowError> {
+  let prefixed = metadata::encapsulate(message);
+  try!(writer.write_all(&prefixed));
+  try!(writer.write_all(body));
+  Ok(prefixed.len() as i32)
+}
+
+// Writes the Arrow streaming format: the schema, the row batches and then an end-of-stream marker.
+// finish() must be called to write the marker.
+pub struct StreamWriter<W: Write> {
+  writer: W,
+  schema: Schema,
+  dictionaries: Dictionaries,
+  finished: bool
+}
+
+impl<W: Write> StreamWriter<W> {
+  // Writes the schema, which every row batch must have
+  pub fn new(writer: W, schema: &Schema) -> Result<StreamWriter<W>, ArrowError> {
+    StreamWriter::with_dictionaries(writer, schema, Vec::new())
+  }
+
+  // Writes the schema, in which the dictionary-encoded columns have the type of their keys. Each
+  // dictionary must be written before the first row batch.
+  pub fn with_dictionaries(mut writer: W, schema: &Schema, dictionaries: Vec<DictionaryField>) -> Result<StreamWriter<W>, ArrowError> {
+    let dictionaries = try!(Dictionaries::new(schema, dictionaries));
+    let header = try!(metadata::schema_to_fb(schema, &dictionaries.fields));
+    try!(write_message(&mut writer, &metadata::write_message(MessageHeader::Schema, header, 0), &[]));
+
+    Ok(StreamWriter {
+      writer: writer,
+      schema: schema.clone(),
+      dictionaries: dictionaries,
+      finished: false
+    })
+  }
+
+  pub fn schema(&self) -> &Schema {
+    &self.schema
+  }
+
+  pub fn dictionary_fields(&self) -> &[DictionaryField] {
+    &self.dictionaries.fields
+  }
+
+  pub fn write(&mut self, batch: &RowBatch) -> Result<(), ArrowError> {
+    if self.finished {
+      return Err(ArrowError::invalid("the stream is already finished"));
+    }
+    if batch.schema() != self.schema {
+      return Err(ArrowError::invalid("the schema of the row batch does not match the schema of the stream"));
+    }
+    try!(self.dictionaries.check_batch());
+
+    let (message, body) = try!(encode_row_batch(batch));
+    try!(write_message(&mut self.writer, &message, &body));
+    Ok(())
+  }
+
+  // Writes the values of the dictionary, which replace any values written before
+  pub fn write_dictionary(&mut self, id: i64, values: &BaseArray) -> Result<(), ArrowError> {
+    self.write_dictionary_batch(id, values, false)
+  }
+
+  // Appends the values to the dictionary, so that the keys written before remain valid
+  pub fn write_delta_dictionary(&mut self, id: i64, values: &BaseArray) -> Result<(), ArrowError> {
+    self.write_dictionary_batch(id, values, true)
+  }
+
+  // Writes the values of the array as the dictionary. Its keys go in the row batches.
+  pub fn write_dictionary_array<K: KeyArray>(&mut self, id: i64, array: &DictionaryArray<K>) -> Result<(), ArrowError> {
+    self.write_dictionary(id, array.values())
+  }
+
+  fn write_dictionary_batch(&mut self, id: i64, values: &BaseArray, is_delta: bool) -> Result<(), ArrowError> {
+    if self.finished {
+      return Err(ArrowError::invalid("the stream is already finished"));
+    }
+    try!(self.dictionaries.check_values(id, values, is_delta));
+
+    let (message, body) = try!(encode_dictionary_batch(id, values, is_delta));
+    try!(write_message(&mut self.writer, &message, &body));
+    self.dictionaries.written.insert(id);
+    Ok(())
+  }
+
+  // Writes the end-of-stream marker and flushes the writer. Calling it again does nothing.
+  pub fn finish(&mut self) -> Result<(), ArrowError> {
+    if !self.finished {
+      try!(self.writer.write_all(&metadata::encapsulate(&[])));
+      try!(self.writer.flush());
+      self.finished = true;
+    }
+    Ok(())
+  }
+
+  // Finishes the stream and returns the underlying writer
+  pub fn into_inner(mut self) -> Result<W, ArrowError> {
+    try!(self.finish());
+    Ok(self.writer)
+  }
+}
+
+impl StreamWriter<MemoryMappedSink> {
+  // Writes to a new memory-mapped file, which grows with the batches written
+  pub fn create(path: String, schema: &Schema) -> Result<StreamWriter<MemoryMappedSink>, ArrowError> {
+    StreamWriter::new(try!(MemoryMappedSink::create(path)), schema)
+  }
+}
+
+// Writes the Arrow file format: the magic, the schema, the dictionaries and the row batches as in
+// the streaming format, and then a footer locating each batch. finish() must be called to write
+// the footer.
+pub struct FileWriter<W: Write> {
+  writer: W,
+  schema: Schema,
+  dictionaries: Dictionaries,
+  // number of bytes written so far
+  pos: i64,
+  dictionary_blocks: Vec<Block>,
+  batches: Vec<Block>,
+  finished: bool
+}
+
+impl<W: Write> FileWriter<W> {
+  // Writes the magic and the schema, which every row batch must have
+  pub fn new(writer: W, schema: &Schema) -> Result<FileWriter<W>, ArrowError> {
+    FileWriter::with_dictionaries(writer, schema, Vec::new())
+  }
+
+  // Writes the magic and the schema, in which the dictionary-encoded columns have the type of their
+  // keys. Each dictionary must be written before the first row batch.
+  pub fn with_dictionaries(mut writer: W, schema: &Schema, dictionaries: Vec<DictionaryField>) -> Result<FileWriter<W>, ArrowError> {
+    let dictionaries = try!(Dictionaries::new(schema, dictionaries));
+    let mut header = metadata::MAGIC.to_vec();
+    pad(&mut header);
+    try!(writer.write_all(&header));
+    let schema_header = try!(metadata::schema_to_fb(schema, &dictionaries.fields));
+    let len = try!(write_message(&mut writer, &metadata::write_message(MessageHeader::Schema, schema_header, 0), &[]));
+
+    Ok(FileWriter {
+      writer: writer,
+      schema: schema.clone(),
+      dictionaries: dictionaries,
+      pos: header.len() as i64 + len as i64,
+      dictionary_blocks: Vec::new(),
+      batches: Vec::new(),
+      finished: false
+    })
+  }
+
+  pub fn schema(&self) -> &Schema {
+    &self.schema
+  }
+
+  pub fn dictionary_fields(&self) -> &[DictionaryField] {
+    &self.dictionaries.fields
+  }
+
+  pub fn num_batches(&self) -> i32 {
+    self.batches.len() as i32
+  }
+
+  pub fn write(&mut self, batch: &RowBatch) -> Result<(), ArrowError> {
+    if self.finished {
+      return Err(ArrowError::invalid("the file is already finished"));
+    }
+    if batch.schema() != self.schema {
+      return Err(ArrowError::invalid("the schema of the row batch does not match the schema of the file"));
+    }
+    try!(self.dictionaries.check_batch());
+
+    let (message, body) = try!(encode_row_batch(batch));
+    let block = try!(self.write_block(&message, &body));
+    self.batches.push(block);
+    Ok(())
+  }
+
+  // Writes the values of the dictionary. The file format has no replacements, since the row batches
+  // can be read in any order, so each dictionary is written once and then only extended by deltas.
+  pub fn write_dictionary(&mut self, id: i64, values: &BaseArray) -> Result<(), ArrowError> {
+    if self.dictionaries.written.contains(&id) {
+      return Err(ArrowError::invalid(format!("dictionary {} is already written", id)));
+    }
+    self.write_dictionary_batch(id, values, false)
+  }
+
+  // Appends the values to the dictionary. Readers load every dictionary batch before the row
+  // batches, so the keys of any batch may refer to the appended values.
+  pub fn write_delta_dictionary(&mut self, id: i64, values: &BaseArray) -> Result<(), ArrowError> {
+    self.write_dictionary_batch(id, values, true)
+  }
+
+  // Writes the values of the array as the dictionary. Its keys go in the row batches.
+  pub fn write_dictionary_array<K: KeyArray>(&mut self, id: i64, array: &DictionaryArray<K>) -> Result<(), ArrowError> {
+    self.write_dictionary(id, array.values())
+  }
+
+  fn write_dictionary_batch(&mut self, id: i64, values: &BaseArray, is_delta: bool) -> Result<(), ArrowError> {
+    if self.finished {
+      return Err(ArrowError::invalid("the file is already finished"));
+    }
+    try!(self.dictionaries.check_values(id, values, is_delta));
+
+    let (message, body) = try!(encode_dictionary_batch(id, values, is_delta));
+    let block = try!(self.write_block(&message, &body));
+    self.dictionary_blocks.push(block);
+    self.dictionaries.written.insert(id);
+    Ok(())
+  }
+
+  fn write_block(&mut self, message: &[u8], body: &[u8]) -> Result<Block, ArrowError> {
+    let metadata_len = try!(write_message(&mut self.writer, message, body));
+    let block = Block {
+      offset: self.pos,
+      metadata_len: metadata_len,
+      body_len: body.len() as i64
+    };
+    self.pos += metadata_len as i64 + body.len() as i64;
+    Ok(block)
+  }
+
+  // Writes the end-of-stream marker, the footer and the closing magic, and flushes the writer.
+  // Calling it again does nothing.
+  pub fn finish(&mut self) -> Result<(), ArrowError> {
+    if self.finished {
+      return Ok(());
+    }
+
+    let footer = try!(metadata::footer_to_bytes(&self.schema, &self.dictionaries.fields, &self.dictionary_blocks,
+                                                   &self.batches));
+    let mut trailer = metadata::encapsulate(&[]);
+    trailer.extend_from_slice(&footer);
+    trailer.extend_from_slice(&(footer.len() as i32).to_le_bytes());
+    trailer.extend_from_slice(metadata::MAGIC);
+    try!(self.writer.write_all(&trailer));
+    try!(self.writer.flush());
+    self.pos += trailer.len() as i64;
+    self.finished = true;
+    Ok(())
+  }
+
+  // Finishes the file and returns the underlying writer
+  pub fn into_inner(mut self) -> Result<W, ArrowError> {
+    try!(self.finish());
+    Ok(self.writer)
+  }
+}
+
+impl FileWriter<MemoryMappedSink> {
+  // Writes to a new memory-mapped file, which grows with the batches written
+  pub fn create(path: String, schema: &Schema) -> Result<FileWriter<MemoryMappedSink>, ArrowError> {
+    FileWriter::new(try!(MemoryMappedSink::create(path)), schema)
+  }
+}
\ No newline at end of file
@@ -9,7 +9,9 @@ pub mod common;
 pub mod types;
 pub mod ty;
 pub mod array;
+pub mod array_builder;
 pub mod buffer;
+pub mod bitmap;
 pub mod column;
 pub mod table;
 pub mod ipc;
@@ -59,8 +61,11 @@ mod benchmarks {
       f.set_len(batch_size as u64).unwrap();
       f.sync_all().unwrap();
 
+      let mut s = ptr::null();
       let src = memory::open_mmap_src(CString::new(file_name).unwrap().as_ptr(),
-                                      memory::AccessMode::READWRITE);
+                                      memory::AccessMode::READWRITE, &mut s);
+      assert!(status::ok(s));
+      status::release_status(s);
       let header_pos = adapter::c_api::write_row_batch(src, row_batch, 0, 64);
 
       let s = memory::close_mmap_src(src);
@@ -68,8 +73,11 @@ mod benchmarks {
       memory::release_mmap_src(src);
       table::release_row_batch(row_batch);
 
+      let mut s = ptr::null();
       let src = memory::open_mmap_src(CString::new(file_name).unwrap().as_ptr(),
-                                      memory::AccessMode::READ);
+                                      memory::AccessMode::READ, &mut s);
+      assert!(status::ok(s));
+      status::release_status(s);
 
       let result = adapter::c_api::open_row_batch_reader(src, header_pos);
       assert!(status::ok((*result).status()));
@@ -107,7 +115,7 @@ mod benchmarks {
 
   #[bench]
   fn bench_adapter(b: &mut Bencher) {
-    use common::memory_pool::MemoryPool;
+    use common::memory_pool::DefaultMemoryPool;
     use ty::{DataTypeProvider, Schema, Field};
     use types::primitive::{I32Array, F32Array, I32ArrayBuilder, F32ArrayBuilder, PrimitiveArray};
     use table::RowBatch;
@@ -115,7 +123,7 @@ mod benchmarks {
     use array::Array;
 
     let type_provider = DataTypeProvider::new();
-    let pool = MemoryPool::default();
+    let pool = DefaultMemoryPool::default();
     let f1 = Field::new(String::from("key"), type_provider.i32(), false);
     let f2 = Field::new(String::from("payload"), type_provider.f32(), false);
     let schema = Schema::new(&[&f1, &f2]);
@@ -137,16 +145,19 @@ mod benchmarks {
     f.set_len(batch_size as u64).unwrap();
     f.sync_all().unwrap();
 
-    let src = MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READWRITE);
+    let src = MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READWRITE).unwrap();
     let header_pos = adapter::write_row_batch(&src, &row_batch, 0);
     src.close();
 
-    let src = MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READ);
+    let src = MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READ).unwrap();
     let reader = match adapter::RowBatchReader::open(&src, header_pos) {
       Ok(reader) => reader,
       Err(e) => panic!("Failed to open RowBatchReader: {}", e.message())
     };
-    let row_batch = reader.read(&schema);
+    let row_batch = match reader.read(&schema) {
+      Ok(row_batch) => row_batch,
+      Err(e) => panic!("Failed to read the row batch: {}", e.message())
+    };
     let key_col: I32Array = row_batch.column(0);
     let payload_col: F32Array = row_batch.column(1);
 
@@ -1,3 +1,180 @@
+use native::common::status::ArrowError;
+use native::buffer::Buffer;
+use native::common::ty::{BufferType, DataType};
+
+/// The physical contents of an array: its logical type, length, null count, the offset of the
+/// first slot in its buffers, one buffer per entry of `DataType::get_buffer_layout()` and one
+/// child per field of nested types.
+///
+/// A validity buffer can be omitted (`None`) when the array has no nulls.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArrayData {
+  data_type: DataType,
+  len: i64,
+  null_count: i64,
+  offset: i64,
+  buffers: Vec<Option<Buffer>>,
+  children: Vec<ArrayData>
+}
+
+impl ArrayData {
+  pub fn new(data_type: DataType, len: i64, null_count: i64, offset: i64,
+             buffers: Vec<Option<Buffer>>, children: Vec<ArrayData>) -> Result<ArrayData, ArrowError> {
+    if len < 0 || offset < 0 {
+      return Err(ArrowError::invalid("length and offset must not be negative"));
+    }
+    if null_count < 0 || null_count > len {
+      return Err(ArrowError::invalid("null count must be between 0 and the length"));
+    }
+
+    {
+      let layout = data_type.get_buffer_layout();
+      if buffers.len() != layout.len() {
+        return Err(ArrowError::invalid("the number of buffers does not match the buffer layout"));
+      }
+
+      let slots = offset + len;
+      for (desc, buffer) in layout.iter().zip(buffers.iter()) {
+        let required_bits = match *desc.get_type() {
+          // offsets have an extra entry marking the end of the last slot
+          BufferType::OFFSET => (slots + 1) * desc.get_bit_width() as i64,
+          _ => slots * desc.get_bit_width() as i64
+        };
+
+        match *buffer {
+          Some(ref buffer) => {
+            if (buffer.len() as i64) * 8 < required_bits {
+              return Err(ArrowError::invalid("buffer is too small for the length of the array"));
+            }
+          },
+          None => {
+            if *desc.get_type() != BufferType::VALIDITY || null_count != 0 {
+              return Err(ArrowError::invalid("only the validity buffer of an array without nulls can be omitted"));
+            }
+          }
+        }
+      }
+    }
+
+    if children.len() as i32 != data_type.num_children() {
+      return Err(ArrowError::invalid("the number of children does not match the data type"));
+    }
+    if children.iter().zip(data_type.get_children().iter()).any(|(child, field)| child.data_type() != field.get_type()) {
+      return Err(ArrowError::invalid("the type of a child does not match its field"));
+    }
+
+    Ok(ArrayData {
+      data_type: data_type,
+      len: len,
+      null_count: null_count,
+      offset: offset,
+      buffers: buffers,
+      children: children
+    })
+  }
+
+  pub fn data_type(&self) -> &DataType {
+    &self.data_type
+  }
+
+  pub fn len(&self) -> i64 {
+    self.len
+  }
+
+  pub fn null_count(&self) -> i64 {
+    self.null_count
+  }
+
+  pub fn offset(&self) -> i64 {
+    self.offset
+  }
+
+  pub fn buffers(&self) -> &Vec<Option<Buffer>> {
+    &self.buffers
+  }
+
+  // Panics if i is out of the bounds of the buffer layout
+  pub fn buffer(&self, i: usize) -> Option<&Buffer> {
+    self.buffers[i].as_ref()
+  }
+
+  pub fn children(&self) -> &Vec<ArrayData> {
+    &self.children
+  }
+
+  pub fn child(&self, i: usize) -> &ArrayData {
+    &self.children[i]
+  }
+
+  // The validity buffer comes first in every layout which has one
+  fn validity_buffer(&self) -> Option<&Buffer> {
+    match self.data_type.get_buffer_layout().first() {
+      Some(desc) if *desc.get_type() == BufferType::VALIDITY => self.buffer(0),
+      _ => None
+    }
+  }
+
+  // Panics if i is out of bounds, since the bit would belong to another array sharing the buffer
+  pub fn is_valid(&self, i: i64) -> bool {
+    assert!(i >= 0 && i < self.len, "index is out of the bounds of the array");
+    if let DataType::Null = self.data_type {
+      return false;
+    }
+    match self.validity_buffer() {
+      Some(bitmap) => {
+        let bit = (self.offset + i) as usize;
+        bitmap.as_slice()[bit / 8] & (1 << (bit % 8)) != 0
+      },
+      None => true
+    }
+  }
+
+  pub fn is_null(&self, i: i64) -> bool {
+    !self.is_valid(i)
+  }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Array {
+  data: ArrayData
+}
+
+impl Array {
+  pub fn new(data: ArrayData) -> Array {
+    Array {
+      data: data
+    }
+  }
+
+  pub fn data(&self) -> &ArrayData {
+    &self.data
+  }
+
+  pub fn into_data(self) -> ArrayData {
+    self.data
+  }
+
+  pub fn data_type(&self) -> &DataType {
+    self.data.data_type()
+  }
+
+  pub fn len(&self) -> i64 {
+    self.data.len()
+  }
+
+  pub fn null_count(&self) -> i64 {
+    self.data.null_count()
+  }
+
+  pub fn offset(&self) -> i64 {
+    self.data.offset()
+  }
+
+  pub fn is_null(&self, i: i64) -> bool {
+    self.data.is_null(i)
+  }
 
+  pub fn is_valid(&self, i: i64) -> bool {
+    self.data.is_valid(i)
+  }
 }
\ No newline at end of file
@@ -0,0 +1,180 @@
+use std::alloc::{self, Layout};
+use std::fmt;
+use std::mem;
+use std::ptr;
+use std::slice;
+use std::sync::Arc;
+
+/// Memory of native buffers is aligned and padded to 64 bytes, the width of a cache line. Allocations
+/// of the memory pools and buffers handed over to C++ are aligned to it too.
+pub const ALIGNMENT: usize = 64;
+
+// Rounds up the size to a multiple of the alignment
+fn padded_capacity(size: usize) -> usize {
+  (size + ALIGNMENT - 1) / ALIGNMENT * ALIGNMENT
+}
+
+mod sealed {
+  pub trait Sealed {}
+}
+
+// Primitive types which can be viewed directly from the bytes of a buffer. They have no padding
+// bytes, so it is sealed.
+pub trait NativeType: Copy + sealed::Sealed {}
+
+macro_rules! impl_native_type {
+  ($ty:ty) => (
+    impl sealed::Sealed for $ty {}
+    impl NativeType for $ty {}
+  );
+}
+
+impl_native_type!(u8);
+impl_native_type!(i8);
+impl_native_type!(u16);
+impl_native_type!(i16);
+impl_native_type!(u32);
+impl_native_type!(i32);
+impl_native_type!(u64);
+impl_native_type!(i64);
+impl_native_type!(f32);
+impl_native_type!(f64);
+
+// An aligned allocation released when the last buffer referring to it is dropped
+struct BufferData {
+  ptr: *mut u8,
+  capacity: usize
+}
+
+impl BufferData {
+  fn new(len: usize) -> BufferData {
+    let capacity = padded_capacity(len);
+    let ptr = if capacity == 0 {
+      // a dangling but aligned pointer, which is never dereferenced
+      ALIGNMENT as *mut u8
+    } else {
+      let ptr = unsafe { alloc::alloc_zeroed(Layout::from_size_align(capacity, ALIGNMENT).unwrap()) };
+      if ptr.is_null() {
+        alloc::handle_alloc_error(Layout::from_size_align(capacity, ALIGNMENT).unwrap());
+      }
+      ptr
+    };
+
+    BufferData {
+      ptr: ptr,
+      capacity: capacity
+    }
+  }
+}
+
+impl Drop for BufferData {
+  fn drop(&mut self) {
+    if self.capacity > 0 {
+      unsafe { alloc::dealloc(self.ptr, Layout::from_size_align(self.capacity, ALIGNMENT).unwrap()); }
+    }
+  }
+}
+
+// The memory is never mutated once it is shared
+unsafe impl Send for BufferData {}
+unsafe impl Sync for BufferData {}
+
+/// An immutable, reference-counted byte buffer. Cloning and slicing share the same memory.
+#[derive(Clone)]
+pub struct Buffer {
+  data: Arc<BufferData>,
+  offset: usize,
+  len: usize
+}
+
+impl Buffer {
+  // Copies the bytes into a new aligned allocation
+  pub fn from_slice(bytes: &[u8]) -> Buffer {
+    let data = BufferData::new(bytes.len());
+    unsafe { ptr::copy_nonoverlapping(bytes.as_ptr(), data.ptr, bytes.len()); }
+    Buffer {
+      data: Arc::new(data),
+      offset: 0,
+      len: bytes.len()
+    }
+  }
+
+  // A buffer of len zeroed bytes
+  pub fn zeroed(len: usize) -> Buffer {
+    Buffer {
+      data: Arc::new(BufferData::new(len)),
+      offset: 0,
+      len: len
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  // Size of the whole allocation including the padding
+  pub fn capacity(&self) -> usize {
+    self.data.capacity
+  }
+
+  pub fn as_ptr(&self) -> *const u8 {
+    unsafe { self.data.ptr.offset(self.offset as isize) }
+  }
+
+  pub fn as_slice(&self) -> &[u8] {
+    unsafe { slice::from_raw_parts(self.as_ptr(), self.len) }
+  }
+
+  /// Returns a buffer sharing len bytes from offset. Panics if the range is out of bounds.
+  pub fn slice(&self, offset: usize, len: usize) -> Buffer {
+    assert!(offset + len <= self.len, "slice is out of the bounds of the buffer");
+    Buffer {
+      data: self.data.clone(),
+      offset: self.offset + offset,
+      len: len
+    }
+  }
+
+  /// Views the bytes as values of T. Panics if the view would be misaligned, which can happen
+  /// only for slices at an offset which is not a multiple of the size of T.
+  pub fn typed_data<T: NativeType>(&self) -> &[T] {
+    assert_eq!(0, self.as_ptr() as usize % mem::align_of::<T>(), "buffer is not aligned for the type");
+    unsafe { slice::from_raw_parts(self.as_ptr() as *const T, self.len / mem::size_of::<T>()) }
+  }
+
+  // Whether both buffers refer to the same memory
+  pub fn ptr_eq(&self, other: &Buffer) -> bool {
+    self.as_ptr() == other.as_ptr() && self.len == other.len
+  }
+}
+
+impl PartialEq for Buffer {
+  fn eq(&self, other: &Buffer) -> bool {
+    self.as_slice() == other.as_slice()
+  }
+}
+
+impl Eq for Buffer {}
+
+impl fmt::Debug for Buffer {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "Buffer {{ len: {}, data: {:?} }}", self.len, self.as_slice())
+  }
+}
+
+impl<'a> From<&'a [u8]> for Buffer {
+  fn from(bytes: &'a [u8]) -> Buffer {
+    Buffer::from_slice(bytes)
+  }
+}
+
+// Vec allocations are not aligned to 64 bytes, so the bytes are copied
+impl From<Vec<u8>> for Buffer {
+  fn from(bytes: Vec<u8>) -> Buffer {
+    Buffer::from_slice(&bytes)
+  }
+}
\ No newline at end of file
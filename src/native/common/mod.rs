@@ -38,135 +38,182 @@ mod tests {
     assert_eq!(String::from("not implemented"), *arrow_error.message());
   }
 
-//  #[test]
-//  fn test_field() {
-//    let field = Field::basic("f1", NullType::new());
-//    assert_eq!("f1", field.get_name().as_str());
-//    assert_eq!(&NullType::new(), field.get_type());
-//    assert_eq!(true, field.is_nullable());
-//    assert_eq!(0, field.get_dictionary());
-//
-//    let field = Field::non_null("f2", FloatType::new());
-//    assert_eq!("f2", field.get_name().as_str());
-//    assert_eq!(&FloatType::new(), field.get_type());
-//    assert_eq!(false, field.is_nullable());
-//    assert_eq!(0, field.get_dictionary());
-//
-//    let field = Field::with_dic("f3", Int64Type::new(), -1);
-//    assert_eq!("f3", field.get_name().as_str());
-//    assert_eq!(&Int64Type::new(), field.get_type());
-//    assert_eq!(true, field.is_nullable());
-//    assert_eq!(-1, field.get_dictionary());
-//  }
-
-//  #[test]
-//  fn test_null_type() {
-//    let ty = NullType::new();
-//    assert_eq!(RawType::NULL, ty.get_type());
-//    assert_eq!("null", ty.get_name());
-//    let expected_layout: Vec<&BufferDesc> = Vec::new();
-//    assert_eq!(expected_layout, ty.get_buffer_layout());
-//  }
-//
-//  #[test]
-//  fn test_boolean_type() {
-//    let ty = BooleanType::new();
-//    assert_eq!(RawType::BOOL, ty.get_type());
-//    assert_eq!("bool", ty.get_name());
-//    assert_eq!(vec![K_VALIDITY_BUFFER, K_VALUES_1], ty.get_buffer_layout());
-//  }
-//
-//  #[test]
-//  fn test_bool() {
-//    assert_eq!(1, RawType::BOOL.bit_width());
-//  }
-//
-//  macro_rules! test_primitive_int_type {
-//    ($test_name: ident, $type_name: ident, $str_name: expr, $raw_type: expr, $rust_type: ty, $buffer_desc: ident, $is_signed: expr) => (
-//      #[test]
-//      fn $test_name() {
-//        use std::mem;
-//
-//        let ty = $type_name::new();
-//        assert_eq!($raw_type, ty.get_type());
-//        assert_eq!($str_name, ty.get_name());
-//        assert_eq!(mem::size_of::<$rust_type>() as i32, ty.get_bit_width());
-//        assert_eq!($is_signed, ty.is_signed());
-//        assert_eq!(vec![K_VALIDITY_BUFFER, $buffer_desc], ty.get_buffer_layout());
-//      }
-//    );
-//  }
-//
-//  macro_rules! test_primitive_float_type {
-//    ($test_name: ident, $type_name: ident, $str_name: expr, $raw_type: expr, $rust_type: ty, $buffer_desc: ident, $precision: expr) => (
-//      #[test]
-//      fn $test_name() {
-//        use std::mem;
-//
-//        let ty = $type_name::new();
-//        assert_eq!($raw_type, ty.get_type());
-//        assert_eq!($str_name, ty.get_name());
-//        assert_eq!(mem::size_of::<$rust_type>() as i32, ty.get_bit_width());
-//        assert_eq!($precision, ty.precision());
-//        assert_eq!(vec![K_VALIDITY_BUFFER, $buffer_desc], ty.get_buffer_layout());
-//      }
-//    );
-//  }
-//
-//  test_primitive_int_type!(test_uint8_type, UInt8Type, "uint8", RawType::UINT8, u8, K_VALUES_8, false);
-//  test_primitive_int_type!(test_uint16_type, UInt16Type, "uint16", RawType::UINT16, u16, K_VALUES_16, false);
-//  test_primitive_int_type!(test_uint32_type, UInt32Type, "uint32", RawType::UINT32, u32, K_VALUES_32, false);
-//  test_primitive_int_type!(test_uint64_type, UInt64Type, "uint64", RawType::UINT64, u64, K_VALUES_64, false);
-//  test_primitive_int_type!(test_int8_type, Int8Type, "int8", RawType::INT8, i8, K_VALUES_8, true);
-//  test_primitive_int_type!(test_int16_type, Int16Type, "int16", RawType::INT16, i16, K_VALUES_16, true);
-//  test_primitive_int_type!(test_int32_type, Int32Type, "int32", RawType::INT32, i32, K_VALUES_32, true);
-//  test_primitive_int_type!(test_int64_type, Int64Type, "int64", RawType::INT64, i64, K_VALUES_64, true);
-//  test_primitive_float_type!(test_half_float_type, HalfFloatType, "halffloat", RawType::HALF_FLOAT, u16, K_VALUES_16, Precision::HALF);
-//  test_primitive_float_type!(test_float_type, FloatType, "float", RawType::FLOAT, f32, K_VALUES_32, Precision::SINGLE);
-//  test_primitive_float_type!(test_double_type, DoubleType, "double", RawType::DOUBLE, f64, K_VALUES_64, Precision::DOUBLE);
-
-//  #[test]
-//  fn test_list_type() {
-//    let ty = ListType::with_value_type(DoubleType::new());
-//    assert_eq!(RawType::LIST, ty.get_type());
-//    assert_eq!("list", ty.get_name());
-//    assert_eq!(vec![K_VALIDITY_BUFFER, K_OFFSET_BUFFER], ty.get_buffer_layout());
-//    assert_eq!(&DoubleType::new(), ty.value_type());
-//    assert_eq!(1, ty.num_children());
-//    assert_eq!(Field::basic("item", DoubleType::new()), ty[0]);
-//    assert_eq!(&Field::basic("item", DoubleType::new()), ty.value_field());
-//    assert_eq!(&vec![Field::basic("item", DoubleType::new())], ty.get_children())
-//  }
-//
-//  #[test]
-//  fn test_binary_type() {
-//    let ty = BinaryType::new();
-//    assert_eq!(RawType::BINARY, ty.get_type());
-//    assert_eq!("binary", ty.get_name());
-//    assert_eq!(vec![K_VALIDITY_BUFFER, K_OFFSET_BUFFER, K_VALUES_8], ty.get_buffer_layout());
-//  }
-//
-//  #[test]
-//  fn test_string_type() {
-//    let ty = StringType::new();
-//    assert_eq!(RawType::STRING, ty.get_type());
-//    assert_eq!("utf8", ty.get_name());
-//    assert_eq!(vec![K_VALIDITY_BUFFER, K_OFFSET_BUFFER, K_VALUES_8], ty.get_buffer_layout());
-//  }
-
-//  #[test]
-//  fn test_struct_type() {
-//    let ty = StructType::new(vec![Field::basic("f1", NullType::new()), Field::basic("f2", Int32Type::new())]);
-//    assert_eq!(RawType::STRUCT, ty.get_type());
-//    assert_eq!("struct", ty.get_name());
-//    assert_eq!(vec![K_VALIDITY_BUFFER], ty.get_buffer_layout());
-//    assert_eq!(2, ty.num_children());
-//    assert_eq!(Field::basic("f1", NullType::new()), ty[0]);
-//    assert_eq!(Field::basic("f2", Int32Type::new()), ty[1]);
-//  }
-
-//  #[test]
-//  fn test_struct_type() {
-//    let ty = RawType::STRUCT { children: vec![Field::basic("f1", RawType::DATE), Field::basic("f2", RawType::DOUBLE)] };
-//  }
+  #[test]
+  fn test_field() {
+    let field = Field::basic("f1", DataType::Null);
+    assert_eq!("f1", field.get_name().as_str());
+    assert_eq!(&DataType::Null, field.get_type());
+    assert_eq!(true, field.is_nullable());
+    assert_eq!(0, field.get_dictionary());
+    assert_eq!("f1: null", field.to_string());
+
+    let field = Field::non_null("f2", DataType::Float);
+    assert_eq!("f2", field.get_name().as_str());
+    assert_eq!(&DataType::Float, field.get_type());
+    assert_eq!(false, field.is_nullable());
+    assert_eq!(0, field.get_dictionary());
+    assert_eq!("f2: float not null", field.to_string());
+
+    let field = Field::with_dic("f3", DataType::Int64, -1);
+    assert_eq!("f3", field.get_name().as_str());
+    assert_eq!(&DataType::Int64, field.get_type());
+    assert_eq!(true, field.is_nullable());
+    assert_eq!(-1, field.get_dictionary());
+  }
+
+  #[test]
+  fn test_null_type() {
+    let ty = DataType::Null;
+    assert_eq!(DataType::Null, ty.get_type());
+    assert_eq!("null", ty.get_name());
+    let expected_layout: Vec<&BufferDesc> = Vec::new();
+    assert_eq!(expected_layout, ty.get_buffer_layout());
+    assert!(!ty.is_fixed_width());
+    assert_eq!(0, ty.num_children());
+  }
+
+  #[test]
+  fn test_boolean_type() {
+    let ty = DataType::Bool;
+    assert_eq!(DataType::Bool, ty.get_type());
+    assert_eq!("bool", ty.get_name());
+    assert_eq!(vec![K_VALIDITY_BUFFER, K_VALUES_1], ty.get_buffer_layout());
+    assert_eq!(1, ty.bit_width());
+  }
+
+  macro_rules! test_primitive_int_type {
+    ($test_name: ident, $ty: expr, $str_name: expr, $rust_type: ty, $buffer_desc: ident, $is_signed: expr) => (
+      #[test]
+      fn $test_name() {
+        use std::mem;
+
+        let ty = $ty;
+        assert_eq!($ty, ty.get_type());
+        assert_eq!($str_name, ty.get_name());
+        assert_eq!(mem::size_of::<$rust_type>() as i32 * 8, ty.get_bit_width());
+        assert_eq!($is_signed, ty.is_signed());
+        assert_eq!(vec![K_VALIDITY_BUFFER, $buffer_desc], ty.get_buffer_layout());
+      }
+    );
+  }
+
+  macro_rules! test_primitive_float_type {
+    ($test_name: ident, $ty: expr, $str_name: expr, $rust_type: ty, $buffer_desc: ident, $precision: expr) => (
+      #[test]
+      fn $test_name() {
+        use std::mem;
+
+        let ty = $ty;
+        assert_eq!($ty, ty.get_type());
+        assert_eq!($str_name, ty.get_name());
+        assert_eq!(mem::size_of::<$rust_type>() as i32 * 8, ty.get_bit_width());
+        assert_eq!($precision, ty.precision());
+        assert_eq!(vec![K_VALIDITY_BUFFER, $buffer_desc], ty.get_buffer_layout());
+      }
+    );
+  }
+
+  test_primitive_int_type!(test_uint8_type, DataType::Uint8, "uint8", u8, K_VALUES_8, false);
+  test_primitive_int_type!(test_uint16_type, DataType::Uint16, "uint16", u16, K_VALUES_16, false);
+  test_primitive_int_type!(test_uint32_type, DataType::Uint32, "uint32", u32, K_VALUES_32, false);
+  test_primitive_int_type!(test_uint64_type, DataType::Uint64, "uint64", u64, K_VALUES_64, false);
+  test_primitive_int_type!(test_int8_type, DataType::Int8, "int8", i8, K_VALUES_8, true);
+  test_primitive_int_type!(test_int16_type, DataType::Int16, "int16", i16, K_VALUES_16, true);
+  test_primitive_int_type!(test_int32_type, DataType::Int32, "int32", i32, K_VALUES_32, true);
+  test_primitive_int_type!(test_int64_type, DataType::Int64, "int64", i64, K_VALUES_64, true);
+  test_primitive_float_type!(test_half_float_type, DataType::HalfFloat, "halffloat", u16, K_VALUES_16, Precision::HALF);
+  test_primitive_float_type!(test_float_type, DataType::Float, "float", f32, K_VALUES_32, Precision::SINGLE);
+  test_primitive_float_type!(test_double_type, DataType::Double, "double", f64, K_VALUES_64, Precision::DOUBLE);
+
+  #[test]
+  #[should_panic]
+  fn test_bit_width_of_variable_width_type() {
+    DataType::String.bit_width();
+  }
+
+  #[test]
+  fn test_list_type() {
+    let ty = DataType::list(DataType::Double);
+    assert_eq!("list", ty.get_name());
+    assert_eq!(vec![K_VALIDITY_BUFFER, K_OFFSET_BUFFER], ty.get_buffer_layout());
+    assert!(ty.is_nested());
+    assert_eq!(1, ty.num_children());
+    assert_eq!(Field::basic("item", DataType::Double), ty[0]);
+    assert_eq!(&DataType::Double, ty.child(0).get_type());
+    assert_eq!(&[Field::basic("item", DataType::Double)], ty.get_children());
+  }
+
+  #[test]
+  fn test_binary_type() {
+    let ty = DataType::Binary;
+    assert_eq!("binary", ty.get_name());
+    assert_eq!(vec![K_VALIDITY_BUFFER, K_OFFSET_BUFFER, K_VALUES_8], ty.get_buffer_layout());
+  }
+
+  #[test]
+  fn test_string_type() {
+    let ty = DataType::String;
+    assert_eq!("utf8", ty.get_name());
+    assert_eq!(vec![K_VALIDITY_BUFFER, K_OFFSET_BUFFER, K_VALUES_8], ty.get_buffer_layout());
+  }
+
+  #[test]
+  fn test_struct_type() {
+    let ty = DataType::Struct { children: vec![Field::basic("f1", DataType::Date), Field::basic("f2", DataType::Int32)] };
+    assert_eq!("struct", ty.get_name());
+    assert_eq!(vec![K_VALIDITY_BUFFER], ty.get_buffer_layout());
+    assert_eq!(2, ty.num_children());
+    assert_eq!(Field::basic("f1", DataType::Date), ty[0]);
+    assert_eq!(Field::basic("f2", DataType::Int32), ty[1]);
+  }
+
+  #[test]
+  fn test_union_type() {
+    let children = vec![Field::basic("f1", DataType::Int32), Field::basic("f2", DataType::String)];
+    let ty = DataType::Union { children: children.clone(), type_codes: vec![0, 1], mode: UnionMode::SPARSE };
+    assert_eq!("union", ty.get_name());
+    assert_eq!(vec![K_VALIDITY_BUFFER, K_TYPE_BUFFER], ty.get_buffer_layout());
+    assert_eq!(2, ty.num_children());
+
+    let ty = DataType::Union { children: children, type_codes: vec![0, 1], mode: UnionMode::DENSE };
+    assert_eq!(vec![K_VALIDITY_BUFFER, K_TYPE_BUFFER, K_OFFSET_BUFFER], ty.get_buffer_layout());
+    assert_eq!(Field::basic("f2", DataType::String), ty[1]);
+  }
+
+  #[test]
+  fn test_temporal_types() {
+    let ty = DataType::Timestamp { unit: TimeUnit::MILLI };
+    assert_eq!("timestamp", ty.get_name());
+    assert_eq!(64, ty.bit_width());
+    assert_eq!(vec![K_VALIDITY_BUFFER, K_VALUES_64], ty.get_buffer_layout());
+    assert_eq!("time", DataType::Time { unit: TimeUnit::NANO }.get_name());
+    assert_eq!(32, DataType::Date.bit_width());
+    assert_eq!(vec![K_VALIDITY_BUFFER, K_VALUES_32], DataType::Date.get_buffer_layout());
+  }
+
+  #[test]
+  fn test_decimal_type() {
+    let ty = DataType::Decimal { precision: 10, scale: 2 };
+    assert_eq!("decimal", ty.get_name());
+    assert_eq!(128, ty.bit_width());
+    assert!(ty.is_signed());
+    assert_eq!(vec![K_VALIDITY_BUFFER, K_VALUES_128], ty.get_buffer_layout());
+  }
+
+  #[test]
+  fn test_dictionary_type() {
+    use native::{Array, ArrayData};
+    use native::buffer::Buffer;
+
+    let dictionary = match ArrayData::new(DataType::Int8, 3, 0, 0, vec![None, Some(Buffer::from(vec![1, 2, 3]))], vec![]) {
+      Ok(data) => Array::new(data),
+      Err(e) => panic!("invalid array data: {}", e.message())
+    };
+    let ty = DataType::dictionary(DataType::Int16, dictionary);
+    assert_eq!("dictionary", ty.get_name());
+    assert_eq!(16, ty.bit_width());
+    assert!(ty.is_signed());
+    assert!(!ty.is_nested());
+    assert_eq!(vec![K_VALIDITY_BUFFER, K_VALUES_16], ty.get_buffer_layout());
+  }
 }
\ No newline at end of file
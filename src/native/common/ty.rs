@@ -1,16 +1,13 @@
 use std::ops::Index;
-use std::mem;
+use std::slice;
 
 use native;
 
-#[macro_use]
-use std;
-
 /// Data types in this library are all *logical*. They can be expressed as
 /// either a primitive physical type (bytes or bits of some fixed size), a
 /// nested type consisting of other data types, or another data type (e.g. a
 /// timestamp encoded as an int64)
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataType {
   // A degenerate NULL type represented as 0 bytes/bits
   Null,
@@ -43,7 +40,7 @@ pub enum DataType {
   // Variable-length bytes (no guarantee of UTF8-ness)
   Binary,
 
-  // By default, int32 days since the UNIX epoch
+  // int32 days since the UNIX epoch, as in DateArray
   Date,
 
   // Exact timestamp encoded with int64 since UNIX epoch
@@ -61,19 +58,27 @@ pub enum DataType {
   Decimal { precision: i32, scale: i32 },
 
   // A list of some logical data type
-  List { field: Field } ,
+  List { field: Box<Field> },
 
   // Struct of logical types
   Struct { children: Vec<Field> },
 
-  // Unions of logical types
-  Union { mode: UnionMode },
+  // Unions of logical types. The i-th type code identifies the i-th child.
+  Union { children: Vec<Field>, type_codes: Vec<u8>, mode: UnionMode },
 
   // Dictionary aka Category type
-  Dictionary { index_type: DataType, dictionary: native::Array }
+  Dictionary { index_type: Box<DataType>, dictionary: Box<native::Array> }
 }
 
 impl DataType {
+  pub fn list(value_type: DataType) -> DataType {
+    DataType::List { field: Box::new(Field::basic("item", value_type)) }
+  }
+
+  pub fn dictionary(index_type: DataType, dictionary: native::Array) -> DataType {
+    DataType::Dictionary { index_type: Box::new(index_type), dictionary: Box::new(dictionary) }
+  }
+
   pub fn get_buffer_layout(&self) -> Vec<&BufferDesc> {
     match self {
       &DataType::Null => vec![],
@@ -91,22 +96,20 @@ impl DataType {
       &DataType::Double => vec![K_VALIDITY_BUFFER, K_VALUES_64],
       &DataType::String => vec![K_VALIDITY_BUFFER, K_OFFSET_BUFFER, K_VALUES_8],
       &DataType::Binary => vec![K_VALIDITY_BUFFER, K_OFFSET_BUFFER, K_VALUES_8],
-      &DataType::Date => vec![K_VALIDITY_BUFFER, K_VALUES_64],
-      &DataType::Timestamp { unit: ref unit } => vec![K_VALIDITY_BUFFER, K_VALUES_64],
-      &DataType::Time { unit: ref unit } => vec![K_VALIDITY_BUFFER, K_VALUES_64],
-      &DataType::Interval { unit: ref unit } => vec![K_VALIDITY_BUFFER, K_VALUES_64],
-      &DataType::Decimal { precision: precision, scale: scale } => vec![], // TODO
-      &DataType::List { field: ref field } => vec![K_VALIDITY_BUFFER, K_OFFSET_BUFFER],
-      &DataType::Struct { children: ref children } => vec![K_VALIDITY_BUFFER],
-      &DataType::Union { mode: ref mode } => {
-        match mode {
+      &DataType::Date => vec![K_VALIDITY_BUFFER, K_VALUES_32],
+      &DataType::Timestamp { .. } => vec![K_VALIDITY_BUFFER, K_VALUES_64],
+      &DataType::Time { .. } => vec![K_VALIDITY_BUFFER, K_VALUES_64],
+      &DataType::Interval { .. } => vec![K_VALIDITY_BUFFER, K_VALUES_64],
+      &DataType::Decimal { .. } => vec![K_VALIDITY_BUFFER, K_VALUES_128],
+      &DataType::List { .. } => vec![K_VALIDITY_BUFFER, K_OFFSET_BUFFER],
+      &DataType::Struct { .. } => vec![K_VALIDITY_BUFFER],
+      &DataType::Union { ref mode, .. } => {
+        match *mode {
           UnionMode::SPARSE => vec![K_VALIDITY_BUFFER, K_TYPE_BUFFER],
-          UnionMode::DENSE => vec![K_VALIDITY_BUFFER, K_TYPE_BUFFER, K_OFFSET_BUFFER],
-          _ => panic!()
+          UnionMode::DENSE => vec![K_VALIDITY_BUFFER, K_TYPE_BUFFER, K_OFFSET_BUFFER]
         }
       },
-      &DataType::Dictionary { index_type: ref index_type, dictionary: ref dictionary } => vec![K_VALIDITY_BUFFER, BufferDesc { ty: BufferType::DATA, bit_width: index_type.bit_width() } ],
-      _ => panic!()
+      &DataType::Dictionary { ref index_type, .. } => vec![K_VALIDITY_BUFFER, index_type.get_values_desc()]
     }
   }
 
@@ -128,43 +131,97 @@ impl DataType {
       &DataType::String => "utf8",
       &DataType::Binary => "binary",
       &DataType::Date => "date",
-      &DataType::Timestamp { unit: ref unit} => "timestamp",
-      &DataType::Time { unit: ref unit } => "time",
-      &DataType::Interval { unit: ref unit } => "interval",
-      &DataType::Decimal { precision: precision, scale: scale } => "decimal",
-      &DataType::List { field: ref field } => "list",
-      &DataType::Struct { children: ref children } => "struct",
-      &DataType::Union { mode: ref mode } => "union",
-      &DataType::Dictionary { index_type: ref index_type, dictionary: ref dictionary } => "dictionary",
-      _ => panic!()
+      &DataType::Timestamp { .. } => "timestamp",
+      &DataType::Time { .. } => "time",
+      &DataType::Interval { .. } => "interval",
+      &DataType::Decimal { .. } => "decimal",
+      &DataType::List { .. } => "list",
+      &DataType::Struct { .. } => "struct",
+      &DataType::Union { .. } => "union",
+      &DataType::Dictionary { .. } => "dictionary"
     }
   }
 
+  pub fn is_fixed_width(&self) -> bool {
+    match *self {
+      DataType::Null | DataType::String | DataType::Binary | DataType::List { .. } |
+      DataType::Struct { .. } | DataType::Union { .. } => false,
+      _ => true
+    }
+  }
+
+  // Panics if the type is not fixed width
   pub fn bit_width(&self) -> i32 {
     match *self {
       DataType::Bool => 1,
-      _ => panic!()
+      DataType::Uint8 | DataType::Int8 => 8,
+      DataType::Uint16 | DataType::Int16 | DataType::HalfFloat => 16,
+      DataType::Uint32 | DataType::Int32 | DataType::Float | DataType::Date => 32,
+      DataType::Uint64 | DataType::Int64 | DataType::Double => 64,
+      DataType::Timestamp { .. } | DataType::Time { .. } | DataType::Interval { .. } => 64,
+      DataType::Decimal { .. } => 128,
+      DataType::Dictionary { ref index_type, .. } => index_type.bit_width(),
+      _ => panic!("{} is not a fixed width type", self.get_name())
+    }
+  }
+
+  fn get_values_desc(&self) -> &'static BufferDesc {
+    match self.bit_width() {
+      1 => K_VALUES_1,
+      8 => K_VALUES_8,
+      16 => K_VALUES_16,
+      32 => K_VALUES_32,
+      64 => K_VALUES_64,
+      _ => K_VALUES_128
+    }
+  }
+
+  pub fn is_nested(&self) -> bool {
+    match *self {
+      DataType::List { .. } | DataType::Struct { .. } | DataType::Union { .. } => true,
+      _ => false
     }
   }
 
+  // Panics if i is out of the bounds of the children
   pub fn child(&self, i: usize) -> &Field {
-    unimplemented!()
+    &self.get_children()[i]
   }
 
-  pub fn get_children(&self) -> &Vec<Field> {
-    unimplemented!()
+  // Non-nested types have no children
+  pub fn get_children(&self) -> &[Field] {
+    match *self {
+      DataType::List { ref field } => slice::from_ref(&**field),
+      DataType::Struct { ref children } => children,
+      DataType::Union { ref children, .. } => children,
+      _ => &[]
+    }
   }
 
   pub fn num_children(&self) -> i32 {
-    unimplemented!()
+    self.get_children().len() as i32
   }
 
+  // Panics if the type is not numeric. Temporal types are stored as signed integers.
   pub fn is_signed(&self) -> bool {
-    unimplemented!()
+    match *self {
+      DataType::Uint8 | DataType::Uint16 | DataType::Uint32 | DataType::Uint64 => false,
+      DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => true,
+      DataType::HalfFloat | DataType::Float | DataType::Double | DataType::Decimal { .. } => true,
+      DataType::Date | DataType::Timestamp { .. } | DataType::Time { .. } | DataType::Interval { .. } => true,
+      DataType::Dictionary { ref index_type, .. } => index_type.is_signed(),
+      _ => panic!("{} is not a numeric type", self.get_name())
+    }
   }
 
+  // Panics if the type is not a floating point type
   pub fn precision(&self) -> Precision {
-    unimplemented!()
+    match *self {
+      DataType::HalfFloat => Precision::HALF,
+      DataType::Float => Precision::SINGLE,
+      DataType::Double => Precision::DOUBLE,
+      _ => panic!("{} is not a floating point type", self.get_name())
+    }
   }
 }
 
@@ -174,7 +231,61 @@ impl ToString for DataType {
   }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+impl Index<usize> for DataType {
+  type Output = Field;
+
+  fn index(&self, index: usize) -> &Field {
+    self.child(index)
+  }
+}
+
+impl Typed for DataType {
+  fn get_type(&self) -> DataType {
+    self.clone()
+  }
+
+  fn get_buffer_layout(&self) -> Vec<&BufferDesc> {
+    DataType::get_buffer_layout(self)
+  }
+
+  fn get_name(&self) -> &'static str {
+    DataType::get_name(self)
+  }
+}
+
+impl NestedTyped for DataType {
+  fn child(&self, i: usize) -> &Field {
+    DataType::child(self, i)
+  }
+
+  fn get_children(&self) -> &[Field] {
+    DataType::get_children(self)
+  }
+
+  fn num_children(&self) -> i32 {
+    DataType::num_children(self)
+  }
+}
+
+impl FixedWidth for DataType {
+  fn get_bit_width(&self) -> i32 {
+    self.bit_width()
+  }
+}
+
+impl IntegerTyped for DataType {
+  fn is_signed(&self) -> bool {
+    DataType::is_signed(self)
+  }
+}
+
+impl FloatTyped for DataType {
+  fn precision(&self) -> Precision {
+    DataType::precision(self)
+  }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum TimeUnit {
   SECOND,
   MILLI,
@@ -196,7 +307,16 @@ pub struct BufferDesc {
   bit_width: i32
 }
 
-// TODO: pub?
+impl BufferDesc {
+  pub fn get_type(&self) -> &BufferType {
+    &self.ty
+  }
+
+  pub fn get_bit_width(&self) -> i32 {
+    self.bit_width
+  }
+}
+
 pub static K_VALIDITY_BUFFER: &'static BufferDesc = &BufferDesc {
   ty: BufferType::VALIDITY,
   bit_width: 1
@@ -230,14 +350,7 @@ define_buffer_desc!(K_VALUES_8, 8);
 define_buffer_desc!(K_VALUES_16, 16);
 define_buffer_desc!(K_VALUES_32, 32);
 define_buffer_desc!(K_VALUES_64, 64);
-
-//pub trait VisitType {
-//  // TODO
-//}
-
-//pub trait AcceptVisitor {
-//  fn accept(&self, visit_type: &VisitType);
-//}
+define_buffer_desc!(K_VALUES_128, 128);
 
 // Required to implement this trait for every data types
 pub trait Typed {
@@ -249,7 +362,7 @@ pub trait Typed {
 // Required to implement this trait for structured data types
 pub trait NestedTyped {
   fn child(&self, i: usize) -> &Field;
-  fn get_children(&self) -> &Vec<Field>;
+  fn get_children(&self) -> &[Field];
   fn num_children(&self) -> i32;
 }
 
@@ -274,7 +387,7 @@ pub trait FloatTyped {
   fn precision(&self) -> Precision;
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Field {
   name: String,
   ty: DataType,
@@ -331,370 +444,15 @@ impl ToString for Field {
   fn to_string(&self) -> String {
     let str = self.name.clone() + ": " + self.ty.get_name();
     if self.nullable {
-      str + " not null"
-    } else {
       str
+    } else {
+      str + " not null"
     }
   }
 }
 
-// TODO: singleton?
-//#[derive(Debug, Eq, PartialEq)]
-//pub struct NullType {}
-//
-//impl NullType {
-//  pub fn new() -> NullType {
-//    NullType {}
-//  }
-//}
-//
-//impl Typed for NullType {
-//  fn get_type(&self) -> RawType {
-//    RawType::NULL
-//  }
-//
-//  fn get_buffer_layout(&self) -> Vec<&BufferDesc> {
-//    vec![]
-//  }
-//
-//  fn get_name(&self) -> &'static str {
-//    "null"
-//  }
-//}
-//
-//#[derive(Debug, Eq, PartialEq)]
-//pub struct BooleanType {}
-//
-//impl BooleanType {
-//  pub fn new() -> BooleanType {
-//    BooleanType {}
-//  }
-//}
-//
-//impl Typed for BooleanType {
-//  fn get_type(&self) -> RawType {
-//    RawType::BOOL
-//  }
-//
-//  fn get_buffer_layout(&self) -> Vec<&BufferDesc> {
-//    vec![K_VALIDITY_BUFFER, K_VALUES_1]
-//  }
-//
-//  fn get_name(&self) -> &'static str {
-//    "bool"
-//  }
-//}
-//
-//impl FixedWidth for BooleanType {
-//  fn get_bit_width(&self) -> i32 {
-//    1
-//  }
-//}
-//
-////impl AcceptVisitor for NullType {
-////  fn accept(&self, visit_type: &VisitType) {
-////    // TODO
-////  }
-////}
-//
-//macro_rules! define_primitive_type {
-//  ($type_name: ident, $str_name: expr, $raw_type: expr, $rust_type: ty, $buffer_desc: expr) => {
-//    #[derive(Debug, Eq, PartialEq)]
-//    pub struct $type_name {}
-//
-//    impl $type_name {
-//      pub fn new() -> $type_name {
-//        $type_name {}
-//      }
-//    }
-//
-//    impl Typed for $type_name {
-//      fn get_type(&self) -> RawType {
-//        $raw_type
-//      }
-//
-//      fn get_buffer_layout(&self) -> Vec<&BufferDesc> {
-//        vec![K_VALIDITY_BUFFER, $buffer_desc] // TODO?
-//      }
-//
-//      fn get_name(&self) -> &'static str {
-//        $str_name
-//      }
-//    }
-//
-//    impl FixedWidth for $type_name {
-//      fn get_bit_width(&self) -> i32 {
-//        mem::size_of::<$rust_type>() as i32
-//      }
-//    }
-//  }
-//}
-//
-//macro_rules! impl_int_type {
-//  ($type_name: ident, $is_signed: expr) => (
-//    impl IntegerTyped for $type_name {
-//      fn is_signed(&self) -> bool {
-//        $is_signed
-//      }
-//    }
-//  );
-//}
-//
-//macro_rules! impl_float_type {
-//  ($type_name: ident, $precision: expr) => (
-//    impl FloatTyped for $type_name {
-//      fn precision(&self) -> Precision {
-//        $precision
-//      }
-//    }
-//  );
-//}
-//
-////macro_rules! impl_nested_type {
-////  ($type_name: ident) => (
-////    impl<T: Typed> NestedTyped<T> for $type_name<T> {
-////      #[inline]
-////      fn child(&self, i: usize) -> &Field<T> {
-////        &self.children[i]
-////      }
-////
-////      #[inline]
-////      fn get_children(&self) -> &Vec<Field<T>> {
-////        &self.children
-////      }
-////
-////      #[inline]
-////      fn num_children(&self) -> i32 {
-////        self.children.len() as i32
-////      }
-////    }
-////
-////    impl<T: Typed> Index<usize> for $type_name<T> {
-////      type Output = Field<T>;
-////
-////      #[inline]
-////      fn index(&self, index: usize) -> &Field<T> {
-////        &self.children[index]
-////      }
-////    }
-////  );
-////}
-//
-//define_primitive_type!(UInt8Type, "uint8", RawType::UINT8, u8, K_VALUES_8);
-//define_primitive_type!(UInt16Type, "uint16", RawType::UINT16, u16, K_VALUES_16);
-//define_primitive_type!(UInt32Type, "uint32", RawType::UINT32, u32, K_VALUES_32);
-//define_primitive_type!(UInt64Type, "uint64", RawType::UINT64, u64, K_VALUES_64);
-//define_primitive_type!(Int8Type, "int8", RawType::INT8, i8, K_VALUES_8);
-//define_primitive_type!(Int16Type, "int16", RawType::INT16, i16, K_VALUES_16);
-//define_primitive_type!(Int32Type, "int32", RawType::INT32, i32, K_VALUES_32);
-//define_primitive_type!(Int64Type, "int64", RawType::INT64, i64, K_VALUES_64);
-//impl_int_type!(UInt8Type, false);
-//impl_int_type!(UInt16Type, false);
-//impl_int_type!(UInt32Type, false);
-//impl_int_type!(UInt64Type, false);
-//impl_int_type!(Int8Type, true);
-//impl_int_type!(Int16Type, true);
-//impl_int_type!(Int32Type, true);
-//impl_int_type!(Int64Type, true);
-//
-//define_primitive_type!(HalfFloatType, "halffloat", RawType::HALF_FLOAT, u16, K_VALUES_16);
-//define_primitive_type!(FloatType, "float", RawType::FLOAT, f32, K_VALUES_32);
-//define_primitive_type!(DoubleType, "double", RawType::DOUBLE, f64, K_VALUES_64);
-//impl_float_type!(HalfFloatType, Precision::HALF);
-//impl_float_type!(FloatType, Precision::SINGLE);
-//impl_float_type!(DoubleType, Precision::DOUBLE);
-//
-////impl AcceptVisitor for UInt8Type {
-////  fn accept(&self, visit_type: &VisitType) {
-////    unimplemented!()
-////  }
-////}
-//
-////#[derive(Debug, Eq, PartialEq)]
-////pub struct ListType<T: Typed> {
-////  children: Vec<Field<T>>
-////}
-////
-////impl<T: Typed> ListType<T> {
-////  pub fn with_value_type(value_type: T) -> ListType<T> {
-////    ListType::with_field(Field::basic("item", value_type))
-////  }
-////
-////  pub fn with_field(field: Field<T>) -> ListType<T> {
-////    ListType {
-////      children: vec![field]
-////    }
-////  }
-////
-////  pub fn value_field(&self) -> &Field<T> {
-////    &self.children[0]
-////  }
-////
-////  pub fn value_type(&self) -> &T {
-////    self.children[0].get_type()
-////  }
-////}
-////
-////impl<T: Typed> Typed for ListType<T> {
-////  fn get_type(&self) -> RawType {
-////    RawType::LIST
-////  }
-////
-////  fn get_buffer_layout(&self) -> Vec<&BufferDesc> {
-////    vec![K_VALIDITY_BUFFER, K_OFFSET_BUFFER]
-////  }
-////
-////  fn get_name(&self) -> &'static str {
-////    "list"
-////  }
-////}
-////
-////impl_nested_type!(ListType);
-////
-////#[derive(Debug, Eq, PartialEq)]
-////pub struct BinaryType {}
-////
-////impl BinaryType {
-////  pub fn new() -> BinaryType {
-////    BinaryType {}
-////  }
-////}
-////
-////impl Typed for BinaryType {
-////  fn get_type(&self) -> RawType {
-////    RawType::BINARY
-////  }
-////
-////  fn get_buffer_layout(&self) -> Vec<&BufferDesc> {
-////    vec![K_VALIDITY_BUFFER, K_OFFSET_BUFFER, K_VALUES_8]
-////  }
-////
-////  fn get_name(&self) -> &'static str {
-////    "binary"
-////  }
-////}
-////
-////#[derive(Debug, Eq, PartialEq)]
-////pub struct StringType {}
-////
-////impl StringType {
-////  pub fn new() -> StringType {
-////    StringType {}
-////  }
-////}
-////
-////impl Typed for StringType {
-////  fn get_type(&self) -> RawType {
-////    RawType::STRING
-////  }
-////
-////  fn get_buffer_layout(&self) -> Vec<&BufferDesc> {
-////    vec![K_VALIDITY_BUFFER, K_OFFSET_BUFFER, K_VALUES_8]
-////  }
-////
-////  fn get_name(&self) -> &'static str {
-////    "utf8"
-////  }
-////}
-////
-////#[derive(Debug, Eq, PartialEq)]
-////pub struct StructType<T: Typed> {
-////  children: Vec<Field<T>>
-////}
-////
-////impl<T: Typed> StructType<T> {
-////  pub fn new(fields: Vec<Field<T>>) -> StructType<T> {
-////    StructType {
-////      children: fields
-////    }
-////  }
-////}
-////
-////impl<T:Typed> Typed for StructType<T> {
-////  fn get_type(&self) -> RawType {
-////    RawType::STRUCT
-////  }
-////
-////  fn get_buffer_layout(&self) -> Vec<&BufferDesc> {
-////    vec![K_VALIDITY_BUFFER]
-////  }
-////
-////  fn get_name(&self) -> &'static str {
-////    "struct"
-////  }
-////}
-////
-////impl_nested_type!(StructType);
-////
-////#[derive(Debug, Eq, PartialEq)]
-////pub struct DecimalType {
-////  precision: i32,
-////  scale: i32
-////}
-////
-////impl DecimalType {
-////  pub fn new(precision: i32, scale: i32) -> DecimalType {
-////    DecimalType {
-////      precision: precision,
-////      scale: scale
-////    }
-////  }
-////}
-////
-////impl Typed for DecimalType {
-////  fn get_type(&self) -> RawType {
-////    RawType::DECIMAL
-////  }
-////
-////  fn get_buffer_layout(&self) -> Vec<&BufferDesc> {
-////    // TODO
-////    vec![]
-////  }
-////
-////  fn get_name(&self) -> &'static str {
-////    unimplemented!()
-////  }
-////}
-
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum UnionMode {
   SPARSE,
   DENSE
-}
-
-////#[derive(Debug, Eq, PartialEq)]
-////pub struct UnionType<T: Typed> {
-////  children: Vec<Field<T>>,
-////  type_codes: Vec<u8>,
-////  mode: UnionMode,
-////}
-////
-////impl<T:Typed> UnionType<T> {
-////  pub fn new_sparse_type(fields: Vec<Field<T>>, type_codes: Vec<u8>) -> UnionType<T> {
-////    UnionType {
-////      children: fields,
-////      type_codes: type_codes,
-////      mode: UnionMode::SPARSE
-////    }
-////  }
-////
-////  pub fn new_dense_type(fields: Vec<Field<T>>, type_codes: Vec<u8>) -> UnionType<T> {
-////    UnionType {
-////      children: fields,
-////      type_codes: type_codes,
-////      mode: UnionMode::DENSE
-////    }
-////  }
-////}
-////
-////impl_nested_type!(UnionType);
-//
-//// DateType
-//
-//// TimeType
-//
-//// TimestampType
-//
-//// IntervalType
-//
-//// DictionaryType
\ No newline at end of file
+}
\ No newline at end of file
@@ -1,5 +1,126 @@
 pub mod common;
+pub mod array;
+pub mod buffer;
 
-pub struct Array {
+pub use self::array::{Array, ArrayData};
 
+#[cfg(test)]
+mod tests {
+
+  use native::{Array, ArrayData};
+  use native::buffer::Buffer;
+  use native::common::ty::*;
+
+  fn new_array(data_type: DataType, len: i64, null_count: i64, offset: i64,
+               buffers: Vec<Option<Buffer>>, children: Vec<ArrayData>) -> Array {
+    match ArrayData::new(data_type, len, null_count, offset, buffers, children) {
+      Ok(data) => Array::new(data),
+      Err(e) => panic!("invalid array data: {}", e.message())
+    }
+  }
+
+  #[test]
+  fn test_buffer() {
+    use native::buffer::ALIGNMENT;
+
+    let buffer = Buffer::from(vec![1u8, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]);
+    assert_eq!(12, buffer.len());
+    assert_eq!(ALIGNMENT, buffer.capacity());
+    assert_eq!(0, buffer.as_ptr() as usize % ALIGNMENT);
+    assert_eq!(&[1, 2, 3], buffer.typed_data::<i32>());
+
+    let cloned = buffer.clone();
+    assert!(cloned.ptr_eq(&buffer));
+
+    let sliced = buffer.slice(4, 8);
+    assert_eq!(8, sliced.len());
+    assert_eq!(&[2, 3], sliced.typed_data::<i32>());
+    assert_eq!(&buffer.as_slice()[4..], sliced.as_slice());
+    assert_eq!(Buffer::from(vec![2, 0, 0, 0, 3, 0, 0, 0]), sliced);
+
+    let empty = Buffer::zeroed(0);
+    assert!(empty.is_empty());
+    assert_eq!(0, empty.capacity());
+    assert_eq!(&[0; 3], Buffer::zeroed(3).as_slice());
+  }
+
+  #[test]
+  #[should_panic]
+  fn test_buffer_slice_out_of_bounds() {
+    Buffer::from(vec![1, 2, 3]).slice(2, 2);
+  }
+
+  #[test]
+  fn test_buffer_across_threads() {
+    use std::thread;
+
+    let buffer = Buffer::from(vec![1, 2, 3, 4]);
+    let sliced = buffer.slice(2, 2);
+    let handle = thread::spawn(move || sliced.as_slice().iter().map(|v| *v as i32).sum::<i32>());
+    assert_eq!(7, handle.join().unwrap());
+    assert_eq!(4, buffer.len());
+  }
+
+  #[test]
+  fn test_primitive_array() {
+    // [1, null, 3]
+    let array = new_array(DataType::Int16, 3, 1, 0, vec![Some(Buffer::from(vec![0b101])), Some(Buffer::from(vec![1, 0, 0, 0, 3, 0]))], vec![]);
+    assert_eq!(&DataType::Int16, array.data_type());
+    assert_eq!(3, array.len());
+    assert_eq!(1, array.null_count());
+    assert!(array.is_valid(0));
+    assert!(array.is_null(1));
+    assert_eq!(Some(&Buffer::from(vec![1, 0, 0, 0, 3, 0])), array.data().buffer(1));
+
+    // slots from the offset on
+    let array = new_array(DataType::Int8, 2, 1, 1, vec![Some(Buffer::from(vec![0b011])), Some(Buffer::from(vec![1, 2, 3]))], vec![]);
+    assert!(array.is_valid(0));
+    assert!(array.is_null(1));
+
+    // the validity buffer can be omitted without nulls
+    let array = new_array(DataType::Bool, 9, 0, 0, vec![None, Some(Buffer::from(vec![0xff, 0x01]))], vec![]);
+    assert!(array.data().buffer(0).is_none());
+    assert!(array.is_valid(8));
+  }
+
+  #[test]
+  #[should_panic]
+  fn test_array_index_out_of_bounds() {
+    let array = new_array(DataType::Int8, 2, 1, 1, vec![Some(Buffer::from(vec![0b011])), Some(Buffer::from(vec![1, 2, 3]))], vec![]);
+    array.is_valid(2);
+  }
+
+  #[test]
+  fn test_invalid_array_data() {
+    assert!(ArrayData::new(DataType::Int32, 2, 0, 0, vec![None], vec![]).is_err());
+    assert!(ArrayData::new(DataType::Int32, 2, 0, 0, vec![None, Some(Buffer::from(vec![0; 7]))], vec![]).is_err());
+    assert!(ArrayData::new(DataType::Int32, 2, 1, 0, vec![None, Some(Buffer::from(vec![0; 8]))], vec![]).is_err());
+    assert!(ArrayData::new(DataType::Int32, 2, 3, 0, vec![Some(Buffer::from(vec![0])), Some(Buffer::from(vec![0; 8]))], vec![]).is_err());
+    assert!(ArrayData::new(DataType::Int32, 2, 0, 1, vec![None, Some(Buffer::from(vec![0; 8]))], vec![]).is_err());
+  }
+
+  #[test]
+  fn test_nested_array() {
+    // [[1, 2], null, [3]]
+    let values = match ArrayData::new(DataType::Uint8, 3, 0, 0, vec![None, Some(Buffer::from(vec![1, 2, 3]))], vec![]) {
+      Ok(data) => data,
+      Err(e) => panic!("invalid array data: {}", e.message())
+    };
+    let offsets = Buffer::from(vec![0, 0, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]);
+    let array = new_array(DataType::list(DataType::Uint8), 3, 1, 0, vec![Some(Buffer::from(vec![0b101])), Some(offsets.clone())], vec![values.clone()]);
+    assert_eq!(1, array.data().children().len());
+    assert_eq!(&values, array.data().child(0));
+    assert!(array.is_null(1));
+
+    // children must match the fields of the type
+    assert!(ArrayData::new(DataType::list(DataType::Uint8), 3, 0, 0, vec![None, Some(offsets.clone())], vec![]).is_err());
+    assert!(ArrayData::new(DataType::list(DataType::Int8), 3, 0, 0, vec![None, Some(offsets)], vec![values]).is_err());
+  }
+
+  #[test]
+  fn test_null_array() {
+    let array = new_array(DataType::Null, 2, 2, 0, vec![], vec![]);
+    assert!(array.is_null(0));
+    assert_eq!(2, array.null_count());
+  }
 }
\ No newline at end of file
@@ -1,9 +1,11 @@
 use ty;
-use ty::{Schema};
+use ty::{Schema, Field};
 use common::status;
 use common::status::{RawStatusPtr, ArrowError};
+use common::memory_pool::MemoryPool;
 use column::{RawColumnPtr, Column};
-use array::{RawArrayPtr, Array};
+use array::{RawArrayPtr, Array, BaseArray};
+use array_builder::{ArrayBuilder, Value, make_builder, check_value, append_value};
 use ipc::adapter::c_api::get_row_batch_size;
 use libc;
 use std::ffi::{CStr, CString};
@@ -65,6 +67,88 @@ impl Drop for RowBatch {
   }
 }
 
+// Builds a row batch with one builder per field of the schema, either row by row with append_row()
+// or column by column through column_builder()
+pub struct RowBatchBuilder {
+  schema: Schema,
+  fields: Vec<Field>,
+  builders: Vec<Box<ArrayBuilder>>
+}
+
+impl RowBatchBuilder {
+  pub fn new(schema: &Schema, pool: &MemoryPool) -> Result<RowBatchBuilder, ArrowError> {
+    // Each builder is made for the type of its field, and column_builder() cannot replace it, so
+    // the finished columns always match the schema
+    let fields = (0..schema.len()).map(|i| schema.field(i)).collect::<Vec<Field>>();
+    let mut builders = Vec::with_capacity(fields.len());
+    for field in &fields {
+      builders.push(try!(make_builder(&field.data_type(), pool)));
+    }
+
+    Ok(RowBatchBuilder {
+      schema: Schema::new(&fields.iter().collect::<Vec<&Field>>()),
+      fields: fields,
+      builders: builders
+    })
+  }
+
+  pub fn schema(&self) -> &Schema {
+    &self.schema
+  }
+
+  pub fn column_num(&self) -> i32 {
+    self.builders.len() as i32
+  }
+
+  // The length of the first column. Columns appended separately may differ until finish().
+  pub fn row_num(&self) -> i32 {
+    self.builders.first().map_or(0, |builder| builder.len())
+  }
+
+  pub fn column_builder(&mut self, i: i32) -> &mut ArrayBuilder {
+    &mut *self.builders[i as usize]
+  }
+
+  // Returns None if the builder of the i-th column is not a B
+  pub fn typed_column_builder<B: ArrayBuilder + 'static>(&mut self, i: i32) -> Option<&mut B> {
+    self.builders[i as usize].as_any_mut().downcast_mut::<B>()
+  }
+
+  // Appends one value per field. The whole row is checked first, so nothing is appended if any
+  // value does not match its field.
+  pub fn append_row(&mut self, row: &[Value]) -> Result<&mut RowBatchBuilder, ArrowError> {
+    if row.len() != self.fields.len() {
+      return Err(ArrowError::invalid(format!("the row has {} values but the schema has {} fields",
+                                             row.len(), self.fields.len())));
+    }
+    for (field, value) in self.fields.iter().zip(row.iter()) {
+      if *value == Value::Null && !field.nullable() {
+        return Err(ArrowError::invalid(format!("field {} is not nullable", field.name())));
+      }
+      try!(check_value(&field.data_type(), value));
+    }
+
+    for ((field, builder), value) in self.fields.iter().zip(self.builders.iter_mut()).zip(row.iter()) {
+      try!(append_value(&mut **builder, &field.data_type(), value));
+    }
+    Ok(self)
+  }
+
+  // Fails without finishing any column if the columns differ in length
+  pub fn finish(&mut self) -> Result<RowBatch, ArrowError> {
+    let row_num = self.row_num();
+    for (field, builder) in self.fields.iter().zip(self.builders.iter()) {
+      if builder.len() != row_num {
+        return Err(ArrowError::invalid(format!("column {} has {} rows but {} were expected",
+                                               field.name(), builder.len(), row_num)));
+      }
+    }
+
+    let arrays = try!(self.builders.iter_mut().map(|builder| builder.finish()).collect::<Result<Vec<BaseArray>, ArrowError>>());
+    Ok(RowBatch::new(&self.schema, row_num, &arrays))
+  }
+}
+
 pub struct Table {
   raw_table: RawTablePtr
 }
@@ -92,6 +92,13 @@ DataTypeBox* new_list_type(DataTypeBox* value_type) {
   return box;
 }
 
+DataTypeBox* new_list_type_with_field(FieldBox* value_field) {
+  DataTypeBox* box = new DataTypeBox;
+  box->sp = std::make_shared<ListType>(value_field->sp);
+  box->p = box->sp.get();
+  return box;
+}
+
 DataTypeBox* new_binary_type() {
   DataTypeBox* box = new DataTypeBox;
   box->sp = std::make_shared<BinaryType>();
@@ -116,7 +123,124 @@ DataTypeBox* new_struct_type(int field_num, FieldBox* fields []) {
   box->p = box->sp.get();
   return box;
 }
+
+// Union of the types of the child fields, where type id i selects the i-th child
+struct UnionTypeImpl : public DataType {
+  UnionTypeImpl(const std::vector<std::shared_ptr<Field>>& fields, bool dense)
+      : DataType(dense ? Type::DENSE_UNION : Type::SPARSE_UNION) {
+    children_ = fields;
+  }
+
+  std::string ToString() const override {
+    std::string str = type == Type::DENSE_UNION ? "union[dense]<" : "union[sparse]<";
+    for (size_t i = 0; i < children_.size(); i++) {
+      if (i > 0) {
+        str += ", ";
+      }
+      str += children_[i]->ToString();
+    }
+    return str + ">";
+  }
+};
+
+DataTypeBox* new_union_type(FieldBox* fields [], int field_num, bool dense) {
+  std::vector<std::shared_ptr<Field>> vec;
+  for (int i = 0; i < field_num; i++) {
+    vec.push_back(fields[i]->sp);
+  }
+  DataTypeBox* box = new DataTypeBox;
+  box->sp = std::make_shared<UnionTypeImpl>(vec, dense);
+  box->p = box->sp.get();
+  return box;
+}
+
+// Date, timestamp or time type. unit is the index of a Rust TimeUnit, which dates do not have, and
+// an empty timezone means none.
+struct TemporalTypeImpl : public DataType {
+  TemporalTypeImpl(Type::type ty, int unit, const std::string& timezone)
+      : DataType(ty), unit(unit), timezone(timezone) {}
+
+  std::string ToString() const override {
+    static const char* unit_names[] = {"s", "ms", "us", "ns"};
+    switch (type) {
+      case Type::DATE:
+        return "date";
+      case Type::TIMESTAMP:
+        return std::string("timestamp[") + unit_names[unit] + (timezone.empty() ? "" : ", tz=" + timezone) + "]";
+      default:
+        return std::string("time[") + unit_names[unit] + "]";
+    }
+  }
+
+  int unit;
+  std::string timezone;
+};
+
+DataTypeBox* new_temporal_type(Type::type ty, int unit, const char* timezone) {
+  DataTypeBox* box = new DataTypeBox;
+  box->sp = std::make_shared<TemporalTypeImpl>(ty, unit, timezone ? std::string(timezone) : std::string());
+  box->p = box->sp.get();
+  return box;
+}
+
+int temporal_type_unit(DataTypeBox* dt) {
+  TemporalTypeImpl* temporal = dynamic_cast<TemporalTypeImpl*>(dt->p);
+  return temporal ? temporal->unit : -1;
+}
+
+// Points into the type, so the type must outlive the string
+const char* temporal_type_timezone(DataTypeBox* dt) {
+  TemporalTypeImpl* temporal = dynamic_cast<TemporalTypeImpl*>(dt->p);
+  return temporal && !temporal->timezone.empty() ? temporal->timezone.c_str() : nullptr;
+}
+
+// Decimal type whose values are 128-bit unscaled integers
+struct DecimalTypeImpl : public DataType {
+  DecimalTypeImpl(int precision, int scale)
+      : DataType(Type::DECIMAL), precision(precision), scale(scale) {}
+
+  int value_size() const override {
+    return 16;
+  }
+
+  std::string ToString() const override {
+    return "decimal(" + std::to_string(precision) + ", " + std::to_string(scale) + ")";
+  }
+
+  int precision;
+  int scale;
+};
+
+DataTypeBox* new_decimal_type(int precision, int scale) {
+  DataTypeBox* box = new DataTypeBox;
+  box->sp = std::make_shared<DecimalTypeImpl>(precision, scale);
+  box->p = box->sp.get();
+  return box;
+}
+
+int decimal_type_precision(DataTypeBox* dt) {
+  DecimalTypeImpl* decimal = dynamic_cast<DecimalTypeImpl*>(dt->p);
+  return decimal ? decimal->precision : -1;
+}
+
+int decimal_type_scale(DataTypeBox* dt) {
+  DecimalTypeImpl* decimal = dynamic_cast<DecimalTypeImpl*>(dt->p);
+  return decimal ? decimal->scale : -1;
+}
+
+// DataType::Equals() only compares the type enum and the children, which ignores temporal units
+// and decimal parameters
 bool data_type_equals(const DataTypeBox* dt1, const DataTypeBox* dt2) {
+  const TemporalTypeImpl* t1 = dynamic_cast<const TemporalTypeImpl*>(dt1->p);
+  const TemporalTypeImpl* t2 = dynamic_cast<const TemporalTypeImpl*>(dt2->p);
+  if (t1 && t2 && (t1->unit != t2->unit || t1->timezone != t2->timezone)) {
+    return false;
+  }
+  const DecimalTypeImpl* d1 = dynamic_cast<const DecimalTypeImpl*>(dt1->p);
+  const DecimalTypeImpl* d2 = dynamic_cast<const DecimalTypeImpl*>(dt2->p);
+  if (d1 && d2 && (d1->precision != d2->precision || d1->scale != d2->scale)) {
+    return false;
+  }
   return dt1->p->Equals(dt2->p);
 }
 
@@ -124,6 +248,21 @@ int value_size(DataTypeBox* dt) {
   return dt->p->value_size();
 }
 
+Type::type data_type_enum(DataTypeBox* dt) {
+  return dt->p->type;
+}
+
+int data_type_num_children(DataTypeBox* dt) {
+  return dt->p->num_children();
+}
+
+FieldBox* data_type_child(DataTypeBox* dt, int i) {
+  FieldBox* field = new FieldBox;
+  field->sp = dt->p->child(i);
+  field->p = field->sp.get();
+  return field;
+}
+
 const char* data_type_to_string(DataTypeBox* dt) {
   std::string str = dt->p->ToString();
   char *cstr = new char[str.length() + 1];
@@ -148,6 +287,24 @@ bool field_equals(const FieldBox* f1, const FieldBox* f2) {
   return f1->p->Equals(*(f2->p));
 }
 
+const char* field_name(FieldBox* fp) {
+  std::string str = fp->p->name;
+  char *cstr = new char[str.length() + 1];
+  std::strcpy(cstr, str.c_str());
+  return cstr;
+}
+
+DataTypeBox* field_type(FieldBox* fp) {
+  DataTypeBox* dt = new DataTypeBox;
+  dt->sp = fp->p->type;
+  dt->p = dt->sp.get();
+  return dt;
+}
+
+bool field_nullable(FieldBox* fp) {
+  return fp->p->nullable;
+}
+
 const char* field_to_string(FieldBox* fp) {
   std::string str = fp->p->ToString();
   char *cstr = new char[str.length() + 1];
@@ -188,6 +345,13 @@ bool schema_equals(SchemaBox* s1, SchemaBox* s2) {
   return s1->p->Equals(*(s2->p));
 }
 
+SchemaBox* schema_share(SchemaBox* schema) {
+  SchemaBox* shared = new SchemaBox;
+  shared->sp = schema->sp;
+  shared->p = shared->sp.get();
+  return shared;
+}
+
 const char* schema_to_string(SchemaBox* schema) {
   std::string str = schema->p->ToString();
   char *cstr = new char[str.length() + 1];
@@ -29,16 +29,38 @@ extern "C" {
 
   DataTypeBox* new_list_type(DataTypeBox* value_type);
 
+  DataTypeBox* new_list_type_with_field(FieldBox* value_field);
+
   DataTypeBox* new_binary_type();
 
   DataTypeBox* new_string_type();
 
   DataTypeBox* new_struct_type(int field_num, FieldBox* fields []);
 
+  DataTypeBox* new_union_type(FieldBox* fields [], int field_num, bool dense);
+
+  DataTypeBox* new_temporal_type(Type::type ty, int unit, const char* timezone);
+
+  int temporal_type_unit(DataTypeBox* dt);
+
+  const char* temporal_type_timezone(DataTypeBox* dt);
+
+  DataTypeBox* new_decimal_type(int precision, int scale);
+
+  int decimal_type_precision(DataTypeBox* dt);
+
+  int decimal_type_scale(DataTypeBox* dt);
+
   bool data_type_equals(const DataTypeBox* dt1, const DataTypeBox* dt2);
 
   int value_size(DataTypeBox* dt);
 
+  Type::type data_type_enum(DataTypeBox* dt);
+
+  int data_type_num_children(DataTypeBox* dt);
+
+  FieldBox* data_type_child(DataTypeBox* dt, int i);
+
   const char* data_type_to_string(DataTypeBox* dt);
 
   void release_data_type(DataTypeBox * dt);
@@ -47,6 +69,12 @@ extern "C" {
 
   bool field_equals(const FieldBox* f1, const FieldBox* f2);
 
+  const char* field_name(FieldBox* fp);
+
+  DataTypeBox* field_type(FieldBox* fp);
+
+  bool field_nullable(FieldBox* fp);
+
   const char* field_to_string(FieldBox* fp);
 
   void release_field(FieldBox* fp);
@@ -59,6 +87,8 @@ extern "C" {
 
   bool schema_equals(SchemaBox* s1, SchemaBox* s2);
 
+  SchemaBox* schema_share(SchemaBox* schema);
+
   const char* schema_to_string(SchemaBox* schema);
 
   void release_schema(SchemaBox* schema);
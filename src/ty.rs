@@ -2,8 +2,12 @@ use libc;
 use std::ops::Drop;
 use std::cmp::Eq;
 use std::ffi::{CStr, CString};
+use std::fmt;
+use std::ptr;
 use std::str;
 
+use common::status::ArrowError;
+
 #[macro_use]
 use common;
 
@@ -86,6 +90,172 @@ pub enum Ty {
   USER = 60
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnionMode {
+  SPARSE,
+  DENSE
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimeUnit {
+  SECOND,
+  MILLI,
+  MICRO,
+  NANO
+}
+
+impl TimeUnit {
+  // Number of units in a second
+  pub fn per_second(&self) -> i64 {
+    match *self {
+      TimeUnit::SECOND => 1,
+      TimeUnit::MILLI => 1_000,
+      TimeUnit::MICRO => 1_000_000,
+      TimeUnit::NANO => 1_000_000_000
+    }
+  }
+}
+
+// Logical types of temporal arrays. data_type() creates the native type of the arrays, which
+// carries the unit and the timezone.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TemporalType {
+  // int32 days since the UNIX epoch
+  Date,
+
+  // int64 units since the UNIX epoch, with an optional timezone name such as "Asia/Seoul"
+  Timestamp { unit: TimeUnit, timezone: Option<String> },
+
+  // int64 units since midnight
+  Time { unit: TimeUnit }
+}
+
+impl TemporalType {
+  pub fn ty(&self) -> Ty {
+    match *self {
+      TemporalType::Date => Ty::DATE,
+      TemporalType::Timestamp { .. } => Ty::TIMESTAMP,
+      TemporalType::Time { .. } => Ty::TIME
+    }
+  }
+
+  pub fn unit(&self) -> Option<TimeUnit> {
+    match *self {
+      TemporalType::Date => None,
+      TemporalType::Timestamp { unit, .. } => Some(unit),
+      TemporalType::Time { unit } => Some(unit)
+    }
+  }
+
+  // Fails if the timezone contains a NUL character
+  pub fn timestamp(unit: TimeUnit, timezone: Option<String>) -> Result<TemporalType, ArrowError> {
+    if let Some(ref tz) = timezone {
+      try!(timezone_to_cstring(tz));
+    }
+    Ok(TemporalType::Timestamp { unit: unit, timezone: timezone })
+  }
+
+  // Fails if the timezone contains a NUL character, which the native type cannot hold
+  pub fn data_type(&self) -> Result<DataType, ArrowError> {
+    let timezone = match *self {
+      TemporalType::Timestamp { timezone: Some(ref tz), .. } => Some(try!(timezone_to_cstring(tz))),
+      _ => None
+    };
+    let raw_timezone = timezone.as_ref().map_or(ptr::null(), |tz| tz.as_ptr());
+    let unit = self.unit().map_or(-1, |unit| unit as i32);
+    Ok(DataType::new( unsafe { new_temporal_type(self.ty(), unit, raw_timezone) } ))
+  }
+
+  // Physical type of the values
+  pub fn storage_type(&self) -> DataType {
+    match *self {
+      TemporalType::Date => DataType::new_primitive(Ty::INT32),
+      _ => DataType::new_primitive(Ty::INT64)
+    }
+  }
+}
+
+fn timezone_to_cstring(timezone: &str) -> Result<CString, ArrowError> {
+  match CString::new(timezone) {
+    Ok(timezone) => Ok(timezone),
+    Err(_) => Err(ArrowError::invalid("timezone must not contain a NUL character"))
+  }
+}
+
+impl fmt::Display for TemporalType {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fn unit_name(unit: &TimeUnit) -> &'static str {
+      match *unit {
+        TimeUnit::SECOND => "s",
+        TimeUnit::MILLI => "ms",
+        TimeUnit::MICRO => "us",
+        TimeUnit::NANO => "ns"
+      }
+    }
+
+    match *self {
+      TemporalType::Date => write!(f, "date"),
+      TemporalType::Timestamp { ref unit, timezone: Some(ref tz) } => write!(f, "timestamp[{}, tz={}]", unit_name(unit), tz),
+      TemporalType::Timestamp { ref unit, timezone: None } => write!(f, "timestamp[{}]", unit_name(unit)),
+      TemporalType::Time { ref unit } => write!(f, "time[{}]", unit_name(unit))
+    }
+  }
+}
+
+// The largest precision whose unscaled values fit in 128 bits
+pub const MAX_DECIMAL_PRECISION: i32 = 38;
+
+// Logical type of decimal arrays. Values are stored as 128-bit unscaled integers, so a value v
+// represents v * 10^-scale.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DecimalType {
+  precision: i32,
+  scale: i32
+}
+
+impl DecimalType {
+  pub fn new(precision: i32, scale: i32) -> Result<DecimalType, ArrowError> {
+    if precision < 1 || precision > MAX_DECIMAL_PRECISION {
+      return Err(ArrowError::invalid("decimal precision must be between 1 and 38"));
+    }
+    if scale < 0 || scale > precision {
+      return Err(ArrowError::invalid("decimal scale must be between 0 and the precision"));
+    }
+
+    Ok(DecimalType {
+      precision: precision,
+      scale: scale
+    })
+  }
+
+  pub fn precision(&self) -> i32 {
+    self.precision
+  }
+
+  pub fn scale(&self) -> i32 {
+    self.scale
+  }
+
+  pub fn ty(&self) -> Ty {
+    Ty::DECIMAL
+  }
+
+  pub fn bit_width(&self) -> i32 {
+    128
+  }
+
+  // The native type of decimal arrays, which carries the precision and the scale
+  pub fn data_type(&self) -> DataType {
+    DataType::new( unsafe { new_decimal_type(self.precision, self.scale) } )
+  }
+}
+
+impl fmt::Display for DecimalType {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "decimal({}, {})", self.precision, self.scale)
+  }
+}
+
 #[derive(Debug)]
 pub struct DataType {
   raw_type: RawDataTypePtr
@@ -122,6 +292,15 @@ impl DataType {
     }
   }
 
+  // Keeps the name and the nullability of the value field
+  pub fn new_list_with_field(value_field: &Field) -> DataType {
+    unsafe {
+      DataType {
+        raw_type: new_list_type_with_field(value_field.raw_field)
+      }
+    }
+  }
+
   pub fn new_binary() -> DataType {
     unsafe {
       DataType {
@@ -147,15 +326,81 @@ impl DataType {
     }
   }
 
+  // The i-th field holds the values of type id i
+  pub fn new_union(fields: &[Field], mode: UnionMode) -> DataType {
+    let raw_fields = fields.into_iter().map(|f| f.raw_field).collect::<Vec<RawFieldPtr>>();
+    unsafe {
+      DataType {
+        raw_type: new_union_type(raw_fields.as_ptr(), fields.len() as i32, mode == UnionMode::DENSE)
+      }
+    }
+  }
+
   pub fn value_size(&self) -> i32 {
     unsafe {
       value_size(self.raw_type)
     }
   }
 
+  pub fn ty(&self) -> Ty {
+    unsafe {
+      data_type_enum(self.raw_type)
+    }
+  }
+
+  pub fn num_children(&self) -> i32 {
+    unsafe {
+      data_type_num_children(self.raw_type)
+    }
+  }
+
+  pub fn child(&self, i: i32) -> Field {
+    Field {
+      raw_field: unsafe { data_type_child(self.raw_type, i) }
+    }
+  }
+
   pub fn raw_data_type(&self) -> RawDataTypePtr {
     self.raw_type
   }
+
+  // Unit of timestamp and time types created by TemporalType::data_type()
+  pub fn time_unit(&self) -> Option<TimeUnit> {
+    match unsafe { temporal_type_unit(self.raw_type) } {
+      0 => Some(TimeUnit::SECOND),
+      1 => Some(TimeUnit::MILLI),
+      2 => Some(TimeUnit::MICRO),
+      3 => Some(TimeUnit::NANO),
+      _ => None
+    }
+  }
+
+  // Describes a type created by TemporalType::data_type()
+  pub fn temporal_type(&self) -> Option<TemporalType> {
+    match (self.ty(), self.time_unit()) {
+      (Ty::DATE, _) => Some(TemporalType::Date),
+      (Ty::TIMESTAMP, Some(unit)) => {
+        let raw_timezone = unsafe { temporal_type_timezone(self.raw_type) };
+        let timezone = if raw_timezone.is_null() { None } else { Some(cstr_to_string!(raw_timezone)) };
+        Some(TemporalType::Timestamp { unit: unit, timezone: timezone })
+      },
+      (Ty::TIME, Some(unit)) => Some(TemporalType::Time { unit: unit }),
+      _ => None
+    }
+  }
+
+  // Describes a type created by DecimalType::data_type()
+  pub fn decimal_type(&self) -> Option<DecimalType> {
+    let precision = unsafe { decimal_type_precision(self.raw_type) };
+    if precision < 0 {
+      None
+    } else {
+      Some(DecimalType {
+        precision: precision,
+        scale: unsafe { decimal_type_scale(self.raw_type) }
+      })
+    }
+  }
 }
 
 impl PartialEq<DataType> for DataType {
@@ -181,6 +426,7 @@ impl Drop for DataType {
 }
 
 pub struct DataTypeProvider {
+  bool: DataType,
   u8: DataType,
   i8: DataType,
   u16: DataType,
@@ -190,12 +436,15 @@ pub struct DataTypeProvider {
   u64: DataType,
   i64: DataType,
   f32: DataType,
-  f64: DataType
+  f64: DataType,
+  binary: DataType,
+  string: DataType
 }
 
 impl DataTypeProvider {
   pub fn new() -> DataTypeProvider {
     DataTypeProvider {
+      bool: DataType::new_primitive(Ty::BOOL),
       u8: DataType::new_primitive(Ty::UINT8),
       i8: DataType::new_primitive(Ty::INT8),
       u16: DataType::new_primitive(Ty::UINT16),
@@ -205,10 +454,16 @@ impl DataTypeProvider {
       u64: DataType::new_primitive(Ty::UINT64),
       i64: DataType::new_primitive(Ty::INT64),
       f32: DataType::new_primitive(Ty::FLOAT),
-      f64: DataType::new_primitive(Ty::DOUBLE)
+      f64: DataType::new_primitive(Ty::DOUBLE),
+      binary: DataType::new_binary(),
+      string: DataType::new_string()
     }
   }
 
+  pub fn bool(&self) -> &DataType {
+    &self.bool
+  }
+
   pub fn u8(&self) -> &DataType {
     &self.u8
   }
@@ -248,6 +503,14 @@ impl DataTypeProvider {
   pub fn f64(&self) -> &DataType {
     &self.f64
   }
+
+  pub fn binary(&self) -> &DataType {
+    &self.binary
+  }
+
+  pub fn string(&self) -> &DataType {
+    &self.string
+  }
 }
 
 impl Field {
@@ -257,6 +520,18 @@ impl Field {
     }
   }
 
+  pub fn name(&self) -> String {
+    cstr_to_string!( unsafe { field_name(self.raw_field) } )
+  }
+
+  pub fn data_type(&self) -> DataType {
+    DataType::new(unsafe { field_type(self.raw_field) })
+  }
+
+  pub fn nullable(&self) -> bool {
+    unsafe { field_nullable(self.raw_field) }
+  }
+
   pub fn raw_field(&self) -> RawFieldPtr {
     self.raw_field
   }
@@ -323,6 +598,13 @@ impl PartialEq for Schema {
   }
 }
 
+// Shares the underlying schema
+impl Clone for Schema {
+  fn clone(&self) -> Schema {
+    Schema::from_raw( unsafe { schema_share(self.raw_schema) } )
+  }
+}
+
 impl ToString for Schema {
   fn to_string(&self) -> String {
     cstr_to_string!( unsafe { schema_to_string(self.raw_schema) } )
@@ -348,17 +630,31 @@ pub type RawSchemaPtr = *const RawSchema;
 extern "C" {
   pub fn new_primitive_type(ty: Ty) -> RawDataTypePtr;
   pub fn new_list_type(data_type: RawDataTypePtr) -> RawDataTypePtr;
+  pub fn new_list_type_with_field(value_field: RawFieldPtr) -> RawDataTypePtr;
   pub fn new_binary_type() -> RawDataTypePtr;
   pub fn new_string_type() -> RawDataTypePtr;
   pub fn new_struct_type(field_num: i32, fields: &[RawFieldPtr]) -> RawDataTypePtr;
+  pub fn new_union_type(fields: *const RawFieldPtr, field_num: i32, dense: bool) -> RawDataTypePtr;
+  pub fn new_temporal_type(ty: Ty, unit: i32, timezone: *const libc::c_char) -> RawDataTypePtr;
+  pub fn temporal_type_unit(data_type: RawDataTypePtr) -> i32;
+  pub fn temporal_type_timezone(data_type: RawDataTypePtr) -> *const libc::c_char;
+  pub fn new_decimal_type(precision: i32, scale: i32) -> RawDataTypePtr;
+  pub fn decimal_type_precision(data_type: RawDataTypePtr) -> i32;
+  pub fn decimal_type_scale(data_type: RawDataTypePtr) -> i32;
 
   pub fn data_type_equals(data_type1: RawDataTypePtr, data_type2: RawDataTypePtr) -> bool;
   pub fn value_size(data_type: RawDataTypePtr) -> i32;
+  pub fn data_type_enum(data_type: RawDataTypePtr) -> Ty;
+  pub fn data_type_num_children(data_type: RawDataTypePtr) -> i32;
+  pub fn data_type_child(data_type: RawDataTypePtr, i: i32) -> RawFieldPtr;
   pub fn data_type_to_string(data_type: RawDataTypePtr) -> *const libc::c_char;
   pub fn release_data_type(data_type: RawDataTypePtr);
 
   pub fn new_field(name: *const libc::c_char, data_type: RawDataTypePtr, nullable: bool) -> RawFieldPtr;
   pub fn field_equals(field1: RawFieldPtr, field2: RawFieldPtr) -> bool;
+  pub fn field_name(field: RawFieldPtr) -> *const libc::c_char;
+  pub fn field_type(field: RawFieldPtr) -> RawDataTypePtr;
+  pub fn field_nullable(field: RawFieldPtr) -> bool;
   pub fn field_to_string(field: RawFieldPtr) -> *const libc::c_char;
   pub fn release_field(field: RawFieldPtr);
 
@@ -366,6 +662,7 @@ extern "C" {
   pub fn get_schema_field(schema: RawSchemaPtr, i: i32) -> RawFieldPtr;
   pub fn schema_size(schema: RawSchemaPtr) -> i32;
   pub fn schema_equals(s1: RawSchemaPtr, s2: RawSchemaPtr) -> bool;
+  pub fn schema_share(schema: RawSchemaPtr) -> RawSchemaPtr;
   pub fn schema_to_string(schema: RawSchemaPtr) -> *const libc::c_char;
   pub fn release_schema(schema: RawSchemaPtr);
 }
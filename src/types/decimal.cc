@@ -0,0 +1 @@
+#include "decimal.h"
\ No newline at end of file
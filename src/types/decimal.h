@@ -0,0 +1,142 @@
+#ifndef DECIMAL_H
+#define DECIMAL_H
+
+#include <cstring>
+
+#include "arrow/array.h"
+#include "arrow/builder.h"
+#include "../array.h"
+#include "../buffer.h"
+#include "../ty.h"
+#include "../common/memory_pool.h"
+#include "../common/status.h"
+
+using namespace arrow;
+
+// A decimal array stores each value as 16 bytes of little-endian two's complement
+class DecimalArrayImpl : public Array {
+ public:
+  static const int kValueWidth = 16;
+
+  DecimalArrayImpl(const std::shared_ptr<DataType>& type, int32_t length, const std::shared_ptr<Buffer>& data,
+                   int32_t null_count, const std::shared_ptr<Buffer>& null_bitmap)
+      : Array(type, length, null_count, null_bitmap), data_(data) {}
+
+  const std::shared_ptr<Buffer>& data() const { return data_; }
+
+  const uint8_t* raw_data() const { return data_ ? data_->data() : nullptr; }
+
+  bool Equals(const std::shared_ptr<Array>& arr) const override {
+    if (this == arr.get()) {
+      return true;
+    }
+    if (!arr || length() != arr->length() || null_count() != arr->null_count() ||
+        !type()->Equals(arr->type().get())) {
+      return false;
+    }
+    return RangeEquals(0, length(), 0, arr);
+  }
+
+  bool RangeEquals(int32_t start_idx, int32_t end_idx, int32_t other_start_idx,
+                   const std::shared_ptr<Array>& arr) const override {
+    if (this == arr.get()) {
+      return true;
+    }
+    const DecimalArrayImpl* other = dynamic_cast<const DecimalArrayImpl*>(arr.get());
+    if (!other) {
+      return false;
+    }
+    for (int32_t i = start_idx, o_i = other_start_idx; i < end_idx; ++i, ++o_i) {
+      if (IsNull(i) != other->IsNull(o_i)) {
+        return false;
+      }
+      if (!IsNull(i) &&
+          std::memcmp(raw_data() + i * kValueWidth, other->raw_data() + o_i * kValueWidth, kValueWidth) != 0) {
+        return false;
+      }
+    }
+    return true;
+  }
+
+ private:
+  std::shared_ptr<Buffer> data_;
+};
+
+// Builds the values buffer and the validity bitmap of a decimal array
+class DecimalArrayBuilder : public ArrayBuilder {
+ public:
+  DecimalArrayBuilder(MemoryPool* pool, const std::shared_ptr<DataType>& type)
+      : ArrayBuilder(pool, type), values_(pool) {}
+
+  // value holds kValueWidth bytes, or is null for a null slot
+  Status Append(const uint8_t* value) {
+    static const uint8_t kNullValue[DecimalArrayImpl::kValueWidth] = {0};
+    // the bitmap is reserved first, so a failure never leaves a value without a slot
+    RETURN_NOT_OK(Reserve(1));
+    RETURN_NOT_OK(values_.Append(value ? value : kNullValue, DecimalArrayImpl::kValueWidth));
+    return AppendToBitmap(value != nullptr);
+  }
+
+  std::shared_ptr<Array> Finish() override {
+    auto result = std::make_shared<DecimalArrayImpl>(type_, length_, values_.Finish(), null_count_, null_bitmap_);
+    null_bitmap_ = nullptr;
+    capacity_ = length_ = null_count_ = 0;
+    return result;
+  }
+
+ private:
+  BufferBuilder values_;
+};
+
+extern "C" {
+
+  // Decimal array
+
+  ArrayBox* new_decimal_arr(DataTypeBox* type, int32_t length, BufferBox* data, int32_t null_count,
+                            BufferBox* null_bitmap) {
+    std::shared_ptr<Buffer> bitmap = null_bitmap ? null_bitmap->sp : nullptr;
+    ArrayBox* arr = new ArrayBox;
+    arr->sp = std::make_shared<DecimalArrayImpl>(type->sp, length, data->sp, null_count, bitmap);
+    arr->p = arr->sp.get();
+    return arr;
+  }
+
+  const uint8_t* decimal_arr_raw_data(ArrayBox* arr) {
+    return reinterpret_cast<DecimalArrayImpl*>(arr->p)->raw_data();
+  }
+
+  BufferBox* decimal_arr_data(ArrayBox* arr) {
+    BufferBox* buf = new BufferBox;
+    buf->sp = reinterpret_cast<DecimalArrayImpl*>(arr->p)->data();
+    buf->p = buf->sp.get();
+    return buf;
+  }
+
+  // Decimal array builder
+
+  DecimalArrayBuilder* new_decimal_arr_builder(MemoryPool* pool, DataTypeBox* type) {
+    return new DecimalArrayBuilder(pool, type->sp);
+  }
+
+  // value is null for a null slot
+  StatusBox* append_decimal_arr_builder(DecimalArrayBuilder* builder, const uint8_t* value) {
+    StatusBox* box = new StatusBox;
+    box->status = builder->Append(value);
+    return box;
+  }
+
+  ArrayBox* finish_decimal_arr_builder(DecimalArrayBuilder* builder) {
+    ArrayBox* arr = new ArrayBox;
+    arr->sp = builder->Finish();
+    arr->p = arr->sp.get();
+    return arr;
+  }
+
+  void release_decimal_arr_builder(DecimalArrayBuilder* builder) {
+    if (builder) {
+      delete builder;
+    }
+  }
+}
+
+#endif
@@ -0,0 +1,211 @@
+use array::*;
+use array_builder::{ArrayBuilder, RawArrayBuilderMutPtr};
+use buffer::{Buf, RawBufferPtr};
+use ty::{DataType, DecimalType, RawDataTypePtr, Ty};
+use ty;
+use common::memory_pool::{RawMemoryPoolMutPtr, MemoryPool, PoolRef};
+use common::status::{RawStatusPtr, ArrowError};
+
+use std::any::Any;
+use std::mem;
+use std::ptr;
+
+#[macro_use]
+use common::status;
+
+// Every value occupies 16 bytes of little-endian two's complement
+const VALUE_WIDTH: i32 = 16;
+
+fn max_unscaled(precision: i32) -> i128 {
+  10i128.pow(precision as u32)
+}
+
+// A decimal array stores each value as a 128-bit unscaled integer in a fixed-width values buffer
+pub struct DecimalArray {
+  raw_array: RawArrayPtr
+}
+
+impl DecimalArray {
+  // Fails for arrays whose type was not created by DecimalType::data_type()
+  pub fn decimal_type(&self) -> Result<DecimalType, ArrowError> {
+    match self.data_type().decimal_type() {
+      Some(decimal_type) => Ok(decimal_type),
+      None => Err(ArrowError::type_error(format!("{} is not a decimal type", self.data_type().to_string())))
+    }
+  }
+
+  // Unscaled value of the i-th slot. Panics if i is out of bounds.
+  pub fn value(&self, i: i32) -> i128 {
+    self.check_index(i);
+    let mut bytes = [0u8; 16];
+    unsafe {
+      let data = decimal_arr_raw_data(self.raw_array);
+      ptr::copy_nonoverlapping(data.offset((i * VALUE_WIDTH) as isize), bytes.as_mut_ptr(), 16);
+    }
+    i128::from_le_bytes(bytes)
+  }
+
+  // Formats the i-th value with exactly scale() fractional digits, e.g. "-12.30" for decimal(4, 2)
+  pub fn value_as_string(&self, i: i32) -> Result<String, ArrowError> {
+    let value = self.value(i);
+    let scale = try!(self.decimal_type()).scale() as usize;
+
+    // the precision bounds the value, so negating it cannot overflow
+    let digits = if value < 0 { (-value).to_string() } else { value.to_string() };
+    let digits = if digits.len() <= scale {
+      format!("{}{}", "0".repeat(scale + 1 - digits.len()), digits)
+    } else {
+      digits
+    };
+
+    let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+    let sign = if value < 0 { "-" } else { "" };
+    if scale == 0 {
+      Ok(format!("{}{}", sign, int_part))
+    } else {
+      Ok(format!("{}{}.{}", sign, int_part, frac_part))
+    }
+  }
+
+  fn check_index(&self, i: i32) {
+    assert!(i >= 0 && i < self.len(), "index is out of the bounds of the decimal array");
+  }
+}
+
+impl_array!(DecimalArray, ty::new_decimal_type(1, 0), decimal_arr_data, Ty::DECIMAL);
+
+pub enum RawDecimalArrayBuilder {}
+
+pub struct DecimalArrayBuilder {
+  raw_builder: *mut RawDecimalArrayBuilder,
+  data_type: DecimalType,
+  _pool: PoolRef
+}
+
+impl DecimalArrayBuilder {
+  pub fn new(pool: &MemoryPool, data_type: DecimalType) -> DecimalArrayBuilder {
+    let pool = PoolRef::new(pool);
+    DecimalArrayBuilder {
+      raw_builder: unsafe { new_decimal_arr_builder(pool.raw_memory_pool(), data_type.data_type().raw_data_type()) },
+      data_type: data_type,
+      _pool: pool
+    }
+  }
+
+  pub fn len(&self) -> i32 {
+    ArrayBuilder::len(self)
+  }
+
+  // Appends an unscaled value, which must have at most precision() digits
+  pub fn append_value(&mut self, value: i128) -> Result<&mut DecimalArrayBuilder, ArrowError> {
+    let max = max_unscaled(self.data_type.precision());
+    if value <= -max || value >= max {
+      return Err(ArrowError::invalid("decimal value exceeds the precision"));
+    }
+    let bytes = value.to_le_bytes();
+    unsafe {
+      let s = append_decimal_arr_builder(self.raw_builder, bytes.as_ptr());
+      result_from_status!(s, self)
+    }
+  }
+
+  // Parses a decimal string such as "-12.3". Fractional digits beyond scale() are rejected rather
+  // than rounded, so no value is stored lossily.
+  pub fn append_str(&mut self, value: &str) -> Result<&mut DecimalArrayBuilder, ArrowError> {
+    let (negative, unsigned) = if value.starts_with('-') {
+      (true, &value[1..])
+    } else {
+      (false, value)
+    };
+    let (int_part, frac_part) = match unsigned.find('.') {
+      Some(pos) => (&unsigned[..pos], &unsigned[pos + 1..]),
+      None => (unsigned, "")
+    };
+
+    if int_part.is_empty() && frac_part.is_empty() {
+      return Err(ArrowError::invalid("decimal string has no digits"));
+    }
+    if !int_part.chars().chain(frac_part.chars()).all(|c| c.is_digit(10)) {
+      return Err(ArrowError::invalid("decimal string contains an invalid character"));
+    }
+
+    let scale = self.data_type.scale() as usize;
+    if frac_part.len() > scale {
+      return Err(ArrowError::invalid("decimal string has more fractional digits than the scale"));
+    }
+    let int_part = int_part.trim_start_matches('0');
+    if int_part.len() + scale > self.data_type.precision() as usize {
+      return Err(ArrowError::invalid("decimal value exceeds the precision"));
+    }
+
+    let mut unscaled: i128 = 0;
+    for c in int_part.chars().chain(frac_part.chars()) {
+      unscaled = unscaled * 10 + c.to_digit(10).unwrap() as i128;
+    }
+    unscaled *= 10i128.pow((scale - frac_part.len()) as u32);
+
+    self.append_value(if negative { -unscaled } else { unscaled })
+  }
+
+  pub fn append_null(&mut self) -> Result<&mut DecimalArrayBuilder, ArrowError> {
+    unsafe {
+      let s = append_decimal_arr_builder(self.raw_builder, ptr::null());
+      result_from_status!(s, self)
+    }
+  }
+
+  pub fn append_option(&mut self, value: Option<i128>) -> Result<&mut DecimalArrayBuilder, ArrowError> {
+    match value {
+      Some(v) => self.append_value(v),
+      None => self.append_null()
+    }
+  }
+
+  pub fn finish(&mut self) -> DecimalArray {
+    DecimalArray {
+      raw_array: unsafe { finish_decimal_arr_builder(self.raw_builder) }
+    }
+  }
+
+  pub fn finish_as_base(&mut self) -> BaseArray {
+    let arr = self.finish();
+    unsafe { mem::transmute(arr) }
+  }
+}
+
+impl ArrayBuilder for DecimalArrayBuilder {
+  fn raw_builder(&self) -> RawArrayBuilderMutPtr {
+    self.raw_builder as RawArrayBuilderMutPtr
+  }
+
+  fn append_null(&mut self) -> Result<(), ArrowError> {
+    try!(DecimalArrayBuilder::append_null(self));
+    Ok(())
+  }
+
+  fn as_any(&self) -> &Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut Any {
+    self
+  }
+}
+
+impl Drop for DecimalArrayBuilder {
+  fn drop(&mut self) {
+    unsafe { release_decimal_arr_builder(self.raw_builder); }
+  }
+}
+
+extern "C" {
+  pub fn new_decimal_arr(ty: RawDataTypePtr, length: i32, data: RawBufferPtr, null_count: i32,
+                         null_bitmap: RawBufferPtr) -> RawArrayPtr;
+  pub fn decimal_arr_raw_data(arr: RawArrayPtr) -> *const u8;
+  pub fn decimal_arr_data(arr: RawArrayPtr) -> RawBufferPtr;
+
+  pub fn new_decimal_arr_builder(pool: RawMemoryPoolMutPtr, ty: RawDataTypePtr) -> *mut RawDecimalArrayBuilder;
+  pub fn append_decimal_arr_builder(builder: *mut RawDecimalArrayBuilder, value: *const u8) -> RawStatusPtr;
+  pub fn finish_decimal_arr_builder(builder: *mut RawDecimalArrayBuilder) -> RawArrayPtr;
+  pub fn release_decimal_arr_builder(builder: *mut RawDecimalArrayBuilder);
+}
\ No newline at end of file
@@ -0,0 +1,325 @@
+use array::*;
+use ty::{DataType, Ty};
+use types::primitive::*;
+use bitmap::Bitmap;
+use types::string::{StringArray, StringArrayBuilder, BinaryArray, BinaryArrayBuilder};
+use common::memory_pool::MemoryPool;
+use common::status::ArrowError;
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+
+// Integer arrays that can hold the keys of a dictionary array
+pub trait KeyArray: Array + Sized {
+  fn key(&self, i: i32) -> usize;
+}
+
+// Integer array builders that can build the keys of a dictionary array
+pub trait KeyBuilder {
+  type Array: KeyArray;
+
+  // The largest key the builder can hold
+  fn max_key() -> usize;
+  fn append_key(&mut self, key: usize, is_valid: bool) -> Result<(), ArrowError>;
+  fn finish_keys(&mut self) -> Self::Array;
+}
+
+macro_rules! impl_key {
+  ($array_name:ident, $builder_name:ident, $ty:ident) => (
+    impl KeyArray for $array_name {
+      fn key(&self, i: i32) -> usize {
+        self.value(i) as usize
+      }
+    }
+
+    impl KeyBuilder for $builder_name {
+      type Array = $array_name;
+
+      fn max_key() -> usize {
+        ::std::$ty::MAX as usize
+      }
+
+      fn append_key(&mut self, key: usize, is_valid: bool) -> Result<(), ArrowError> {
+        if is_valid {
+          if key > Self::max_key() {
+            return Err(ArrowError::invalid("dictionary key exceeds the range of the key type"));
+          }
+          try!(self.append_value(key as $ty));
+        } else {
+          try!(self.append_null());
+        }
+        Ok(())
+      }
+
+      fn finish_keys(&mut self) -> $array_name {
+        self.finish()
+      }
+    }
+  );
+}
+
+impl_key!(U8Array, U8ArrayBuilder, u8);
+impl_key!(I8Array, I8ArrayBuilder, i8);
+impl_key!(U16Array, U16ArrayBuilder, u16);
+impl_key!(I16Array, I16ArrayBuilder, i16);
+impl_key!(U32Array, U32ArrayBuilder, u32);
+impl_key!(I32Array, I32ArrayBuilder, i32);
+impl_key!(U64Array, U64ArrayBuilder, u64);
+impl_key!(I64Array, I64ArrayBuilder, i64);
+
+// A dictionary array stores each slot as an integer key into an array of distinct values
+pub struct DictionaryArray<K: KeyArray> {
+  keys: K,
+  values: BaseArray
+}
+
+impl<K: KeyArray> DictionaryArray<K> {
+  pub fn new(keys: K, values: BaseArray) -> Result<DictionaryArray<K>, ArrowError> {
+    for i in 0..keys.len() {
+      if !keys.is_null(i) && keys.key(i) >= values.len() as usize {
+        return Err(ArrowError::invalid("dictionary key is out of the bounds of the values"));
+      }
+    }
+
+    Ok(DictionaryArray {
+      keys: keys,
+      values: values
+    })
+  }
+
+  pub fn keys(&self) -> &K {
+    &self.keys
+  }
+
+  pub fn values(&self) -> &BaseArray {
+    &self.values
+  }
+
+  // Fails if T does not accept the value type
+  pub fn typed_values<T: Any + Array>(&self) -> Result<T, ArrowError> {
+    typed_array( unsafe { arr_share(self.values.raw_array()) } )
+  }
+
+  pub fn key(&self, i: i32) -> usize {
+    self.keys.key(i)
+  }
+
+  pub fn len(&self) -> i32 {
+    self.keys.len()
+  }
+
+  pub fn is_null(&self, i: i32) -> bool {
+    self.keys.is_null(i)
+  }
+
+  pub fn null_count(&self) -> i32 {
+    self.keys.null_count()
+  }
+
+  pub fn value_type(&self) -> DataType {
+    self.values.data_type()
+  }
+
+  // Materializes the dictionary values of every slot into a plain array
+  pub fn decode(&self, pool: &MemoryPool) -> Result<BaseArray, ArrowError> {
+    macro_rules! decode_primitive {
+      ($array_name:ident, $builder_name:ident, $ty:ident) => ({
+        let values: $array_name = try!(self.typed_values());
+        let mut decoded: Vec<$ty> = Vec::with_capacity(self.len() as usize);
+        let mut validity = Bitmap::new(0);
+        for i in 0..self.len() {
+          if self.is_null(i) {
+            decoded.push(0 as $ty);
+            validity.push(false);
+          } else {
+            decoded.push(values.value(self.key(i) as i32));
+            validity.push(true);
+          }
+        }
+
+        let mut builder = $builder_name::new(pool, &values.data_type());
+        try!(builder.append_with_bitmap(&decoded, &validity));
+        Ok(builder.finish_as_base())
+      });
+    }
+
+    macro_rules! decode_binary {
+      ($array_name:ident, $builder_name:ident) => ({
+        let values: $array_name = try!(self.typed_values());
+        let mut decoded = Vec::with_capacity(self.len() as usize);
+        for i in 0..self.len() {
+          if self.is_null(i) {
+            decoded.push(None);
+          } else {
+            decoded.push(Some(values.value(self.key(i) as i32)));
+          }
+        }
+
+        let mut builder = $builder_name::new(pool, &values.data_type());
+        try!(builder.append_nullable(&decoded));
+        Ok(builder.finish_as_base())
+      });
+    }
+
+    match self.values.ty() {
+      Ty::UINT8 => decode_primitive!(U8Array, U8ArrayBuilder, u8),
+      Ty::INT8 => decode_primitive!(I8Array, I8ArrayBuilder, i8),
+      Ty::UINT16 => decode_primitive!(U16Array, U16ArrayBuilder, u16),
+      Ty::INT16 => decode_primitive!(I16Array, I16ArrayBuilder, i16),
+      Ty::UINT32 => decode_primitive!(U32Array, U32ArrayBuilder, u32),
+      Ty::INT32 => decode_primitive!(I32Array, I32ArrayBuilder, i32),
+      Ty::UINT64 => decode_primitive!(U64Array, U64ArrayBuilder, u64),
+      Ty::INT64 => decode_primitive!(I64Array, I64ArrayBuilder, i64),
+      Ty::FLOAT => decode_primitive!(F32Array, F32ArrayBuilder, f32),
+      Ty::DOUBLE => decode_primitive!(F64Array, F64ArrayBuilder, f64),
+      Ty::STRING => decode_binary!(StringArray, StringArrayBuilder),
+      Ty::BINARY => decode_binary!(BinaryArray, BinaryArrayBuilder),
+      _ => Err(ArrowError::invalid("decoding is not supported for the value type"))
+    }
+  }
+}
+
+// Values that can be deduplicated by a DictionaryBuilder
+pub trait DictionaryValue {
+  // Hashable representation used to find the key of a value
+  type MemoKey: Eq + Hash;
+  type Builder;
+
+  fn memo_key(&self) -> Self::MemoKey;
+  fn new_builder(pool: &MemoryPool) -> Self::Builder;
+  fn append_to(&self, builder: &mut Self::Builder) -> Result<(), ArrowError>;
+  fn finish_builder(builder: &mut Self::Builder) -> BaseArray;
+}
+
+impl DictionaryValue for str {
+  type MemoKey = String;
+  type Builder = StringArrayBuilder;
+
+  fn memo_key(&self) -> String {
+    String::from(self)
+  }
+
+  fn new_builder(pool: &MemoryPool) -> StringArrayBuilder {
+    StringArrayBuilder::new(pool, &DataType::new_string())
+  }
+
+  fn append_to(&self, builder: &mut StringArrayBuilder) -> Result<(), ArrowError> {
+    try!(builder.append_value(self));
+    Ok(())
+  }
+
+  fn finish_builder(builder: &mut StringArrayBuilder) -> BaseArray {
+    builder.finish_as_base()
+  }
+}
+
+impl DictionaryValue for [u8] {
+  type MemoKey = Vec<u8>;
+  type Builder = BinaryArrayBuilder;
+
+  fn memo_key(&self) -> Vec<u8> {
+    self.to_vec()
+  }
+
+  fn new_builder(pool: &MemoryPool) -> BinaryArrayBuilder {
+    BinaryArrayBuilder::new(pool, &DataType::new_binary())
+  }
+
+  fn append_to(&self, builder: &mut BinaryArrayBuilder) -> Result<(), ArrowError> {
+    try!(builder.append_value(self));
+    Ok(())
+  }
+
+  fn finish_builder(builder: &mut BinaryArrayBuilder) -> BaseArray {
+    builder.finish_as_base()
+  }
+}
+
+// to_key converts a value into its memo key
+macro_rules! impl_dictionary_value {
+  ($ty:ident, $memo_key:ty, $builder_name:ident, $ty_enum:expr, |$value:ident| $to_key:expr) => (
+    impl DictionaryValue for $ty {
+      type MemoKey = $memo_key;
+      type Builder = $builder_name;
+
+      fn memo_key(&self) -> $memo_key {
+        let $value = *self;
+        $to_key
+      }
+
+      fn new_builder(pool: &MemoryPool) -> $builder_name {
+        $builder_name::new(pool, &DataType::new_primitive($ty_enum))
+      }
+
+      fn append_to(&self, builder: &mut $builder_name) -> Result<(), ArrowError> {
+        try!(builder.append_value(*self));
+        Ok(())
+      }
+
+      fn finish_builder(builder: &mut $builder_name) -> BaseArray {
+        builder.finish_as_base()
+      }
+    }
+  );
+}
+
+impl_dictionary_value!(u8, u8, U8ArrayBuilder, Ty::UINT8, |v| v as u8);
+impl_dictionary_value!(i8, i8, I8ArrayBuilder, Ty::INT8, |v| v as i8);
+impl_dictionary_value!(u16, u16, U16ArrayBuilder, Ty::UINT16, |v| v as u16);
+impl_dictionary_value!(i16, i16, I16ArrayBuilder, Ty::INT16, |v| v as i16);
+impl_dictionary_value!(u32, u32, U32ArrayBuilder, Ty::UINT32, |v| v as u32);
+impl_dictionary_value!(i32, i32, I32ArrayBuilder, Ty::INT32, |v| v as i32);
+impl_dictionary_value!(u64, u64, U64ArrayBuilder, Ty::UINT64, |v| v as u64);
+impl_dictionary_value!(i64, i64, I64ArrayBuilder, Ty::INT64, |v| v as i64);
+// floating point values are compared by their bit patterns
+impl_dictionary_value!(f32, u32, F32ArrayBuilder, Ty::FLOAT, |v| v.to_bits());
+impl_dictionary_value!(f64, u64, F64ArrayBuilder, Ty::DOUBLE, |v| v.to_bits());
+
+// Builds a dictionary array by assigning a key to each distinct value in order of appearance
+pub struct DictionaryBuilder<K: KeyBuilder, V: ?Sized + DictionaryValue> {
+  keys: K,
+  values: V::Builder,
+  memo: HashMap<V::MemoKey, usize>
+}
+
+impl<K: KeyBuilder, V: ?Sized + DictionaryValue> DictionaryBuilder<K, V> {
+  pub fn new(pool: &MemoryPool, keys: K) -> DictionaryBuilder<K, V> {
+    DictionaryBuilder {
+      keys: keys,
+      values: V::new_builder(pool),
+      memo: HashMap::new()
+    }
+  }
+
+  pub fn append(&mut self, value: &V) -> Result<&mut DictionaryBuilder<K, V>, ArrowError> {
+    let next_key = self.memo.len();
+    let key = match self.memo.entry(value.memo_key()) {
+      Entry::Occupied(entry) => *entry.get(),
+      Entry::Vacant(entry) => {
+        if next_key > K::max_key() {
+          return Err(ArrowError::invalid("the dictionary has more values than the key type can index"));
+        }
+        try!(value.append_to(&mut self.values));
+        *entry.insert(next_key)
+      }
+    };
+    try!(self.keys.append_key(key, true));
+    Ok(self)
+  }
+
+  pub fn append_null(&mut self) -> Result<&mut DictionaryBuilder<K, V>, ArrowError> {
+    try!(self.keys.append_key(0, false));
+    Ok(self)
+  }
+
+  pub fn dictionary_len(&self) -> usize {
+    self.memo.len()
+  }
+
+  pub fn finish(&mut self) -> Result<DictionaryArray<K::Array>, ArrowError> {
+    self.memo.clear();
+    DictionaryArray::new(self.keys.finish_keys(), V::finish_builder(&mut self.values))
+  }
+}
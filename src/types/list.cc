@@ -0,0 +1 @@
+#include "list.h"
\ No newline at end of file
@@ -0,0 +1,94 @@
+#ifndef LIST_H
+#define LIST_H
+
+#include "arrow/builder.h"
+#include "arrow/types/list.h"
+#include "../array.h"
+#include "../buffer.h"
+#include "../ty.h"
+#include "../common/memory_pool.h"
+#include "../common/status.h"
+
+using namespace arrow;
+
+extern "C" {
+
+  // List array
+
+  ArrayBox* new_list_arr(DataTypeBox* type, int32_t length, BufferBox* offsets, ArrayBox* values,
+                         int32_t null_count, BufferBox* null_bitmap) {
+    std::shared_ptr<Buffer> bitmap = null_bitmap ? null_bitmap->sp : nullptr;
+    ArrayBox* arr = new ArrayBox;
+    arr->sp = std::make_shared<ListArray>(type->sp, length, offsets->sp, values->sp, null_count, bitmap);
+    arr->p = arr->sp.get();
+    return arr;
+  }
+
+  const int32_t* list_arr_raw_offsets(ArrayBox* arr) {
+    return reinterpret_cast<ListArray*>(arr->p)->raw_offsets();
+  }
+
+  BufferBox* list_arr_offsets(ArrayBox* arr) {
+    BufferBox* buf = new BufferBox;
+    buf->sp = reinterpret_cast<ListArray*>(arr->p)->offsets();
+    buf->p = buf->sp.get();
+    return buf;
+  }
+
+  int32_t list_arr_value_offset(ArrayBox* arr, int i) {
+    return reinterpret_cast<ListArray*>(arr->p)->offset(i);
+  }
+
+  int32_t list_arr_value_length(ArrayBox* arr, int i) {
+    return reinterpret_cast<ListArray*>(arr->p)->value_length(i);
+  }
+
+  ArrayBox* list_arr_values(ArrayBox* arr) {
+    ArrayBox* values = new ArrayBox;
+    values->sp = reinterpret_cast<ListArray*>(arr->p)->values();
+    values->p = values->sp.get();
+    return values;
+  }
+
+  DataTypeBox* list_arr_value_type(ArrayBox* arr) {
+    DataTypeBox* type = new DataTypeBox;
+    type->sp = reinterpret_cast<ListArray*>(arr->p)->value_type();
+    type->p = type->sp.get();
+    return type;
+  }
+
+  // List array builder
+
+  // The value builder is owned by the caller, so it must outlive the list builder
+  ListBuilder* new_list_arr_builder(MemoryPool* pool, ArrayBuilder* value_builder, DataTypeBox* type) {
+    std::shared_ptr<ArrayBuilder> sp(value_builder, [](ArrayBuilder*) {});
+    return new ListBuilder(pool, sp, type->sp);
+  }
+
+  StatusBox* append_list_arr_builder(ListBuilder* builder, bool is_valid) {
+    StatusBox* box = new StatusBox;
+    box->status = builder->Append(is_valid);
+    return box;
+  }
+
+  StatusBox* append_null_list_arr_builder(ListBuilder* builder) {
+    StatusBox* box = new StatusBox;
+    box->status = builder->AppendNull();
+    return box;
+  }
+
+  ArrayBox* finish_list_arr_builder(ListBuilder* builder) {
+    ArrayBox* arr = new ArrayBox;
+    arr->sp = builder->Finish();
+    arr->p = arr->sp.get();
+    return arr;
+  }
+
+  void release_list_arr_builder(ListBuilder* builder) {
+    if (builder) {
+      delete builder;
+    }
+  }
+}
+
+#endif
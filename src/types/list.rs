@@ -0,0 +1,168 @@
+use array::*;
+use array_builder::{ArrayBuilder, RawArrayBuilderMutPtr};
+use buffer::{RawBufferPtr, Buf};
+use ty::{RawDataTypePtr, DataType, Ty};
+use ty;
+use common::memory_pool::{RawMemoryPoolMutPtr, MemoryPool, PoolRef};
+use common::status::{RawStatusPtr, ArrowError};
+
+use std::any::Any;
+use std::mem;
+use std::ptr;
+use std::slice;
+
+#[macro_use]
+use common::status;
+
+pub struct ListArray {
+  raw_array: RawArrayPtr
+}
+
+impl ListArray {
+  // Fails unless the data type is a list of the values, the offsets buffer holds length + 1
+  // increasing entries within the values, and the null bitmap holds length bits
+  pub fn from_buffers(data_type: &DataType, length: i32, offsets: Buf, values: &BaseArray, null_count: i32,
+                      null_bitmap: Option<Buf>) -> Result<ListArray, ArrowError> {
+    match data_type.ty() {
+      Ty::LIST if data_type.child(0).data_type() == values.data_type() => {},
+      _ => return Err(ArrowError::type_error(format!("{} is not a list of {}", data_type.to_string(),
+                                                     values.data_type().to_string())))
+    }
+    try!(check_validity(length, null_count, &null_bitmap));
+    try!(check_offsets(&try!(read_offsets(&offsets, length)), values.len() as usize));
+
+    let raw_null_bitmap = match null_bitmap {
+      Some(ref bitmap) => bitmap.raw_buf(),
+      None => ptr::null()
+    };
+    Ok(ListArray {
+      raw_array: unsafe {
+        new_list_arr(data_type.raw_data_type(), length, offsets.raw_buf(), values.raw_array(), null_count, raw_null_bitmap)
+      }
+    })
+  }
+
+  // The offsets buffer holds len() + 1 entries
+  pub fn value_offsets(&self) -> &[i32] {
+    unsafe { slice::from_raw_parts(list_arr_raw_offsets(self.raw_array), self.len() as usize + 1) }
+  }
+
+  pub fn offsets(&self) -> Buf {
+    Buf::from_raw( unsafe { list_arr_offsets(self.raw_array) } )
+  }
+
+  pub fn value_offset(&self, i: i32) -> i32 {
+    unsafe { list_arr_value_offset(self.raw_array, i) }
+  }
+
+  pub fn value_length(&self, i: i32) -> i32 {
+    unsafe { list_arr_value_length(self.raw_array, i) }
+  }
+
+  pub fn value_type(&self) -> DataType {
+    DataType::new(unsafe { list_arr_value_type(self.raw_array) })
+  }
+
+  pub fn values(&self) -> BaseArray {
+    BaseArray::from_raw( unsafe { list_arr_values(self.raw_array) } )
+  }
+
+  // Fails if T does not accept the value type
+  pub fn typed_values<T: Any + Array>(&self) -> Result<T, ArrowError> {
+    typed_array( unsafe { list_arr_values(self.raw_array) } )
+  }
+}
+
+impl_array!(ListArray, ty::new_list_type(ty::new_primitive_type(Ty::NA)), list_arr_offsets, Ty::LIST);
+
+pub enum RawListArrayBuilder {}
+
+pub struct ListArrayBuilder<B: ArrayBuilder> {
+  raw_builder: *mut RawListArrayBuilder,
+  value_builder: B,
+  _pool: PoolRef
+}
+
+impl<B: ArrayBuilder> ListArrayBuilder<B> {
+  pub fn new(pool: &MemoryPool, data_type: &DataType, value_builder: B) -> ListArrayBuilder<B> {
+    let pool = PoolRef::new(pool);
+    ListArrayBuilder {
+      raw_builder: unsafe { new_list_arr_builder(pool.raw_memory_pool(), value_builder.raw_builder(), data_type.raw_data_type()) },
+      value_builder: value_builder,
+      _pool: pool
+    }
+  }
+
+  // Values appended to the value builder belong to the list slot most recently started by append()
+  pub fn value_builder(&mut self) -> &mut B {
+    &mut self.value_builder
+  }
+
+  pub fn append(&mut self, is_valid: bool) -> Result<&mut ListArrayBuilder<B>, ArrowError> {
+    unsafe {
+      let s = append_list_arr_builder(self.raw_builder, is_valid);
+      result_from_status!(s, self)
+    }
+  }
+
+  pub fn append_null(&mut self) -> Result<&mut ListArrayBuilder<B>, ArrowError> {
+    unsafe {
+      let s = append_null_list_arr_builder(self.raw_builder);
+      result_from_status!(s, self)
+    }
+  }
+
+  pub fn finish(&mut self) -> ListArray {
+    ListArray {
+      raw_array: unsafe { finish_list_arr_builder(self.raw_builder) }
+    }
+  }
+
+  pub fn finish_as_base(&mut self) -> BaseArray {
+    let arr = self.finish();
+    unsafe { mem::transmute(arr) }
+  }
+}
+
+impl<B: ArrayBuilder + 'static> ArrayBuilder for ListArrayBuilder<B> {
+  fn raw_builder(&self) -> RawArrayBuilderMutPtr {
+    self.raw_builder as RawArrayBuilderMutPtr
+  }
+
+  fn append_null(&mut self) -> Result<(), ArrowError> {
+    try!(ListArrayBuilder::append_null(self));
+    Ok(())
+  }
+
+  fn as_any(&self) -> &Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut Any {
+    self
+  }
+}
+
+impl<B: ArrayBuilder> Drop for ListArrayBuilder<B> {
+  fn drop(&mut self) {
+    // the value builder is released after this, when the field is dropped
+    unsafe { release_list_arr_builder(self.raw_builder); }
+  }
+}
+
+extern "C" {
+  pub fn new_list_arr(ty: RawDataTypePtr, length: i32, offsets: RawBufferPtr, values: RawArrayPtr, null_count: i32,
+                      null_bitmap: RawBufferPtr) -> RawArrayPtr;
+  pub fn list_arr_raw_offsets(arr: RawArrayPtr) -> *const i32;
+  pub fn list_arr_offsets(arr: RawArrayPtr) -> RawBufferPtr;
+  pub fn list_arr_value_offset(arr: RawArrayPtr, i: i32) -> i32;
+  pub fn list_arr_value_length(arr: RawArrayPtr, i: i32) -> i32;
+  pub fn list_arr_values(arr: RawArrayPtr) -> RawArrayPtr;
+  pub fn list_arr_value_type(arr: RawArrayPtr) -> RawDataTypePtr;
+
+  pub fn new_list_arr_builder(pool: RawMemoryPoolMutPtr, value_builder: RawArrayBuilderMutPtr, ty: RawDataTypePtr) -> *mut RawListArrayBuilder;
+  pub fn append_list_arr_builder(builder: *mut RawListArrayBuilder, is_valid: bool) -> RawStatusPtr;
+  pub fn append_null_list_arr_builder(builder: *mut RawListArrayBuilder) -> RawStatusPtr;
+  pub fn finish_list_arr_builder(builder: *mut RawListArrayBuilder) -> RawArrayPtr;
+  pub fn release_list_arr_builder(builder: *mut RawListArrayBuilder);
+}
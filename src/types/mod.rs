@@ -1 +1,9 @@
-pub mod primitive;
\ No newline at end of file
+#[macro_use]
+pub mod primitive;
+pub mod string;
+pub mod list;
+pub mod structs;
+pub mod union;
+pub mod dictionary;
+pub mod temporal;
+pub mod decimal;
\ No newline at end of file
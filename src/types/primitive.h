@@ -10,6 +10,16 @@
 
 using namespace arrow;
 
+#define NEW_ARRAY_DECL(NAME, RS_TYPE)                                                        \
+  ArrayBox* new_##RS_TYPE##_arr(DataTypeBox* type, int32_t length, BufferBox* data,          \
+                                int32_t null_count, BufferBox* null_bitmap) {                \
+    std::shared_ptr<Buffer> bitmap = null_bitmap ? null_bitmap->sp : nullptr;                \
+    ArrayBox* arr = new ArrayBox;                                                            \
+    arr->sp = std::make_shared<NAME>(type->sp, length, data->sp, null_count, bitmap);        \
+    arr->p = arr->sp.get();                                                                  \
+    return arr;                                                                              \
+  }
+
 #define RAW_DATA_FUNC_DECL(NAME, TYPE, RS_TYPE)             \
   const TYPE* RS_TYPE##_arr_raw_data(ArrayBox* arr) {       \
     return ((NAME*)arr->p)->raw_data();                     \
@@ -65,6 +75,18 @@ extern "C" {
     return buf;
   }
 
+  NEW_ARRAY_DECL(UInt8Array, u8);
+  NEW_ARRAY_DECL(Int8Array, i8);
+  NEW_ARRAY_DECL(UInt16Array, u16);
+  NEW_ARRAY_DECL(Int16Array, i16);
+  NEW_ARRAY_DECL(UInt32Array, u32);
+  NEW_ARRAY_DECL(Int32Array, i32);
+  NEW_ARRAY_DECL(UInt64Array, u64);
+  NEW_ARRAY_DECL(Int64Array, i64);
+  NEW_ARRAY_DECL(FloatArray, f32);
+  NEW_ARRAY_DECL(DoubleArray, f64);
+  NEW_ARRAY_DECL(BooleanArray, bool);
+
   RAW_DATA_FUNC_DECL(UInt8Array, uint8_t, u8);
   RAW_DATA_FUNC_DECL(Int8Array, int8_t, i8);
   RAW_DATA_FUNC_DECL(UInt16Array, uint16_t, u16);
@@ -75,6 +97,7 @@ extern "C" {
   RAW_DATA_FUNC_DECL(Int64Array, int64_t, i64);
   RAW_DATA_FUNC_DECL(FloatArray, float, f32);
   RAW_DATA_FUNC_DECL(DoubleArray, double, f64);
+  RAW_DATA_FUNC_DECL(BooleanArray, uint8_t, bool);
 
   VALUE_FUNC_DECL(UInt8Array, uint8_t, u8);
   VALUE_FUNC_DECL(Int8Array, int8_t, i8);
@@ -86,6 +109,7 @@ extern "C" {
   VALUE_FUNC_DECL(Int64Array, int64_t, i64);
   VALUE_FUNC_DECL(FloatArray, float, f32);
   VALUE_FUNC_DECL(DoubleArray, double, f64);
+  VALUE_FUNC_DECL(BooleanArray, bool, bool);
 
   // Primitive array builder
 
@@ -99,6 +123,7 @@ extern "C" {
   NEW_ARRAY_BUILDER_DECL(Int64Builder, i64);
   NEW_ARRAY_BUILDER_DECL(FloatBuilder, f32);
   NEW_ARRAY_BUILDER_DECL(DoubleBuilder, f64);
+  NEW_ARRAY_BUILDER_DECL(BooleanBuilder, bool);
 
   INIT_ARRAY_BUILDER_DECL(UInt8Builder, u8);
   INIT_ARRAY_BUILDER_DECL(Int8Builder, i8);
@@ -110,6 +135,7 @@ extern "C" {
   INIT_ARRAY_BUILDER_DECL(Int64Builder, i64);
   INIT_ARRAY_BUILDER_DECL(FloatBuilder, f32);
   INIT_ARRAY_BUILDER_DECL(DoubleBuilder, f64);
+  INIT_ARRAY_BUILDER_DECL(BooleanBuilder, bool);
 
   // TODO: add reserve
 
@@ -123,6 +149,7 @@ extern "C" {
   APPEND_ARRAY_BUILDER_DECL(Int64Builder, int64_t, i64);
   APPEND_ARRAY_BUILDER_DECL(FloatBuilder, float, f32);
   APPEND_ARRAY_BUILDER_DECL(DoubleBuilder, double, f64);
+  APPEND_ARRAY_BUILDER_DECL(BooleanBuilder, uint8_t, bool);
 
   FINISH_ARRAY_BUILDER_DECL(UInt8Builder, u8);
   FINISH_ARRAY_BUILDER_DECL(Int8Builder, i8);
@@ -134,6 +161,7 @@ extern "C" {
   FINISH_ARRAY_BUILDER_DECL(Int64Builder, i64);
   FINISH_ARRAY_BUILDER_DECL(FloatBuilder, f32);
   FINISH_ARRAY_BUILDER_DECL(DoubleBuilder, f64);
+  FINISH_ARRAY_BUILDER_DECL(BooleanBuilder, bool);
 
   RELEASE_ARRAY_BUILDER_DECL(UInt8Builder, u8);
   RELEASE_ARRAY_BUILDER_DECL(Int8Builder, i8);
@@ -145,6 +173,7 @@ extern "C" {
   RELEASE_ARRAY_BUILDER_DECL(Int64Builder, i64);
   RELEASE_ARRAY_BUILDER_DECL(FloatBuilder, f32);
   RELEASE_ARRAY_BUILDER_DECL(DoubleBuilder, f64);
+  RELEASE_ARRAY_BUILDER_DECL(BooleanBuilder, bool);
 }
 
 #endif
\ No newline at end of file
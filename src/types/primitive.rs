@@ -1,11 +1,15 @@
 use array::*;
+use array_builder::{ArrayBuilder, RawArrayBuilderMutPtr};
 use buffer::{RawBufferPtr, Buf, Buffer};
+use bitmap::Bitmap;
 use ty::{RawDataTypePtr, DataType, Ty};
 use ty;
-use common::memory_pool::{RawMemoryPoolMutPtr, MemoryPool};
+use common::memory_pool::{RawMemoryPoolMutPtr, MemoryPool, PoolRef};
 use common::status::{RawStatusPtr, ArrowError};
 
+use std::any::Any;
 use std::mem;
+use std::ptr;
 use std::slice;
 
 #[macro_use]
@@ -19,6 +23,16 @@ pub trait PrimitiveArray<T, Ty=Self> : Array<Ty> {
   fn value(&self, i: i32) -> T;
 }
 
+// Converts the validity of slots into a null bitmap, which is omitted if every slot is valid
+fn null_bitmap(validity: Bitmap) -> (Option<Buf>, i32) {
+  let null_count = validity.count_unset_bits() as i32;
+  if null_count == 0 {
+    (None, 0)
+  } else {
+    (Some(validity.into_buf()), null_count)
+  }
+}
+
 macro_rules! define_raw_builder {
   ($name:ident) => (pub enum $name {});
 }
@@ -33,27 +47,10 @@ define_raw_builder!(RawU64ArrayBuilder);
 define_raw_builder!(RawI64ArrayBuilder);
 define_raw_builder!(RawF32ArrayBuilder);
 define_raw_builder!(RawF64ArrayBuilder);
+define_raw_builder!(RawBooleanArrayBuilder);
 
-macro_rules! define_array {
-  ($name:ident, $ty:ident) => (
-    pub struct $name {
-      raw_array: RawArrayPtr
-    }
-
-    impl PrimitiveArray<$ty> for $name {
-      fn raw_data(&self) -> *const $ty {
-          unsafe { concat_idents!($ty, _arr_raw_data) (self.raw_array) }
-      }
-
-      fn as_slice(&self) -> &[$ty] {
-        unsafe { slice::from_raw_parts(self.raw_data(), self.len() as usize) }
-      }
-
-      fn value(&self, i: i32) -> $ty {
-        unsafe { concat_idents!($ty, _arr_value) (self.raw_array, i) }
-      }
-    }
-
+macro_rules! impl_array {
+  ($name:ident, $raw_type:expr, $data_fn:ident, $($accepted_ty:expr),+) => (
     impl PartialEq for $name {
       fn eq(&self, other: &$name) -> bool {
         unsafe { arr_equals_exact(self.raw_array, other.raw_array) }
@@ -74,7 +71,7 @@ macro_rules! define_array {
       }
 
       fn is_null(&self, i: i32) -> bool {
-        unsafe { arr_is_null(self.raw_array, i) }
+        unsafe { is_null(self.raw_array, i) }
       }
 
       fn len(&self) -> i32 {
@@ -99,7 +96,7 @@ macro_rules! define_array {
 
       fn new_null_array(length: i32) -> $name {
         $name {
-          raw_array: unsafe { new_null_arr(ty::new_primitive_type(Ty::UINT8), length) }
+          raw_array: unsafe { new_null_arr($raw_type, length) }
         }
       }
 
@@ -108,33 +105,159 @@ macro_rules! define_array {
       }
 
       fn data(&self) -> Buf {
-        Buf::from_raw( unsafe { arr_data(self.raw_array) } )
+        Buf::from_raw( unsafe { $data_fn(self.raw_array) } )
+      }
+
+      fn accepts(ty: Ty) -> bool {
+        let ty = ty as i32;
+        false $(|| ty == $accepted_ty as i32)+
       }
     }
   );
 }
 
-define_array!(U8Array, u8);
-define_array!(I8Array, i8);
-define_array!(U16Array, u16);
-define_array!(I16Array, i16);
-define_array!(U32Array, u32);
-define_array!(I32Array, i32);
-define_array!(U64Array, u64);
-define_array!(I64Array, i64);
-define_array!(F32Array, f32);
-define_array!(F64Array, f64);
+macro_rules! define_array {
+  ($name:ident, $ty:ident, $ty_enum:expr) => (
+    pub struct $name {
+      raw_array: RawArrayPtr
+    }
+
+    impl PrimitiveArray<$ty> for $name {
+      fn raw_data(&self) -> *const $ty {
+          unsafe { concat_idents!($ty, _arr_raw_data) (self.raw_array) }
+      }
+
+      fn as_slice(&self) -> &[$ty] {
+        unsafe { slice::from_raw_parts(self.raw_data(), self.len() as usize) }
+      }
+
+      fn value(&self, i: i32) -> $ty {
+        unsafe { concat_idents!($ty, _arr_value) (self.raw_array, i) }
+      }
+    }
+
+    impl $name {
+      // Fails unless the data buffer holds length values and the null bitmap length bits
+      pub fn from_buffers(length: i32, data: Buf, null_count: i32, null_bitmap: Option<Buf>) -> Result<$name, ArrowError> {
+        try!(check_validity(length, null_count, &null_bitmap));
+        if (data.size() as usize) < length as usize * mem::size_of::<$ty>() {
+          return Err(ArrowError::invalid("data buffer is too short"));
+        }
+        Ok(unsafe { $name::from_buffers_unchecked(length, data, null_count, null_bitmap) })
+      }
+
+      unsafe fn from_buffers_unchecked(length: i32, data: Buf, null_count: i32, null_bitmap: Option<Buf>) -> $name {
+        let data_type = DataType::new_primitive($ty_enum);
+        let raw_null_bitmap = match null_bitmap {
+          Some(ref bitmap) => bitmap.raw_buf(),
+          None => ptr::null()
+        };
+        $name {
+          raw_array: concat_idents!(new_, $ty, _arr) (data_type.raw_data_type(), length, data.raw_buf(), null_count, raw_null_bitmap)
+        }
+      }
+    }
+
+    // Takes over the allocation of the vector, see Buf::from
+    impl From<Vec<$ty>> for $name {
+      fn from(values: Vec<$ty>) -> $name {
+        let length = values.len() as i32;
+        unsafe { $name::from_buffers_unchecked(length, Buf::from(values), 0, None) }
+      }
+    }
+
+    // The values are unwrapped into a new vector, whose allocation is then taken over
+    impl From<Vec<Option<$ty>>> for $name {
+      fn from(values: Vec<Option<$ty>>) -> $name {
+        let (null_bitmap, null_count) = null_bitmap(Bitmap::from_options(&values));
+        let values = values.into_iter().map(|v| v.unwrap_or(0 as $ty)).collect::<Vec<$ty>>();
+        unsafe { $name::from_buffers_unchecked(values.len() as i32, Buf::from(values), null_count, null_bitmap) }
+      }
+    }
+
+    impl_array!($name, ty::new_primitive_type($ty_enum), arr_data, $ty_enum);
+  );
+}
+
+define_array!(U8Array, u8, Ty::UINT8);
+define_array!(I8Array, i8, Ty::INT8);
+define_array!(U16Array, u16, Ty::UINT16);
+define_array!(I16Array, i16, Ty::INT16);
+define_array!(U32Array, u32, Ty::UINT32);
+define_array!(I32Array, i32, Ty::INT32);
+define_array!(U64Array, u64, Ty::UINT64);
+define_array!(I64Array, i64, Ty::INT64);
+define_array!(F32Array, f32, Ty::FLOAT);
+define_array!(F64Array, f64, Ty::DOUBLE);
+
+// Values of a boolean array are bit-packed, so they cannot be exposed as a slice of bool
+pub struct BooleanArray {
+  raw_array: RawArrayPtr
+}
+
+impl BooleanArray {
+  pub fn raw_data(&self) -> *const u8 {
+    unsafe { bool_arr_raw_data(self.raw_array) }
+  }
+
+  pub fn value(&self, i: i32) -> bool {
+    unsafe { bool_arr_value(self.raw_array, i) }
+  }
+}
+
+impl BooleanArray {
+  // Fails unless the data buffer and the null bitmap hold length bits
+  pub fn from_buffers(length: i32, data: Buf, null_count: i32, null_bitmap: Option<Buf>) -> Result<BooleanArray, ArrowError> {
+    try!(check_validity(length, null_count, &null_bitmap));
+    if (data.size() as usize) < (length as usize + 7) / 8 {
+      return Err(ArrowError::invalid("data buffer is too short"));
+    }
+    Ok(unsafe { BooleanArray::from_buffers_unchecked(length, data, null_count, null_bitmap) })
+  }
+
+  unsafe fn from_buffers_unchecked(length: i32, data: Buf, null_count: i32, null_bitmap: Option<Buf>) -> BooleanArray {
+    let data_type = DataType::new_primitive(Ty::BOOL);
+    let raw_null_bitmap = match null_bitmap {
+      Some(ref bitmap) => bitmap.raw_buf(),
+      None => ptr::null()
+    };
+    BooleanArray {
+      raw_array: new_bool_arr(data_type.raw_data_type(), length, data.raw_buf(), null_count, raw_null_bitmap)
+    }
+  }
+}
+
+// Booleans are bit-packed, so the values are always copied
+impl From<Vec<bool>> for BooleanArray {
+  fn from(values: Vec<bool>) -> BooleanArray {
+    let data = Bitmap::from_bools(&values);
+    unsafe { BooleanArray::from_buffers_unchecked(values.len() as i32, data.into_buf(), 0, None) }
+  }
+}
+
+impl From<Vec<Option<bool>>> for BooleanArray {
+  fn from(values: Vec<Option<bool>>) -> BooleanArray {
+    let data = Bitmap::from_bools(&values.iter().map(|v| v.unwrap_or(false)).collect::<Vec<bool>>());
+    let (null_bitmap, null_count) = null_bitmap(Bitmap::from_options(&values));
+    unsafe { BooleanArray::from_buffers_unchecked(values.len() as i32, data.into_buf(), null_count, null_bitmap) }
+  }
+}
+
+impl_array!(BooleanArray, ty::new_primitive_type(Ty::BOOL), arr_data, Ty::BOOL);
 
 macro_rules! define_array_builder {
   ($builder_name:ident, $array_name:ident, $ty:ident) => (
     pub struct $builder_name {
-      raw_builder: *mut concat_idents!(Raw, $builder_name)
+      raw_builder: *mut concat_idents!(Raw, $builder_name),
+      _pool: PoolRef
     }
 
     impl $builder_name {
       pub fn new(pool: &MemoryPool, data_type: &DataType) -> $builder_name {
+        let pool = PoolRef::new(pool);
         $builder_name {
-          raw_builder: unsafe { concat_idents!(new_, $ty, _arr_builder) (pool.raw_memory_pool(), data_type.raw_data_type()) }
+          raw_builder: unsafe { concat_idents!(new_, $ty, _arr_builder) (pool.raw_memory_pool(), data_type.raw_data_type()) },
+          _pool: pool
         }
       }
 
@@ -152,6 +275,51 @@ macro_rules! define_array_builder {
         }
       }
 
+      pub fn append_value(&mut self, value: $ty) -> Result<&mut $builder_name, ArrowError> {
+        self.append(&[value], ptr::null())
+      }
+
+      pub fn append_null(&mut self) -> Result<&mut $builder_name, ArrowError> {
+        let valid: u8 = 0;
+        self.append(&[Default::default()], &valid)
+      }
+
+      pub fn append_option(&mut self, value: Option<$ty>) -> Result<&mut $builder_name, ArrowError> {
+        match value {
+          Some(v) => self.append_value(v),
+          None => self.append_null()
+        }
+      }
+
+      // validity holds one flag per value, and false marks a null slot
+      pub fn append_slice_with_validity(&mut self, values: &[$ty], validity: &[bool]) -> Result<&mut $builder_name, ArrowError> {
+        if values.len() != validity.len() {
+          return Err(ArrowError::invalid("the validity must have one flag per value"));
+        }
+        let valid_bytes = validity.iter().map(|v| *v as u8).collect::<Vec<u8>>();
+        self.append(values, valid_bytes.as_ptr())
+      }
+
+      // Appends every value in a single call to the underlying builder
+      pub fn extend<I: IntoIterator<Item=Option<$ty>>>(&mut self, values: I) -> Result<&mut $builder_name, ArrowError> {
+        let (values, valid_bytes): (Vec<$ty>, Vec<u8>) = values.into_iter()
+          .map(|v| match v {
+            Some(v) => (v, 1),
+            None => (Default::default(), 0)
+          })
+          .unzip();
+        self.append(&values, valid_bytes.as_ptr())
+      }
+
+      // validity holds one bit per value, and unset bits mark null slots
+      pub fn append_with_bitmap(&mut self, values: &[$ty], validity: &Bitmap) -> Result<&mut $builder_name, ArrowError> {
+        if values.len() != validity.len() {
+          return Err(ArrowError::invalid("the validity bitmap must have one bit per value"));
+        }
+        let valid_bytes = validity.to_valid_bytes();
+        self.append(values, valid_bytes.as_ptr())
+      }
+
       pub fn finish(&mut self) -> $array_name {
         $array_name {
           raw_array: unsafe { concat_idents!(finish_, $ty, _arr_builder) (self.raw_builder) }
@@ -164,6 +332,25 @@ macro_rules! define_array_builder {
       }
     }
 
+    impl ArrayBuilder for $builder_name {
+      fn raw_builder(&self) -> RawArrayBuilderMutPtr {
+        self.raw_builder as RawArrayBuilderMutPtr
+      }
+
+      fn append_null(&mut self) -> Result<(), ArrowError> {
+        try!($builder_name::append_null(self));
+        Ok(())
+      }
+
+      fn as_any(&self) -> &Any {
+        self
+      }
+
+      fn as_any_mut(&mut self) -> &mut Any {
+        self
+      }
+    }
+
     impl Drop for $builder_name {
       fn drop(&mut self) {
         unsafe { concat_idents!(release_, $ty, _arr_builder) (self.raw_builder); }
@@ -182,10 +369,29 @@ define_array_builder!(U64ArrayBuilder, U64Array, u64);
 define_array_builder!(I64ArrayBuilder, I64Array, i64);
 define_array_builder!(F32ArrayBuilder, F32Array, f32);
 define_array_builder!(F64ArrayBuilder, F64Array, f64);
+define_array_builder!(BooleanArrayBuilder, BooleanArray, bool);
+
+impl BooleanArrayBuilder {
+  pub fn append_nullable(&mut self, values: &[Option<bool>]) -> Result<&mut BooleanArrayBuilder, ArrowError> {
+    self.extend(values.iter().cloned())
+  }
+}
 
 extern "C" {
   pub fn arr_data(arr: RawArrayPtr) -> RawBufferPtr;
 
+  pub fn new_u8_arr(ty: RawDataTypePtr, length: i32, data: RawBufferPtr, null_count: i32, null_bitmap: RawBufferPtr) -> RawArrayPtr;
+  pub fn new_i8_arr(ty: RawDataTypePtr, length: i32, data: RawBufferPtr, null_count: i32, null_bitmap: RawBufferPtr) -> RawArrayPtr;
+  pub fn new_u16_arr(ty: RawDataTypePtr, length: i32, data: RawBufferPtr, null_count: i32, null_bitmap: RawBufferPtr) -> RawArrayPtr;
+  pub fn new_i16_arr(ty: RawDataTypePtr, length: i32, data: RawBufferPtr, null_count: i32, null_bitmap: RawBufferPtr) -> RawArrayPtr;
+  pub fn new_u32_arr(ty: RawDataTypePtr, length: i32, data: RawBufferPtr, null_count: i32, null_bitmap: RawBufferPtr) -> RawArrayPtr;
+  pub fn new_i32_arr(ty: RawDataTypePtr, length: i32, data: RawBufferPtr, null_count: i32, null_bitmap: RawBufferPtr) -> RawArrayPtr;
+  pub fn new_u64_arr(ty: RawDataTypePtr, length: i32, data: RawBufferPtr, null_count: i32, null_bitmap: RawBufferPtr) -> RawArrayPtr;
+  pub fn new_i64_arr(ty: RawDataTypePtr, length: i32, data: RawBufferPtr, null_count: i32, null_bitmap: RawBufferPtr) -> RawArrayPtr;
+  pub fn new_f32_arr(ty: RawDataTypePtr, length: i32, data: RawBufferPtr, null_count: i32, null_bitmap: RawBufferPtr) -> RawArrayPtr;
+  pub fn new_f64_arr(ty: RawDataTypePtr, length: i32, data: RawBufferPtr, null_count: i32, null_bitmap: RawBufferPtr) -> RawArrayPtr;
+  pub fn new_bool_arr(ty: RawDataTypePtr, length: i32, data: RawBufferPtr, null_count: i32, null_bitmap: RawBufferPtr) -> RawArrayPtr;
+
   pub fn u8_arr_raw_data(arr: RawArrayPtr) -> *const u8;
   pub fn i8_arr_raw_data(arr: RawArrayPtr) -> *const i8;
   pub fn u16_arr_raw_data(arr: RawArrayPtr) -> *const u16;
@@ -196,6 +402,7 @@ extern "C" {
   pub fn i64_arr_raw_data(arr: RawArrayPtr) -> *const i64;
   pub fn f32_arr_raw_data(arr: RawArrayPtr) -> *const f32;
   pub fn f64_arr_raw_data(arr: RawArrayPtr) -> *const f64;
+  pub fn bool_arr_raw_data(arr: RawArrayPtr) -> *const u8;
 
   pub fn u8_arr_value(arr: RawArrayPtr, i: i32) -> u8;
   pub fn i8_arr_value(arr: RawArrayPtr, i: i32) -> i8;
@@ -207,6 +414,7 @@ extern "C" {
   pub fn i64_arr_value(arr: RawArrayPtr, i: i32) -> i64;
   pub fn f32_arr_value(arr: RawArrayPtr, i: i32) -> f32;
   pub fn f64_arr_value(arr: RawArrayPtr, i: i32) -> f64;
+  pub fn bool_arr_value(arr: RawArrayPtr, i: i32) -> bool;
 
   pub fn new_u8_arr_builder(pool: RawMemoryPoolMutPtr, ty: RawDataTypePtr) -> *mut RawU8ArrayBuilder;
   pub fn new_i8_arr_builder(pool: RawMemoryPoolMutPtr, ty: RawDataTypePtr) -> *mut RawI8ArrayBuilder;
@@ -218,6 +426,7 @@ extern "C" {
   pub fn new_i64_arr_builder(pool: RawMemoryPoolMutPtr, ty: RawDataTypePtr) -> *mut RawI64ArrayBuilder;
   pub fn new_f32_arr_builder(pool: RawMemoryPoolMutPtr, ty: RawDataTypePtr) -> *mut RawF32ArrayBuilder;
   pub fn new_f64_arr_builder(pool: RawMemoryPoolMutPtr, ty: RawDataTypePtr) -> *mut RawF64ArrayBuilder;
+  pub fn new_bool_arr_builder(pool: RawMemoryPoolMutPtr, ty: RawDataTypePtr) -> *mut RawBooleanArrayBuilder;
 
   pub fn init_u8_arr_builder(builder: *mut RawU8ArrayBuilder, capa: i32) -> RawStatusPtr;
   pub fn init_i8_arr_builder(builder: *mut RawI8ArrayBuilder, capa: i32) -> RawStatusPtr;
@@ -229,6 +438,7 @@ extern "C" {
   pub fn init_i64_arr_builder(builder: *mut RawI64ArrayBuilder, capa: i32) -> RawStatusPtr;
   pub fn init_f32_arr_builder(builder: *mut RawF32ArrayBuilder, capa: i32) -> RawStatusPtr;
   pub fn init_f64_arr_builder(builder: *mut RawF64ArrayBuilder, capa: i32) -> RawStatusPtr;
+  pub fn init_bool_arr_builder(builder: *mut RawBooleanArrayBuilder, capa: i32) -> RawStatusPtr;
 
   pub fn append_u8_arr_builder(builder: *mut RawU8ArrayBuilder, values: *const u8, len: i32, valid_bytes: *const u8) -> RawStatusPtr;
   pub fn append_i8_arr_builder(builder: *mut RawI8ArrayBuilder, values: *const i8, len: i32, valid_bytes: *const u8) -> RawStatusPtr;
@@ -240,6 +450,7 @@ extern "C" {
   pub fn append_i64_arr_builder(builder: *mut RawI64ArrayBuilder, values: *const i64, len: i32, valid_bytes: *const u8) -> RawStatusPtr;
   pub fn append_f32_arr_builder(builder: *mut RawF32ArrayBuilder, values: *const f32, len: i32, valid_bytes: *const u8) -> RawStatusPtr;
   pub fn append_f64_arr_builder(builder: *mut RawF64ArrayBuilder, values: *const f64, len: i32, valid_bytes: *const u8) -> RawStatusPtr;
+  pub fn append_bool_arr_builder(builder: *mut RawBooleanArrayBuilder, values: *const bool, len: i32, valid_bytes: *const u8) -> RawStatusPtr;
 
   pub fn finish_u8_arr_builder(builder: *mut RawU8ArrayBuilder) -> RawArrayPtr;
   pub fn finish_i8_arr_builder(builder: *mut RawI8ArrayBuilder) -> RawArrayPtr;
@@ -251,6 +462,7 @@ extern "C" {
   pub fn finish_i64_arr_builder(builder: *mut RawI64ArrayBuilder) -> RawArrayPtr;
   pub fn finish_f32_arr_builder(builder: *mut RawF32ArrayBuilder) -> RawArrayPtr;
   pub fn finish_f64_arr_builder(builder: *mut RawF64ArrayBuilder) -> RawArrayPtr;
+  pub fn finish_bool_arr_builder(builder: *mut RawBooleanArrayBuilder) -> RawArrayPtr;
 
   pub fn release_u8_arr_builder(builder: *mut RawU8ArrayBuilder);
   pub fn release_i8_arr_builder(builder: *mut RawI8ArrayBuilder);
@@ -262,4 +474,5 @@ extern "C" {
   pub fn release_i64_arr_builder(builder: *mut RawI64ArrayBuilder);
   pub fn release_f32_arr_builder(builder: *mut RawF32ArrayBuilder);
   pub fn release_f64_arr_builder(builder: *mut RawF64ArrayBuilder);
+  pub fn release_bool_arr_builder(builder: *mut RawBooleanArrayBuilder);
 }
@@ -0,0 +1 @@
+#include "string.h"
\ No newline at end of file
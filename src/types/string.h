@@ -0,0 +1,112 @@
+#ifndef STRING_H
+#define STRING_H
+
+#include "arrow/types/string.h"
+#include "../array.h"
+#include "../buffer.h"
+#include "../ty.h"
+#include "../common/memory_pool.h"
+#include "../common/status.h"
+
+using namespace arrow;
+
+#define NEW_BINARY_ARRAY_DECL(NAME, RS_TYPE)                                                      \
+  ArrayBox* new_##RS_TYPE##_arr(int32_t length, BufferBox* offsets, BufferBox* data,              \
+                                int32_t null_count, BufferBox* null_bitmap) {                     \
+    std::shared_ptr<Buffer> bitmap = null_bitmap ? null_bitmap->sp : nullptr;                     \
+    ArrayBox* arr = new ArrayBox;                                                                 \
+    arr->sp = std::make_shared<NAME>(length, offsets->sp, data->sp, null_count, bitmap);          \
+    arr->p = arr->sp.get();                                                                       \
+    return arr;                                                                                   \
+  }
+
+#define NEW_BINARY_BUILDER_DECL(NAME, RS_TYPE)                              \
+  NAME* new_##RS_TYPE##_arr_builder(MemoryPool* pool, DataTypeBox* type) {  \
+    return new NAME(pool, type->sp);                                        \
+  }
+
+#define APPEND_BINARY_BUILDER_DECL(NAME, TYPE, RS_TYPE)                                            \
+  StatusBox* append_##RS_TYPE##_arr_builder(NAME* builder, const uint8_t* value, int32_t length) {  \
+    StatusBox* box = new StatusBox;                                                                 \
+    box->status = builder->Append(reinterpret_cast<const TYPE*>(value), length);                    \
+    return box;                                                                                     \
+  }
+
+#define APPEND_NULL_BINARY_BUILDER_DECL(NAME, RS_TYPE)                \
+  StatusBox* append_null_##RS_TYPE##_arr_builder(NAME* builder) {     \
+    StatusBox* box = new StatusBox;                                   \
+    box->status = builder->AppendNull();                              \
+    return box;                                                       \
+  }
+
+#define FINISH_BINARY_BUILDER_DECL(NAME, RS_TYPE)           \
+  ArrayBox* finish_##RS_TYPE##_arr_builder(NAME* builder) { \
+    ArrayBox* arr = new ArrayBox;                           \
+    arr->sp = builder->Finish();                            \
+    arr->p = arr->sp.get();                                 \
+    return arr;                                             \
+  }
+
+#define RELEASE_BINARY_BUILDER_DECL(NAME, RS_TYPE)          \
+  void release_##RS_TYPE##_arr_builder(NAME* builder) {     \
+    if (builder) {                                          \
+      delete builder;                                       \
+    }                                                       \
+  }
+
+extern "C" {
+
+  // Binary and string array
+
+  NEW_BINARY_ARRAY_DECL(BinaryArray, binary);
+  NEW_BINARY_ARRAY_DECL(StringArray, string);
+
+  const uint8_t* binary_arr_value(ArrayBox* arr, int i, int32_t* out_length) {
+    return reinterpret_cast<BinaryArray*>(arr->p)->GetValue(i, out_length);
+  }
+
+  int32_t binary_arr_value_offset(ArrayBox* arr, int i) {
+    return reinterpret_cast<BinaryArray*>(arr->p)->offset(i);
+  }
+
+  int32_t binary_arr_value_length(ArrayBox* arr, int i) {
+    return reinterpret_cast<BinaryArray*>(arr->p)->value_length(i);
+  }
+
+  const int32_t* binary_arr_raw_offsets(ArrayBox* arr) {
+    return reinterpret_cast<BinaryArray*>(arr->p)->raw_offsets();
+  }
+
+  BufferBox* binary_arr_offsets(ArrayBox* arr) {
+    BufferBox* buf = new BufferBox;
+    buf->sp = reinterpret_cast<BinaryArray*>(arr->p)->offsets();
+    buf->p = buf->sp.get();
+    return buf;
+  }
+
+  BufferBox* binary_arr_data(ArrayBox* arr) {
+    BufferBox* buf = new BufferBox;
+    buf->sp = reinterpret_cast<BinaryArray*>(arr->p)->data();
+    buf->p = buf->sp.get();
+    return buf;
+  }
+
+  // Binary and string array builder
+
+  NEW_BINARY_BUILDER_DECL(BinaryBuilder, binary);
+  NEW_BINARY_BUILDER_DECL(StringBuilder, string);
+
+  APPEND_BINARY_BUILDER_DECL(BinaryBuilder, uint8_t, binary);
+  APPEND_BINARY_BUILDER_DECL(StringBuilder, char, string);
+
+  APPEND_NULL_BINARY_BUILDER_DECL(BinaryBuilder, binary);
+  APPEND_NULL_BINARY_BUILDER_DECL(StringBuilder, string);
+
+  FINISH_BINARY_BUILDER_DECL(BinaryBuilder, binary);
+  FINISH_BINARY_BUILDER_DECL(StringBuilder, string);
+
+  RELEASE_BINARY_BUILDER_DECL(BinaryBuilder, binary);
+  RELEASE_BINARY_BUILDER_DECL(StringBuilder, string);
+}
+
+#endif
@@ -0,0 +1,236 @@
+use array::*;
+use array_builder::{ArrayBuilder, RawArrayBuilderMutPtr};
+use buffer::{RawBufferPtr, Buf};
+use ty::{RawDataTypePtr, DataType, Ty};
+use ty;
+use common::memory_pool::{RawMemoryPoolMutPtr, MemoryPool, PoolRef};
+use common::status::{RawStatusPtr, ArrowError};
+
+use std::any::Any;
+use std::mem;
+use std::ptr;
+use std::slice;
+use std::str;
+
+#[macro_use]
+use common::status;
+
+macro_rules! define_binary_array {
+  ($name:ident, $raw_type:expr, $ty:ident, $utf8:expr, $($accepted_ty:expr),+) => (
+    pub struct $name {
+      raw_array: RawArrayPtr
+    }
+
+    impl $name {
+      // Fails unless the offsets buffer holds length + 1 increasing entries within the data buffer,
+      // the null bitmap holds length bits, and string values are valid UTF-8
+      pub fn from_buffers(length: i32, offsets: Buf, data: Buf, null_count: i32, null_bitmap: Option<Buf>) -> Result<$name, ArrowError> {
+        try!(check_validity(length, null_count, &null_bitmap));
+        let value_offsets = try!(read_offsets(&offsets, length));
+        let bytes = buf_bytes(&data);
+        try!(check_offsets(&value_offsets, bytes.len()));
+        if $utf8 {
+          try!(check_utf8(&value_offsets, bytes));
+        }
+
+        let raw_null_bitmap = match null_bitmap {
+          Some(ref bitmap) => bitmap.raw_buf(),
+          None => ptr::null()
+        };
+        Ok($name {
+          raw_array: unsafe {
+            concat_idents!(new_, $ty, _arr) (length, offsets.raw_buf(), data.raw_buf(), null_count, raw_null_bitmap)
+          }
+        })
+      }
+
+      // Panics if i is out of bounds
+      pub fn value_offset(&self, i: i32) -> i32 {
+        self.check_index(i);
+        unsafe { binary_arr_value_offset(self.raw_array, i) }
+      }
+
+      // Panics if i is out of bounds
+      pub fn value_length(&self, i: i32) -> i32 {
+        self.check_index(i);
+        unsafe { binary_arr_value_length(self.raw_array, i) }
+      }
+
+      // The offsets buffer holds len() + 1 entries
+      pub fn value_offsets(&self) -> &[i32] {
+        unsafe { slice::from_raw_parts(binary_arr_raw_offsets(self.raw_array), self.len() as usize + 1) }
+      }
+
+      pub fn offsets(&self) -> Buf {
+        Buf::from_raw( unsafe { binary_arr_offsets(self.raw_array) } )
+      }
+
+      pub fn values(&self) -> Buf {
+        self.data()
+      }
+
+      pub fn null_bitmap(&self) -> Option<Buf> {
+        let raw_buf = unsafe { arr_null_bitmap(self.raw_array) };
+        if raw_buf.is_null() {
+          None
+        } else {
+          Some(Buf::from_raw(raw_buf))
+        }
+      }
+
+      fn value_bytes(&self, i: i32) -> &[u8] {
+        self.check_index(i);
+        unsafe {
+          let mut len: i32 = 0;
+          let data = binary_arr_value(self.raw_array, i, &mut len);
+          slice::from_raw_parts(data, len as usize)
+        }
+      }
+
+      fn check_index(&self, i: i32) {
+        assert!(i >= 0 && i < self.len(), "index is out of the bounds of the array");
+      }
+    }
+
+    impl_array!($name, $raw_type, binary_arr_data, $($accepted_ty),+);
+  );
+}
+
+// Strings are valid binaries, so string arrays can also be viewed as binary arrays
+define_binary_array!(BinaryArray, ty::new_binary_type(), binary, false, Ty::BINARY, Ty::STRING);
+define_binary_array!(StringArray, ty::new_string_type(), string, true, Ty::STRING);
+
+impl BinaryArray {
+  // Panics if i is out of bounds
+  pub fn value(&self, i: i32) -> &[u8] {
+    self.value_bytes(i)
+  }
+}
+
+impl StringArray {
+  // Panics if i is out of bounds. The values are checked to be valid UTF-8 when the array is
+  // created from buffers or read from IPC messages, so they are not checked again here.
+  pub fn value(&self, i: i32) -> &str {
+    unsafe { str::from_utf8_unchecked(self.value_bytes(i)) }
+  }
+}
+
+pub enum RawBinaryArrayBuilder {}
+pub enum RawStringArrayBuilder {}
+
+macro_rules! define_binary_array_builder {
+  ($builder_name:ident, $raw_builder:ident, $array_name:ident, $value_ty:ty, $ty:ident) => (
+    pub struct $builder_name {
+      raw_builder: *mut $raw_builder,
+      _pool: PoolRef
+    }
+
+    impl $builder_name {
+      pub fn new(pool: &MemoryPool, data_type: &DataType) -> $builder_name {
+        let pool = PoolRef::new(pool);
+        $builder_name {
+          raw_builder: unsafe { concat_idents!(new_, $ty, _arr_builder) (pool.raw_memory_pool(), data_type.raw_data_type()) },
+          _pool: pool
+        }
+      }
+
+      pub fn append_value(&mut self, value: &$value_ty) -> Result<&mut $builder_name, ArrowError> {
+        let bytes: &[u8] = value.as_ref();
+        unsafe {
+          let s = concat_idents!(append_, $ty, _arr_builder) (self.raw_builder, bytes.as_ptr(), bytes.len() as i32);
+          result_from_status!(s, self)
+        }
+      }
+
+      pub fn append_null(&mut self) -> Result<&mut $builder_name, ArrowError> {
+        unsafe {
+          let s = concat_idents!(append_null_, $ty, _arr_builder) (self.raw_builder);
+          result_from_status!(s, self)
+        }
+      }
+
+      pub fn append(&mut self, values: &[&$value_ty]) -> Result<&mut $builder_name, ArrowError> {
+        for value in values {
+          try!(self.append_value(value));
+        }
+        Ok(self)
+      }
+
+      pub fn append_nullable(&mut self, values: &[Option<&$value_ty>]) -> Result<&mut $builder_name, ArrowError> {
+        for value in values {
+          match *value {
+            Some(v) => try!(self.append_value(v)),
+            None => try!(self.append_null())
+          };
+        }
+        Ok(self)
+      }
+
+      pub fn finish(&mut self) -> $array_name {
+        $array_name {
+          raw_array: unsafe { concat_idents!(finish_, $ty, _arr_builder) (self.raw_builder) }
+        }
+      }
+
+      pub fn finish_as_base(&mut self) -> BaseArray {
+        let arr = self.finish();
+        unsafe { mem::transmute(arr) }
+      }
+    }
+
+    impl ArrayBuilder for $builder_name {
+      fn raw_builder(&self) -> RawArrayBuilderMutPtr {
+        self.raw_builder as RawArrayBuilderMutPtr
+      }
+
+      fn append_null(&mut self) -> Result<(), ArrowError> {
+        try!($builder_name::append_null(self));
+        Ok(())
+      }
+
+      fn as_any(&self) -> &Any {
+        self
+      }
+
+      fn as_any_mut(&mut self) -> &mut Any {
+        self
+      }
+    }
+
+    impl Drop for $builder_name {
+      fn drop(&mut self) {
+        unsafe { concat_idents!(release_, $ty, _arr_builder) (self.raw_builder); }
+      }
+    }
+  );
+}
+
+define_binary_array_builder!(BinaryArrayBuilder, RawBinaryArrayBuilder, BinaryArray, [u8], binary);
+define_binary_array_builder!(StringArrayBuilder, RawStringArrayBuilder, StringArray, str, string);
+
+extern "C" {
+  pub fn new_binary_arr(length: i32, offsets: RawBufferPtr, data: RawBufferPtr, null_count: i32, null_bitmap: RawBufferPtr) -> RawArrayPtr;
+  pub fn new_string_arr(length: i32, offsets: RawBufferPtr, data: RawBufferPtr, null_count: i32, null_bitmap: RawBufferPtr) -> RawArrayPtr;
+
+  pub fn binary_arr_value(arr: RawArrayPtr, i: i32, out_length: *mut i32) -> *const u8;
+  pub fn binary_arr_value_offset(arr: RawArrayPtr, i: i32) -> i32;
+  pub fn binary_arr_value_length(arr: RawArrayPtr, i: i32) -> i32;
+  pub fn binary_arr_raw_offsets(arr: RawArrayPtr) -> *const i32;
+  pub fn binary_arr_offsets(arr: RawArrayPtr) -> RawBufferPtr;
+  pub fn binary_arr_data(arr: RawArrayPtr) -> RawBufferPtr;
+
+  pub fn new_binary_arr_builder(pool: RawMemoryPoolMutPtr, ty: RawDataTypePtr) -> *mut RawBinaryArrayBuilder;
+  pub fn new_string_arr_builder(pool: RawMemoryPoolMutPtr, ty: RawDataTypePtr) -> *mut RawStringArrayBuilder;
+
+  pub fn append_binary_arr_builder(builder: *mut RawBinaryArrayBuilder, value: *const u8, len: i32) -> RawStatusPtr;
+  pub fn append_string_arr_builder(builder: *mut RawStringArrayBuilder, value: *const u8, len: i32) -> RawStatusPtr;
+
+  pub fn append_null_binary_arr_builder(builder: *mut RawBinaryArrayBuilder) -> RawStatusPtr;
+  pub fn append_null_string_arr_builder(builder: *mut RawStringArrayBuilder) -> RawStatusPtr;
+
+  pub fn finish_binary_arr_builder(builder: *mut RawBinaryArrayBuilder) -> RawArrayPtr;
+  pub fn finish_string_arr_builder(builder: *mut RawStringArrayBuilder) -> RawArrayPtr;
+
+  pub fn release_binary_arr_builder(builder: *mut RawBinaryArrayBuilder);
+  pub fn release_string_arr_builder(builder: *mut RawStringArrayBuilder);
+}
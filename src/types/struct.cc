@@ -0,0 +1 @@
+#include "struct.h"
\ No newline at end of file
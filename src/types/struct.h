@@ -0,0 +1,107 @@
+#ifndef STRUCT_H
+#define STRUCT_H
+
+#include "arrow/builder.h"
+#include "arrow/types/struct.h"
+#include "../array.h"
+#include "../buffer.h"
+#include "../ty.h"
+#include "../common/memory_pool.h"
+#include "../common/status.h"
+
+using namespace arrow;
+
+// Builds the validity bitmap of the struct slots. The field builders are owned by the caller, and
+// are finished together with the struct.
+class StructArrayBuilder : public ArrayBuilder {
+ public:
+  StructArrayBuilder(MemoryPool* pool, const std::shared_ptr<DataType>& type,
+                     const std::vector<ArrayBuilder*>& field_builders)
+      : ArrayBuilder(pool, type), field_builders_(field_builders) {}
+
+  Status Append(bool is_valid) {
+    return AppendToBitmap(is_valid);
+  }
+
+  std::shared_ptr<Array> Finish() override {
+    std::vector<std::shared_ptr<Array>> fields;
+    for (ArrayBuilder* field_builder : field_builders_) {
+      fields.push_back(field_builder->Finish());
+    }
+    auto result = std::make_shared<StructArray>(type_, length_, fields, null_count_, null_bitmap_);
+    null_bitmap_ = nullptr;
+    capacity_ = length_ = null_count_ = 0;
+    return result;
+  }
+
+ private:
+  std::vector<ArrayBuilder*> field_builders_;
+};
+
+extern "C" {
+
+  // Struct array
+
+  ArrayBox* new_struct_arr(DataTypeBox* type, int32_t length, ArrayBox* fields[], int field_num,
+                           int32_t null_count, BufferBox* null_bitmap) {
+    std::vector<std::shared_ptr<Array>> field_arrays;
+    for (int i = 0; i < field_num; i++) {
+      field_arrays.push_back(fields[i]->sp);
+    }
+
+    std::shared_ptr<Buffer> bitmap = null_bitmap ? null_bitmap->sp : nullptr;
+    ArrayBox* arr = new ArrayBox;
+    arr->sp = std::make_shared<StructArray>(type->sp, length, field_arrays, null_count, bitmap);
+    arr->p = arr->sp.get();
+    return arr;
+  }
+
+  ArrayBox* struct_arr_field(ArrayBox* arr, int i) {
+    ArrayBox* field = new ArrayBox;
+    field->sp = reinterpret_cast<StructArray*>(arr->p)->field(i);
+    field->p = field->sp.get();
+    return field;
+  }
+
+  int struct_arr_num_fields(ArrayBox* arr) {
+    return reinterpret_cast<StructArray*>(arr->p)->fields().size();
+  }
+
+  // Struct arrays keep their values in the child arrays, so the data buffer is always empty
+  BufferBox* struct_arr_data(ArrayBox* arr) {
+    BufferBox* buf = new BufferBox;
+    buf->sp = std::make_shared<Buffer>(nullptr, 0);
+    buf->p = buf->sp.get();
+    return buf;
+  }
+
+  // Struct array builder
+
+  // The field builders are owned by the caller, so they must outlive the struct builder
+  StructArrayBuilder* new_struct_arr_builder(MemoryPool* pool, DataTypeBox* type, ArrayBuilder* field_builders[],
+                                             int field_num) {
+    std::vector<ArrayBuilder*> builders(field_builders, field_builders + field_num);
+    return new StructArrayBuilder(pool, type->sp, builders);
+  }
+
+  StatusBox* append_struct_arr_builder(StructArrayBuilder* builder, bool is_valid) {
+    StatusBox* box = new StatusBox;
+    box->status = builder->Append(is_valid);
+    return box;
+  }
+
+  ArrayBox* finish_struct_arr_builder(StructArrayBuilder* builder) {
+    ArrayBox* arr = new ArrayBox;
+    arr->sp = builder->Finish();
+    arr->p = arr->sp.get();
+    return arr;
+  }
+
+  void release_struct_arr_builder(StructArrayBuilder* builder) {
+    if (builder) {
+      delete builder;
+    }
+  }
+}
+
+#endif
@@ -0,0 +1,230 @@
+use array::*;
+use array_builder::{ArrayBuilder, RawArrayBuilderMutPtr};
+use buffer::{RawBufferPtr, Buf, BufferBuilder};
+use bitmap::Bitmap;
+use ty::{RawDataTypePtr, DataType, Ty};
+use ty;
+use common::memory_pool::{RawMemoryPoolMutPtr, MemoryPool, PoolRef};
+use common::status::{RawStatusPtr, ArrowError};
+
+use std::any::Any;
+use std::mem;
+use std::ptr;
+
+#[macro_use]
+use common::status;
+
+// A struct array owns one child array per field of its type. The validity bitmap of the struct
+// array marks which struct slots are null, independently of the nulls in the child arrays.
+pub struct StructArray {
+  raw_array: RawArrayPtr
+}
+
+impl StructArray {
+  // validity marks the valid slots and must cover every slot; None means that every slot is valid
+  pub fn new(pool: &MemoryPool, data_type: &DataType, fields: &[BaseArray], validity: Option<&Bitmap>) -> Result<StructArray, ArrowError> {
+    match data_type.ty() {
+      Ty::STRUCT => {},
+      _ => return Err(ArrowError::type_error(format!("{} is not a struct type", data_type.to_string())))
+    }
+    if fields.len() as i32 != data_type.num_children() {
+      return Err(ArrowError::invalid("the number of arrays does not match the number of struct fields"));
+    }
+    for (i, field) in fields.iter().enumerate() {
+      let field_type = data_type.child(i as i32).data_type();
+      if field.data_type() != field_type {
+        return Err(ArrowError::type_error(format!("struct field {} expects {}, but the array holds {}", i,
+                                                  field_type.to_string(), field.data_type().to_string())));
+      }
+    }
+
+    let length = match fields.first() {
+      Some(field) => field.len(),
+      None => 0
+    };
+    if fields.iter().any(|field| field.len() != length) {
+      return Err(ArrowError::invalid("struct fields must have the same length"));
+    }
+
+    let raw_fields = fields.into_iter().map(|field| field.raw_array()).collect::<Vec<RawArrayPtr>>();
+
+    let validity = match validity {
+      Some(validity) => validity,
+      None => return Ok(StructArray {
+        raw_array: unsafe { new_struct_arr(data_type.raw_data_type(), length, raw_fields.as_ptr(), fields.len() as i32, 0, ptr::null()) }
+      })
+    };
+    if validity.len() != length as usize {
+      return Err(ArrowError::invalid(format!("the validity bitmap has {} slots, but the struct fields have {}",
+                                             validity.len(), length)));
+    }
+    let null_count = validity.count_unset_bits() as i32;
+
+    let mut builder = BufferBuilder::new(pool);
+    try!(builder.raw_append(validity.as_bytes().as_ptr(), validity.as_bytes().len() as i32));
+    let null_bitmap = builder.finish();
+
+    Ok(StructArray {
+      raw_array: unsafe {
+        new_struct_arr(data_type.raw_data_type(), length, raw_fields.as_ptr(), fields.len() as i32, null_count,
+                       null_bitmap.raw_buf() as RawBufferPtr)
+      }
+    })
+  }
+
+  pub fn num_columns(&self) -> i32 {
+    unsafe { struct_arr_num_fields(self.raw_array) }
+  }
+
+  pub fn column(&self, i: i32) -> BaseArray {
+    BaseArray::from_raw( unsafe { struct_arr_field(self.raw_array, i) } )
+  }
+
+  // Fails if T does not accept the type of the i-th field
+  pub fn typed_column<T: Any + Array>(&self, i: i32) -> Result<T, ArrowError> {
+    typed_array( unsafe { struct_arr_field(self.raw_array, i) } )
+  }
+
+  pub fn column_by_name(&self, name: &str) -> Option<BaseArray> {
+    let data_type = self.data_type();
+    (0..data_type.num_children())
+      .find(|i| data_type.child(*i).name() == name)
+      .map(|i| self.column(i))
+  }
+
+  pub fn null_bitmap(&self) -> Option<Buf> {
+    let raw_buf = unsafe { arr_null_bitmap(self.raw_array) };
+    if raw_buf.is_null() {
+      None
+    } else {
+      Some(Buf::from_raw(raw_buf))
+    }
+  }
+}
+
+impl_array!(StructArray, ty::new_struct_type(0, &[]), struct_arr_data, Ty::STRUCT);
+
+pub enum RawStructArrayBuilder {}
+
+// Builds a struct array from one builder per field. Each slot started by append(true) takes one
+// value from every field builder, which the caller appends separately.
+pub struct StructArrayBuilder {
+  raw_builder: *mut RawStructArrayBuilder,
+  field_builders: Vec<Box<ArrayBuilder>>,
+  _pool: PoolRef
+}
+
+impl StructArrayBuilder {
+  pub fn new(pool: &MemoryPool, data_type: &DataType, field_builders: Vec<Box<ArrayBuilder>>) -> Result<StructArrayBuilder, ArrowError> {
+    match data_type.ty() {
+      Ty::STRUCT => {},
+      _ => return Err(ArrowError::type_error(format!("{} is not a struct type", data_type.to_string())))
+    }
+    if field_builders.len() as i32 != data_type.num_children() {
+      return Err(ArrowError::invalid("the number of builders does not match the number of struct fields"));
+    }
+
+    let pool = PoolRef::new(pool);
+    let raw_field_builders = field_builders.iter().map(|builder| builder.raw_builder()).collect::<Vec<RawArrayBuilderMutPtr>>();
+    Ok(StructArrayBuilder {
+      raw_builder: unsafe {
+        new_struct_arr_builder(pool.raw_memory_pool(), data_type.raw_data_type(), raw_field_builders.as_ptr(),
+                               field_builders.len() as i32)
+      },
+      field_builders: field_builders,
+      _pool: pool
+    })
+  }
+
+  pub fn num_fields(&self) -> i32 {
+    self.field_builders.len() as i32
+  }
+
+  pub fn field_builder(&mut self, i: i32) -> &mut ArrayBuilder {
+    &mut *self.field_builders[i as usize]
+  }
+
+  // Returns None if the builder of the i-th field is not a B
+  pub fn typed_field_builder<B: ArrayBuilder + 'static>(&mut self, i: i32) -> Option<&mut B> {
+    self.field_builders[i as usize].as_any_mut().downcast_mut::<B>()
+  }
+
+  pub fn append(&mut self, is_valid: bool) -> Result<&mut StructArrayBuilder, ArrowError> {
+    unsafe {
+      let s = append_struct_arr_builder(self.raw_builder, is_valid);
+      result_from_status!(s, self)
+    }
+  }
+
+  // Also appends a null to every field builder, so the fields keep the length of the struct
+  pub fn append_null(&mut self) -> Result<&mut StructArrayBuilder, ArrowError> {
+    for builder in self.field_builders.iter_mut() {
+      try!(builder.append_null());
+    }
+    self.append(false)
+  }
+
+  // Fails without finishing any field if a field builder does not hold one value per slot
+  pub fn finish(&mut self) -> Result<StructArray, ArrowError> {
+    let len = ArrayBuilder::len(self);
+    for (i, builder) in self.field_builders.iter().enumerate() {
+      if builder.len() != len {
+        return Err(ArrowError::invalid(format!("struct field {} has {} values but the struct has {} slots", i,
+                                               builder.len(), len)));
+      }
+    }
+
+    Ok(StructArray {
+      raw_array: unsafe { finish_struct_arr_builder(self.raw_builder) }
+    })
+  }
+
+  pub fn finish_as_base(&mut self) -> Result<BaseArray, ArrowError> {
+    let arr = try!(self.finish());
+    Ok(unsafe { mem::transmute(arr) })
+  }
+}
+
+impl ArrayBuilder for StructArrayBuilder {
+  fn raw_builder(&self) -> RawArrayBuilderMutPtr {
+    self.raw_builder as RawArrayBuilderMutPtr
+  }
+
+  fn append_null(&mut self) -> Result<(), ArrowError> {
+    try!(StructArrayBuilder::append_null(self));
+    Ok(())
+  }
+
+  fn as_any(&self) -> &Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut Any {
+    self
+  }
+
+  fn finish(&mut self) -> Result<BaseArray, ArrowError> {
+    self.finish_as_base()
+  }
+}
+
+impl Drop for StructArrayBuilder {
+  fn drop(&mut self) {
+    // the field builders are released after this, when the field is dropped
+    unsafe { release_struct_arr_builder(self.raw_builder); }
+  }
+}
+
+extern "C" {
+  pub fn new_struct_arr(ty: RawDataTypePtr, length: i32, fields: *const RawArrayPtr, field_num: i32,
+                        null_count: i32, null_bitmap: RawBufferPtr) -> RawArrayPtr;
+  pub fn struct_arr_field(arr: RawArrayPtr, i: i32) -> RawArrayPtr;
+  pub fn struct_arr_num_fields(arr: RawArrayPtr) -> i32;
+  pub fn struct_arr_data(arr: RawArrayPtr) -> RawBufferPtr;
+
+  pub fn new_struct_arr_builder(pool: RawMemoryPoolMutPtr, ty: RawDataTypePtr, field_builders: *const RawArrayBuilderMutPtr,
+                                field_num: i32) -> *mut RawStructArrayBuilder;
+  pub fn append_struct_arr_builder(builder: *mut RawStructArrayBuilder, is_valid: bool) -> RawStatusPtr;
+  pub fn finish_struct_arr_builder(builder: *mut RawStructArrayBuilder) -> RawArrayPtr;
+  pub fn release_struct_arr_builder(builder: *mut RawStructArrayBuilder);
+}
@@ -0,0 +1,165 @@
+use array::*;
+use buffer::{RawBufferPtr, Buf};
+use ty::{RawDataTypePtr, DataType, TemporalType, TimeUnit, Ty};
+use ty;
+use types::primitive::{I32Array, I64Array, new_i32_arr, new_i64_arr, i32_arr_raw_data, i64_arr_raw_data, i32_arr_value,
+                       i64_arr_value, arr_data};
+
+use common::status::ArrowError;
+
+use std::ptr;
+use std::slice;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+type NewArrayFn = unsafe extern "C" fn(RawDataTypePtr, i32, RawBufferPtr, i32, RawBufferPtr) -> RawArrayPtr;
+
+// Creates an array of the data type over the data and the null bitmap of the values
+fn share_buffers<A: Array>(new_arr: NewArrayFn, data_type: &DataType, values: &A) -> RawArrayPtr {
+  let raw_null_bitmap = unsafe { arr_null_bitmap(values.raw_array()) };
+  let null_bitmap = if raw_null_bitmap.is_null() { None } else { Some(Buf::from_raw(raw_null_bitmap)) };
+  let raw_null_bitmap = match null_bitmap {
+    Some(ref bitmap) => bitmap.raw_buf(),
+    None => ptr::null()
+  };
+  unsafe { new_arr(data_type.raw_data_type(), values.len(), values.data().raw_buf(), values.null_count(), raw_null_bitmap) }
+}
+
+fn unit_of<A: Array>(array: &A) -> Result<TimeUnit, ArrowError> {
+  match array.data_type().time_unit() {
+    Some(unit) => Ok(unit),
+    None => Err(ArrowError::type_error(format!("{} has no time unit", array.data_type().to_string())))
+  }
+}
+
+// Temporal arrays are primitive arrays whose data type carries the unit and the timezone
+macro_rules! define_temporal_array {
+  ($name:ident, $values_type:ident, $value_ty:ty, $new_fn:ident, $raw_data_fn:ident, $value_fn:ident, $storage_ty:expr,
+   $temporal_ty:expr, $null_type:expr) => (
+    pub struct $name {
+      raw_array: RawArrayPtr
+    }
+
+    impl $name {
+      // Fails for arrays whose type was not created by TemporalType::data_type(), e.g. ones taken
+      // from a row batch as the wrong array type
+      pub fn temporal_type(&self) -> Result<TemporalType, ArrowError> {
+        match self.data_type().temporal_type() {
+          Some(temporal_type) => Ok(temporal_type),
+          None => Err(ArrowError::type_error(format!("{} is not a temporal type", self.data_type().to_string())))
+        }
+      }
+
+      // Raw value in the unit of the array
+      pub fn value(&self, i: i32) -> $value_ty {
+        unsafe { $value_fn(self.raw_array, i) }
+      }
+
+      pub fn as_slice(&self) -> &[$value_ty] {
+        unsafe { slice::from_raw_parts($raw_data_fn(self.raw_array), self.len() as usize) }
+      }
+
+      // The raw values as a primitive array sharing the buffers of this array
+      pub fn values(&self) -> $values_type {
+        $values_type::from_raw(share_buffers($new_fn, &DataType::new_primitive($storage_ty), self))
+      }
+    }
+
+    impl_array!($name, $null_type, arr_data, $temporal_ty);
+  );
+}
+
+// Splits a value since the UNIX epoch into a point of time, rounding toward negative infinity.
+// None if the point is out of the range of SystemTime.
+fn to_system_time(value: i64, unit: TimeUnit) -> Option<SystemTime> {
+  let per_second = unit.per_second();
+  let secs = value.div_euclid(per_second);
+  let nanos = Duration::new(0, (value.rem_euclid(per_second) * (1_000_000_000 / per_second)) as u32);
+
+  let time = if secs >= 0 {
+    UNIX_EPOCH.checked_add(Duration::from_secs(secs as u64))
+  } else {
+    secs.checked_neg().and_then(|secs| UNIX_EPOCH.checked_sub(Duration::from_secs(secs as u64)))
+  };
+  time.and_then(|time| time.checked_add(nanos))
+}
+
+define_temporal_array!(DateArray, I32Array, i32, new_i32_arr, i32_arr_raw_data, i32_arr_value, Ty::INT32, Ty::DATE,
+                       ty::new_temporal_type(Ty::DATE, -1, ptr::null()));
+
+impl DateArray {
+  pub fn new(values: I32Array) -> DateArray {
+    let data_type = DataType::new( unsafe { ty::new_temporal_type(Ty::DATE, -1, ptr::null()) } );
+    DateArray {
+      raw_array: share_buffers(new_i32_arr, &data_type, &values)
+    }
+  }
+
+  // None if the day is out of the range of SystemTime
+  pub fn value_as_system_time(&self, i: i32) -> Option<SystemTime> {
+    to_system_time(self.value(i) as i64 * SECONDS_PER_DAY, TimeUnit::SECOND)
+  }
+}
+
+define_temporal_array!(TimestampArray, I64Array, i64, new_i64_arr, i64_arr_raw_data, i64_arr_value, Ty::INT64,
+                       Ty::TIMESTAMP, ty::new_temporal_type(Ty::TIMESTAMP, TimeUnit::MILLI as i32, ptr::null()));
+
+impl TimestampArray {
+  // Fails if the timezone contains a NUL character
+  pub fn new(values: I64Array, unit: TimeUnit, timezone: Option<String>) -> Result<TimestampArray, ArrowError> {
+    let data_type = try!(try!(TemporalType::timestamp(unit, timezone)).data_type());
+    Ok(TimestampArray {
+      raw_array: share_buffers(new_i64_arr, &data_type, &values)
+    })
+  }
+
+  pub fn unit(&self) -> Result<TimeUnit, ArrowError> {
+    unit_of(self)
+  }
+
+  pub fn timezone(&self) -> Result<Option<String>, ArrowError> {
+    match try!(self.temporal_type()) {
+      TemporalType::Timestamp { timezone, .. } => Ok(timezone),
+      _ => Ok(None)
+    }
+  }
+
+  // Timestamps are instants, so the timezone does not affect the result. None if the array has no
+  // time unit or the instant is out of the range of SystemTime.
+  pub fn value_as_system_time(&self, i: i32) -> Option<SystemTime> {
+    let value = self.value(i);
+    self.unit().ok().and_then(|unit| to_system_time(value, unit))
+  }
+}
+
+define_temporal_array!(TimeArray, I64Array, i64, new_i64_arr, i64_arr_raw_data, i64_arr_value, Ty::INT64,
+                       Ty::TIME, ty::new_temporal_type(Ty::TIME, TimeUnit::MILLI as i32, ptr::null()));
+
+impl TimeArray {
+  pub fn new(values: I64Array, unit: TimeUnit) -> TimeArray {
+    let data_type = DataType::new( unsafe { ty::new_temporal_type(Ty::TIME, unit as i32, ptr::null()) } );
+    TimeArray {
+      raw_array: share_buffers(new_i64_arr, &data_type, &values)
+    }
+  }
+
+  pub fn unit(&self) -> Result<TimeUnit, ArrowError> {
+    unit_of(self)
+  }
+
+  // Time elapsed since midnight, or None for a negative value or an array without a time unit
+  pub fn value_as_duration(&self, i: i32) -> Option<Duration> {
+    let unit = match self.unit() {
+      Ok(unit) => unit,
+      Err(_) => return None
+    };
+    let value = self.value(i);
+    if value < 0 {
+      return None;
+    }
+    let value = value as u64;
+    let per_second = unit.per_second() as u64;
+    Some(Duration::new(value / per_second, ((value % per_second) * (1_000_000_000 / per_second)) as u32))
+  }
+}
\ No newline at end of file
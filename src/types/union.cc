@@ -0,0 +1 @@
+#include "union.h"
\ No newline at end of file
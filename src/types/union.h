@@ -0,0 +1,207 @@
+#ifndef UNION_H
+#define UNION_H
+
+#include "arrow/array.h"
+#include "arrow/builder.h"
+#include "arrow/types/primitive.h"
+#include "../array.h"
+#include "../buffer.h"
+#include "../ty.h"
+#include "../common/memory_pool.h"
+
+using namespace arrow;
+
+// A union array selects the value of each slot by an int8 type id, and by an int32 offset into the
+// child for the dense mode. The type id array carries the validity of slots.
+class UnionArrayImpl : public Array {
+ public:
+  UnionArrayImpl(const std::shared_ptr<DataType>& type, const std::shared_ptr<Array>& type_ids,
+                 const std::shared_ptr<Array>& value_offsets, const std::vector<std::shared_ptr<Array>>& children)
+      : Array(type, type_ids->length(), type_ids->null_count(), type_ids->null_bitmap()),
+        type_ids_(type_ids), value_offsets_(value_offsets), children_(children) {}
+
+  const std::shared_ptr<Array>& type_ids() const { return type_ids_; }
+
+  // Absent for sparse unions
+  const std::shared_ptr<Array>& value_offsets() const { return value_offsets_; }
+
+  const std::vector<std::shared_ptr<Array>>& children() const { return children_; }
+
+  int8_t type_id(int32_t i) const {
+    return static_cast<Int8Array*>(type_ids_.get())->Value(i);
+  }
+
+  int32_t value_offset(int32_t i) const {
+    return value_offsets_ ? static_cast<Int32Array*>(value_offsets_.get())->Value(i) : i;
+  }
+
+  bool Equals(const std::shared_ptr<Array>& arr) const override {
+    if (this == arr.get()) {
+      return true;
+    }
+    if (!arr || length() != arr->length() || null_count() != arr->null_count() ||
+        !type()->Equals(arr->type().get())) {
+      return false;
+    }
+    return RangeEquals(0, length(), 0, arr);
+  }
+
+  bool RangeEquals(int32_t start_idx, int32_t end_idx, int32_t other_start_idx,
+                   const std::shared_ptr<Array>& arr) const override {
+    if (this == arr.get()) {
+      return true;
+    }
+    const UnionArrayImpl* other = dynamic_cast<const UnionArrayImpl*>(arr.get());
+    if (!other) {
+      return false;
+    }
+    for (int32_t i = start_idx, o_i = other_start_idx; i < end_idx; ++i, ++o_i) {
+      if (IsNull(i) != other->IsNull(o_i)) {
+        return false;
+      }
+      if (IsNull(i)) {
+        continue;
+      }
+      int8_t id = type_id(i);
+      if (id != other->type_id(o_i)) {
+        return false;
+      }
+      int32_t offset = value_offset(i);
+      if (!children_[id]->RangeEquals(offset, offset + 1, other->value_offset(o_i), other->children_[id])) {
+        return false;
+      }
+    }
+    return true;
+  }
+
+ private:
+  std::shared_ptr<Array> type_ids_;
+  std::shared_ptr<Array> value_offsets_;
+  std::vector<std::shared_ptr<Array>> children_;
+};
+
+// Counts the slots of a union. The type id, value offset and child builders are owned by the
+// caller, and are finished together with the union.
+class UnionArrayBuilder : public ArrayBuilder {
+ public:
+  // value_offsets is null for sparse unions
+  UnionArrayBuilder(MemoryPool* pool, const std::shared_ptr<DataType>& type, ArrayBuilder* type_ids,
+                    ArrayBuilder* value_offsets, const std::vector<ArrayBuilder*>& children)
+      : ArrayBuilder(pool, type), type_ids_(type_ids), value_offsets_(value_offsets), children_(children) {}
+
+  // Called after a type id has been appended, so the builders of enclosing lists see the new slot
+  void Advance() {
+    length_ = type_ids_->length();
+    null_count_ = type_ids_->null_count();
+  }
+
+  std::shared_ptr<Array> Finish() override {
+    std::shared_ptr<Array> type_ids = type_ids_->Finish();
+    std::shared_ptr<Array> value_offsets = value_offsets_ ? value_offsets_->Finish() : nullptr;
+    std::vector<std::shared_ptr<Array>> children;
+    for (ArrayBuilder* child : children_) {
+      children.push_back(child->Finish());
+    }
+    length_ = null_count_ = 0;
+    return std::make_shared<UnionArrayImpl>(type_, type_ids, value_offsets, children);
+  }
+
+ private:
+  ArrayBuilder* type_ids_;
+  ArrayBuilder* value_offsets_;
+  std::vector<ArrayBuilder*> children_;
+};
+
+extern "C" {
+
+  // Union array
+
+  // value_offsets is null for sparse unions
+  ArrayBox* new_union_arr(DataTypeBox* type, ArrayBox* type_ids, ArrayBox* value_offsets, ArrayBox* children[],
+                          int child_num) {
+    std::vector<std::shared_ptr<Array>> child_arrays;
+    for (int i = 0; i < child_num; i++) {
+      child_arrays.push_back(children[i]->sp);
+    }
+
+    std::shared_ptr<Array> offsets = value_offsets ? value_offsets->sp : nullptr;
+    ArrayBox* arr = new ArrayBox;
+    arr->sp = std::make_shared<UnionArrayImpl>(type->sp, type_ids->sp, offsets, child_arrays);
+    arr->p = arr->sp.get();
+    return arr;
+  }
+
+  ArrayBox* union_arr_type_ids(ArrayBox* arr) {
+    ArrayBox* type_ids = new ArrayBox;
+    type_ids->sp = reinterpret_cast<UnionArrayImpl*>(arr->p)->type_ids();
+    type_ids->p = type_ids->sp.get();
+    return type_ids;
+  }
+
+  ArrayBox* union_arr_value_offsets(ArrayBox* arr) {
+    std::shared_ptr<Array> offsets = reinterpret_cast<UnionArrayImpl*>(arr->p)->value_offsets();
+    if (!offsets) {
+      return nullptr;
+    }
+    ArrayBox* value_offsets = new ArrayBox;
+    value_offsets->sp = offsets;
+    value_offsets->p = value_offsets->sp.get();
+    return value_offsets;
+  }
+
+  ArrayBox* union_arr_child(ArrayBox* arr, int i) {
+    ArrayBox* child = new ArrayBox;
+    child->sp = reinterpret_cast<UnionArrayImpl*>(arr->p)->children()[i];
+    child->p = child->sp.get();
+    return child;
+  }
+
+  int union_arr_num_children(ArrayBox* arr) {
+    return reinterpret_cast<UnionArrayImpl*>(arr->p)->children().size();
+  }
+
+  int8_t union_arr_type_id(ArrayBox* arr, int i) {
+    return reinterpret_cast<UnionArrayImpl*>(arr->p)->type_id(i);
+  }
+
+  int32_t union_arr_value_offset(ArrayBox* arr, int i) {
+    return reinterpret_cast<UnionArrayImpl*>(arr->p)->value_offset(i);
+  }
+
+  // Union arrays keep their values in the child arrays, so the data buffer is always empty
+  BufferBox* union_arr_data(ArrayBox* arr) {
+    BufferBox* buf = new BufferBox;
+    buf->sp = std::make_shared<Buffer>(nullptr, 0);
+    buf->p = buf->sp.get();
+    return buf;
+  }
+
+  // Union array builder
+
+  // The type id, value offset and child builders are owned by the caller, so they must outlive the
+  // union builder
+  UnionArrayBuilder* new_union_arr_builder(MemoryPool* pool, DataTypeBox* type, ArrayBuilder* type_ids,
+                                           ArrayBuilder* value_offsets, ArrayBuilder* children[], int child_num) {
+    std::vector<ArrayBuilder*> builders(children, children + child_num);
+    return new UnionArrayBuilder(pool, type->sp, type_ids, value_offsets, builders);
+  }
+
+  void advance_union_arr_builder(UnionArrayBuilder* builder) {
+    builder->Advance();
+  }
+
+  ArrayBox* finish_union_arr_builder(UnionArrayBuilder* builder) {
+    ArrayBox* arr = new ArrayBox;
+    arr->sp = builder->Finish();
+    arr->p = arr->sp.get();
+    return arr;
+  }
+
+  void release_union_arr_builder(UnionArrayBuilder* builder) {
+    if (builder) {
+      delete builder;
+    }
+  }
+}
+
+#endif
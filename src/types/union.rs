@@ -0,0 +1,321 @@
+use array::*;
+use array_builder::{ArrayBuilder, RawArrayBuilderMutPtr, Value, make_builder, append_value};
+use buffer::{RawBufferPtr, Buf};
+use ty::{RawDataTypePtr, DataType, Field, Ty, UnionMode};
+use ty;
+use types::primitive::{I8Array, I32Array, I8ArrayBuilder, I32ArrayBuilder, PrimitiveArray};
+use common::memory_pool::{RawMemoryPoolMutPtr, MemoryPool, PoolRef};
+use common::status::ArrowError;
+
+use std::any::Any;
+use std::mem;
+use std::ptr;
+
+// A union array is composed of an int8 type id per slot, an int32 offset per slot for the dense
+// mode, and one child array per field of its type. The type id array also carries the validity
+// of slots.
+pub struct UnionArray {
+  raw_array: RawArrayPtr
+}
+
+impl UnionArray {
+  // Every child of a sparse union has the same length as the union itself
+  pub fn new_sparse(data_type: &DataType, type_ids: I8Array, children: Vec<BaseArray>) -> Result<UnionArray, ArrowError> {
+    if children.iter().any(|child| child.len() != type_ids.len()) {
+      return Err(ArrowError::invalid("children of a sparse union must have the same length as the union"));
+    }
+    UnionArray::new(data_type, UnionMode::SPARSE, type_ids, None, children)
+  }
+
+  // Each slot of a dense union points to value_offsets[i] of the child selected by type_ids[i]
+  pub fn new_dense(data_type: &DataType, type_ids: I8Array, value_offsets: I32Array, children: Vec<BaseArray>) -> Result<UnionArray, ArrowError> {
+    if value_offsets.len() != type_ids.len() {
+      return Err(ArrowError::invalid("a dense union must have one value offset per slot"));
+    }
+    UnionArray::new(data_type, UnionMode::DENSE, type_ids, Some(value_offsets), children)
+  }
+
+  fn new(data_type: &DataType, mode: UnionMode, type_ids: I8Array, value_offsets: Option<I32Array>,
+         children: Vec<BaseArray>) -> Result<UnionArray, ArrowError> {
+    match (data_type.ty(), mode) {
+      (Ty::SPARSE_UNION, UnionMode::SPARSE) | (Ty::DENSE_UNION, UnionMode::DENSE) => {},
+      _ => return Err(ArrowError::type_error(format!("{} is not a {:?} union type", data_type.to_string(), mode)))
+    }
+    if children.len() as i32 != data_type.num_children() {
+      return Err(ArrowError::invalid("the number of children does not match the number of union fields"));
+    }
+    for (i, child) in children.iter().enumerate() {
+      let field_type = data_type.child(i as i32).data_type();
+      if child.data_type() != field_type {
+        return Err(ArrowError::type_error(format!("union field {} expects {}, but the child holds {}", i,
+                                                  field_type.to_string(), child.data_type().to_string())));
+      }
+    }
+
+    for i in 0..type_ids.len() {
+      if type_ids.is_null(i) {
+        continue;
+      }
+      let type_id = type_ids.value(i);
+      if type_id < 0 || type_id as usize >= children.len() {
+        return Err(ArrowError::invalid("type id does not refer to any child array"));
+      }
+      if let Some(ref offsets) = value_offsets {
+        if offsets.is_null(i) || offsets.value(i) < 0 || offsets.value(i) >= children[type_id as usize].len() {
+          return Err(ArrowError::invalid("value offset is out of the bounds of the child array"));
+        }
+      }
+    }
+
+    let raw_children = children.iter().map(|child| child.raw_array()).collect::<Vec<RawArrayPtr>>();
+    let raw_value_offsets = match value_offsets {
+      Some(ref offsets) => offsets.raw_array(),
+      None => ptr::null()
+    };
+    Ok(UnionArray {
+      raw_array: unsafe {
+        new_union_arr(data_type.raw_data_type(), type_ids.raw_array(), raw_value_offsets, raw_children.as_ptr(),
+                      children.len() as i32)
+      }
+    })
+  }
+
+  pub fn mode(&self) -> UnionMode {
+    match self.ty() {
+      Ty::DENSE_UNION => UnionMode::DENSE,
+      _ => UnionMode::SPARSE
+    }
+  }
+
+  // Panics if i is out of bounds
+  pub fn type_id(&self, i: i32) -> i8 {
+    self.check_index(i);
+    unsafe { union_arr_type_id(self.raw_array, i) }
+  }
+
+  pub fn type_ids(&self) -> I8Array {
+    I8Array::from_raw( unsafe { union_arr_type_ids(self.raw_array) } )
+  }
+
+  // Position of the i-th value in the child array selected by type_id(i). Panics if i is out of
+  // bounds.
+  pub fn value_offset(&self, i: i32) -> i32 {
+    self.check_index(i);
+    unsafe { union_arr_value_offset(self.raw_array, i) }
+  }
+
+  // None for sparse unions
+  pub fn value_offsets(&self) -> Option<I32Array> {
+    let raw_offsets = unsafe { union_arr_value_offsets(self.raw_array) };
+    if raw_offsets.is_null() {
+      None
+    } else {
+      Some(I32Array::from_raw(raw_offsets))
+    }
+  }
+
+  pub fn num_children(&self) -> i32 {
+    unsafe { union_arr_num_children(self.raw_array) }
+  }
+
+  // Panics if type_id does not refer to any child
+  pub fn child(&self, type_id: i8) -> BaseArray {
+    self.check_type_id(type_id);
+    BaseArray::from_raw( unsafe { union_arr_child(self.raw_array, type_id as i32) } )
+  }
+
+  // Fails if T does not accept the type of the child. Panics if type_id does not refer to any child.
+  pub fn typed_child<T: Any + Array>(&self, type_id: i8) -> Result<T, ArrowError> {
+    self.check_type_id(type_id);
+    typed_array( unsafe { union_arr_child(self.raw_array, type_id as i32) } )
+  }
+
+  // Panics if type_id does not refer to any child
+  pub fn child_data_type(&self, type_id: i8) -> DataType {
+    self.check_type_id(type_id);
+    self.data_type().child(type_id as i32).data_type()
+  }
+
+  fn check_index(&self, i: i32) {
+    assert!(i >= 0 && i < self.len(), "index is out of the bounds of the union array");
+  }
+
+  fn check_type_id(&self, type_id: i8) {
+    assert!(type_id >= 0 && (type_id as i32) < self.num_children(), "type id does not refer to any child of the union");
+  }
+}
+
+impl_array!(UnionArray, ty::new_union_type(ptr::null(), 0, false), union_arr_data, Ty::DENSE_UNION, Ty::SPARSE_UNION);
+
+pub enum RawUnionArrayBuilder {}
+
+// Builds a union array of the given type. Each value is appended to the child builder of its type
+// id, and a sparse union also appends a null to every other child.
+pub struct UnionArrayBuilder {
+  raw_builder: *mut RawUnionArrayBuilder,
+  mode: UnionMode,
+  fields: Vec<Field>,
+  type_ids: I8ArrayBuilder,
+  value_offsets: I32ArrayBuilder,
+  children: Vec<Box<ArrayBuilder>>,
+  _pool: PoolRef
+}
+
+impl UnionArrayBuilder {
+  pub fn new(pool: &MemoryPool, data_type: &DataType) -> Result<UnionArrayBuilder, ArrowError> {
+    let mode = match data_type.ty() {
+      Ty::SPARSE_UNION => UnionMode::SPARSE,
+      Ty::DENSE_UNION => UnionMode::DENSE,
+      _ => return Err(ArrowError::type_error(format!("{} is not a union type", data_type.to_string())))
+    };
+
+    let fields = (0..data_type.num_children()).map(|i| data_type.child(i)).collect::<Vec<Field>>();
+    let mut children = Vec::with_capacity(fields.len());
+    for field in &fields {
+      children.push(try!(make_builder(&field.data_type(), pool)));
+    }
+
+    let type_ids = I8ArrayBuilder::new(pool, &DataType::new_primitive(Ty::INT8));
+    let value_offsets = I32ArrayBuilder::new(pool, &DataType::new_primitive(Ty::INT32));
+    let pool = PoolRef::new(pool);
+    let raw_value_offsets = match mode {
+      UnionMode::SPARSE => ptr::null_mut(),
+      UnionMode::DENSE => value_offsets.raw_builder()
+    };
+    let raw_children = children.iter().map(|child| child.raw_builder()).collect::<Vec<RawArrayBuilderMutPtr>>();
+    Ok(UnionArrayBuilder {
+      raw_builder: unsafe {
+        new_union_arr_builder(pool.raw_memory_pool(), data_type.raw_data_type(), type_ids.raw_builder(),
+                              raw_value_offsets, raw_children.as_ptr(), children.len() as i32)
+      },
+      mode: mode,
+      type_ids: type_ids,
+      value_offsets: value_offsets,
+      children: children,
+      fields: fields,
+      _pool: pool
+    })
+  }
+
+  pub fn len(&self) -> i32 {
+    self.type_ids.len()
+  }
+
+  // Appends the value to the child of type_id, e.g. Value::Str to a string child. Nothing is
+  // appended if the value does not match the type of the child.
+  pub fn append_value(&mut self, type_id: i8, value: &Value) -> Result<&mut UnionArrayBuilder, ArrowError> {
+    if type_id < 0 || type_id as usize >= self.children.len() {
+      return Err(ArrowError::invalid("type id does not refer to any child of the union"));
+    }
+    let type_id_idx = type_id as usize;
+
+    let offset = self.children[type_id_idx].len();
+    try!(append_value(&mut *self.children[type_id_idx], &self.fields[type_id_idx].data_type(), value));
+    if self.mode == UnionMode::SPARSE {
+      for (i, child) in self.children.iter_mut().enumerate() {
+        if i != type_id_idx {
+          try!(child.append_null());
+        }
+      }
+    }
+
+    if self.mode == UnionMode::DENSE {
+      try!(self.value_offsets.append_value(offset));
+    }
+    try!(self.type_ids.append_value(type_id));
+    unsafe { advance_union_arr_builder(self.raw_builder); }
+    Ok(self)
+  }
+
+  pub fn append_null(&mut self) -> Result<&mut UnionArrayBuilder, ArrowError> {
+    if self.mode == UnionMode::SPARSE {
+      for child in self.children.iter_mut() {
+        try!(child.append_null());
+      }
+    }
+    if self.mode == UnionMode::DENSE {
+      try!(self.value_offsets.append_null());
+    }
+    try!(self.type_ids.append_null());
+    unsafe { advance_union_arr_builder(self.raw_builder); }
+    Ok(self)
+  }
+
+  // Fails without finishing anything if a failed append left the children of a sparse union or
+  // the value offsets out of step with the type ids. A failed append to a dense union may leave an
+  // unreferenced value in a child, which does not change the union.
+  pub fn finish(&mut self) -> Result<UnionArray, ArrowError> {
+    let len = self.len();
+    if self.mode == UnionMode::SPARSE {
+      for (i, child) in self.children.iter().enumerate() {
+        if child.len() != len {
+          return Err(ArrowError::invalid(format!("union child {} has {} values but the union has {} slots", i,
+                                                 child.len(), len)));
+        }
+      }
+    }
+    if self.mode == UnionMode::DENSE && self.value_offsets.len() != len {
+      return Err(ArrowError::invalid(format!("the union has {} value offsets but {} slots",
+                                             self.value_offsets.len(), len)));
+    }
+
+    Ok(UnionArray {
+      raw_array: unsafe { finish_union_arr_builder(self.raw_builder) }
+    })
+  }
+
+  pub fn finish_as_base(&mut self) -> Result<BaseArray, ArrowError> {
+    let arr = try!(self.finish());
+    Ok(unsafe { mem::transmute(arr) })
+  }
+}
+
+impl ArrayBuilder for UnionArrayBuilder {
+  fn raw_builder(&self) -> RawArrayBuilderMutPtr {
+    self.raw_builder as RawArrayBuilderMutPtr
+  }
+
+  fn append_null(&mut self) -> Result<(), ArrowError> {
+    try!(UnionArrayBuilder::append_null(self));
+    Ok(())
+  }
+
+  fn as_any(&self) -> &Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut Any {
+    self
+  }
+
+  fn finish(&mut self) -> Result<BaseArray, ArrowError> {
+    self.finish_as_base()
+  }
+}
+
+impl Drop for UnionArrayBuilder {
+  fn drop(&mut self) {
+    // the type id, value offset and child builders are released after this, when the fields are
+    // dropped
+    unsafe { release_union_arr_builder(self.raw_builder); }
+  }
+}
+
+extern "C" {
+  pub fn new_union_arr(ty: RawDataTypePtr, type_ids: RawArrayPtr, value_offsets: RawArrayPtr, children: *const RawArrayPtr,
+                       child_num: i32) -> RawArrayPtr;
+  pub fn union_arr_type_ids(arr: RawArrayPtr) -> RawArrayPtr;
+  pub fn union_arr_value_offsets(arr: RawArrayPtr) -> RawArrayPtr;
+  pub fn union_arr_child(arr: RawArrayPtr, i: i32) -> RawArrayPtr;
+  pub fn union_arr_num_children(arr: RawArrayPtr) -> i32;
+  pub fn union_arr_type_id(arr: RawArrayPtr, i: i32) -> i8;
+  pub fn union_arr_value_offset(arr: RawArrayPtr, i: i32) -> i32;
+  pub fn union_arr_data(arr: RawArrayPtr) -> RawBufferPtr;
+
+  pub fn new_union_arr_builder(pool: RawMemoryPoolMutPtr, ty: RawDataTypePtr, type_ids: RawArrayBuilderMutPtr,
+                               value_offsets: RawArrayBuilderMutPtr, children: *const RawArrayBuilderMutPtr,
+                               child_num: i32) -> *mut RawUnionArrayBuilder;
+  pub fn advance_union_arr_builder(builder: *mut RawUnionArrayBuilder);
+  pub fn finish_union_arr_builder(builder: *mut RawUnionArrayBuilder) -> RawArrayPtr;
+  pub fn release_union_arr_builder(builder: *mut RawUnionArrayBuilder);
+}
\ No newline at end of file
@@ -25,6 +25,25 @@ mod tests {
   use arrow::types::primitive;
   use arrow::common::memory_pool;
   use arrow::common::status;
+  use arrow::array_builder;
+
+  // Unwraps the result, or fails the test with the action that failed and the error
+  fn expect<T>(result: Result<T, status::ArrowError>, action: &str) -> T {
+    match result {
+      Ok(value) => value,
+      Err(e) => panic!("Failed to {}: {}", action, e.message())
+    }
+  }
+
+  // Builds a row batch of the schema, in which row() appends the row of each item
+  fn build_batch<I, F>(schema: &ty::Schema, pool: &memory_pool::MemoryPool, items: I, mut row: F) -> table::RowBatch
+    where I: IntoIterator, F: FnMut(I::Item, &mut FnMut(&[array_builder::Value])) {
+    let mut builder = expect(table::RowBatchBuilder::new(schema, pool), "create RowBatchBuilder");
+    for item in items {
+      row(item, &mut |values| { expect(builder.append_row(values), "append a row"); });
+    }
+    expect(builder.finish(), "finish the row batch")
+  }
 
   #[test]
   fn test_raw_field() {
@@ -148,10 +167,10 @@ mod tests {
 
   #[test]
   fn test_buffer() {
-    use arrow::common::memory_pool::MemoryPool;
+    use arrow::common::memory_pool::DefaultMemoryPool;
     use arrow::buffer::{BufferBuilder, Buffer, Resizable, Mutable};
 
-    let pool = MemoryPool::default();
+    let pool = DefaultMemoryPool::default();
     let mut builder = BufferBuilder::new(&pool);
     let val: u8 = 10;
 
@@ -213,13 +232,13 @@ mod tests {
 
   #[test]
   fn test_array() {
-    use arrow::common::memory_pool::MemoryPool;
+    use arrow::common::memory_pool::DefaultMemoryPool;
     use arrow::ty::{DataTypeProvider, DataType};
     use arrow::types::primitive::{U8ArrayBuilder, PrimitiveArray};
     use arrow::array::Array;
 
     let ty_provider = DataTypeProvider::new();
-    let pool = MemoryPool::default();
+    let pool = DefaultMemoryPool::default();
 
     let mut builder = U8ArrayBuilder::new(&pool, ty_provider.u8());
     let values: Vec<u8> = (0..32).collect();
@@ -235,6 +254,819 @@ mod tests {
     assert_eq!(values.as_slice(), array.as_slice());
   }
 
+  #[test]
+  fn test_raw_bool_array() {
+    unsafe {
+      let pool = memory_pool::default_mem_pool();
+      let bool_ty = ty::new_primitive_type(ty::Ty::BOOL);
+      let builder = primitive::new_bool_arr_builder(pool, bool_ty);
+      let values: Vec<bool> = (0..32).map(|i| i % 3 == 0).collect();
+
+      let s = primitive::append_bool_arr_builder(builder, values.as_ptr(), 32, ptr::null());
+      assert!(status::ok(s));
+      status::release_status(s);
+
+      let arr = primitive::finish_bool_arr_builder(builder);
+      assert!(ty::data_type_equals(bool_ty, array::arr_type(arr)));
+      assert_eq!(32, array::arr_len(arr));
+
+      for i in 0..32 {
+        assert_eq!(i % 3 == 0, primitive::bool_arr_value(arr, i));
+      }
+
+      array::release_arr(arr);
+      primitive::release_bool_arr_builder(builder);
+      ty::release_data_type(bool_ty);
+    }
+  }
+
+  #[test]
+  fn test_bool_array() {
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::{DataTypeProvider, Field, Schema};
+    use arrow::types::primitive::{BooleanArray, BooleanArrayBuilder};
+    use arrow::array::Array;
+    use arrow::table::RowBatch;
+
+    let ty_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+
+    let mut builder = BooleanArrayBuilder::new(&pool, ty_provider.bool());
+    let values = [Some(true), None, Some(false), Some(true)];
+
+    let mut builder = match builder.append_nullable(&values) {
+      Ok(builder) => builder,
+      Err(e) => panic!("append failed: {}", e.message())
+    };
+    let array = builder.finish();
+    assert_eq!(ty_provider.bool(), &array.data_type());
+    assert_eq!(4, array.len());
+    assert_eq!(1, array.null_count());
+    assert!(array.value(0));
+    assert!(array.is_null(1));
+    assert!(!array.value(2));
+    assert!(array.value(3));
+
+    let f1 = Field::new(String::from("flag"), ty_provider.bool(), true);
+    let schema = Schema::new(&[&f1]);
+    let row_batch = RowBatch::new(&schema, 4, &[array]);
+    let col: BooleanArray = row_batch.column(0);
+    assert_eq!(4, col.len());
+    assert!(col.is_null(1));
+    assert!(col.value(3));
+  }
+
+  #[test]
+  fn test_bitmap() {
+    use arrow::bitmap::Bitmap;
+
+    let bitmap = Bitmap::from(&[true, false, true, true, false, false, false, false, true][..]);
+    assert_eq!(9, bitmap.len());
+    assert_eq!(&[0b00001101, 0b1], bitmap.as_bytes());
+    assert_eq!(4, bitmap.count_set_bits());
+    assert_eq!(5, bitmap.count_unset_bits());
+    assert!(bitmap.get(2));
+    assert!(!bitmap.get(4));
+    assert_eq!(vec![0, 2, 3, 8], bitmap.iter_set_bits().collect::<Vec<usize>>());
+    assert_eq!(vec![1, 0, 1, 1, 0, 0, 0, 0, 1], bitmap.to_valid_bytes());
+
+    let negated = !&bitmap;
+    assert_eq!(&[0b11110010, 0b0], negated.as_bytes());
+    assert_eq!(5, negated.count_set_bits());
+
+    let other = Bitmap::from_options(&[Some(1), Some(2), None, Some(4), None, None, None, None, None]);
+    assert_eq!(vec![0, 3], (&bitmap & &other).iter_set_bits().collect::<Vec<usize>>());
+    assert_eq!(vec![0, 1, 2, 3, 8], (&bitmap | &other).iter_set_bits().collect::<Vec<usize>>());
+
+    // slices are realigned to the first bit
+    let sliced = bitmap.slice(2, 7);
+    assert_eq!(vec![true, true, false, false, false, false, true], sliced.iter().collect::<Vec<bool>>());
+    assert_eq!(&[0b01000011], sliced.as_bytes());
+
+    let mut bitmap = Bitmap::new(0);
+    for i in 0..10 {
+      bitmap.push(i % 3 == 0);
+    }
+    assert_eq!(vec![0, 3, 6, 9], bitmap.iter_set_bits().collect::<Vec<usize>>());
+    bitmap.set(9, false);
+    assert_eq!(3, bitmap.count_set_bits());
+    assert_eq!(Bitmap::new_set(3), Bitmap::from_valid_bytes(&[1, 2, 3]));
+  }
+
+  #[test]
+  fn test_array_validity() {
+    use arrow::bitmap::Bitmap;
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::DataTypeProvider;
+    use arrow::types::primitive::{I32Array, I32ArrayBuilder};
+    use arrow::array::Array;
+
+    let ty_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+    let validity = Bitmap::from_bools(&[true, false, true]);
+
+    let mut builder = I32ArrayBuilder::new(&pool, ty_provider.i32());
+    assert!(builder.append_with_bitmap(&[1, 2], &validity).is_err());
+    builder.append_with_bitmap(&[1, 2, 3], &validity);
+    let array = builder.finish();
+    assert!(!array.is_null(0));
+    assert!(array.is_null(1));
+    let view = array.validity().unwrap();
+    assert!(view == validity);
+    assert_eq!(1, view.count_unset_bits());
+    assert_eq!(validity, view.to_bitmap());
+
+    assert!(I32Array::from(vec![1, 2]).validity().is_none());
+  }
+
+  #[test]
+  fn test_safe_builder_api() {
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::DataTypeProvider;
+    use arrow::types::primitive::{I64Array, I64ArrayBuilder, BooleanArrayBuilder, PrimitiveArray};
+    use arrow::array::Array;
+
+    let ty_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+
+    let mut builder = I64ArrayBuilder::new(&pool, ty_provider.i64());
+    builder.append_value(1);
+    builder.append_null();
+    builder.append_option(Some(3));
+    builder.append_option(None);
+    builder.append_slice_with_validity(&[5, 6], &[true, false]);
+    assert!(builder.append_slice_with_validity(&[7, 8], &[true]).is_err());
+    builder.extend(vec![Some(9), None, Some(11)]);
+    let array = builder.finish();
+
+    assert_eq!(9, array.len());
+    assert_eq!(4, array.null_count());
+    let nulls = (0..array.len()).filter(|i| array.is_null(*i)).collect::<Vec<i32>>();
+    assert_eq!(vec![1, 3, 5, 7], nulls);
+    assert_eq!(1, array.value(0));
+    assert_eq!(5, array.value(4));
+    assert_eq!(11, array.value(8));
+    assert!(array == I64Array::from(vec![Some(1), None, Some(3), None, Some(5), None, Some(9), None, Some(11)]));
+
+    let mut builder = BooleanArrayBuilder::new(&pool, ty_provider.bool());
+    builder.append_value(true);
+    builder.extend((0..3).map(|i| if i == 1 { None } else { Some(i == 2) }));
+    let array = builder.finish();
+    assert_eq!(4, array.len());
+    assert!(array.value(0));
+    assert!(!array.value(1));
+    assert!(array.is_null(2));
+    assert!(array.value(3));
+  }
+
+  #[test]
+  fn test_array_from_vec() {
+    use arrow::buffer::{Buf, Buffer};
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::native::buffer::ALIGNMENT;
+    use arrow::ty::DataTypeProvider;
+    use arrow::types::primitive::{I32Array, F64Array, BooleanArray, I32ArrayBuilder, PrimitiveArray};
+    use arrow::array::Array;
+
+    // aligned allocations are taken over, and the others copied into aligned buffers
+    let values: Vec<i64> = vec![1, 2, 3];
+    let data = values.as_ptr() as *const u8;
+    let buf = Buf::from(values);
+    assert_eq!(24, buf.size());
+    assert_eq!(0, buf.data() as usize % ALIGNMENT);
+    assert_eq!(data as usize % ALIGNMENT == 0, data == buf.data());
+    assert_eq!(&[1, 0, 0, 0, 0, 0, 0, 0], unsafe { slice::from_raw_parts(buf.data(), 8) });
+
+    let values = vec![1, 2, 3];
+    let data = values.as_ptr();
+    let array = I32Array::from(values);
+    assert_eq!(3, array.len());
+    assert_eq!(0, array.null_count());
+    assert_eq!(data as usize % ALIGNMENT == 0, data == array.raw_data());
+    assert_eq!(&[1, 2, 3], array.as_slice());
+
+    let ty_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+    let mut builder = I32ArrayBuilder::new(&pool, ty_provider.i32());
+    builder.append(&[1, 2, 3], ptr::null());
+    assert!(builder.finish() == array);
+
+    let array = F64Array::from(vec![Some(0.5), None, Some(1.5)]);
+    assert_eq!(3, array.len());
+    assert_eq!(1, array.null_count());
+    assert!(array.is_null(1));
+    assert_eq!(1.5, array.value(2));
+
+    let array = BooleanArray::from(vec![Some(true), None, Some(false)]);
+    assert_eq!(1, array.null_count());
+    assert!(array.value(0));
+    assert!(array.is_null(1));
+    assert!(!array.value(2));
+    assert!(BooleanArray::from(vec![true, true]).value(1));
+
+    // buffers must hold the values and the validity of every slot
+    let array = match I32Array::from_buffers(2, Buf::from(vec![4, 5]), 0, None) {
+      Ok(array) => array,
+      Err(e) => panic!("Failed to create an array: {}", e.message())
+    };
+    assert_eq!(&[4, 5], array.as_slice());
+    assert!(I32Array::from_buffers(3, Buf::from(vec![4, 5]), 0, None).is_err());
+    assert!(I32Array::from_buffers(2, Buf::from(vec![4, 5]), 3, Some(Buf::from(vec![1u8]))).is_err());
+    assert!(I32Array::from_buffers(2, Buf::from(vec![4, 5]), 1, None).is_err());
+    assert!(BooleanArray::from_buffers(9, Buf::from(vec![0xffu8]), 0, None).is_err());
+    assert!(BooleanArray::from_buffers(8, Buf::from(vec![0xffu8]), 0, None).is_ok());
+  }
+
+  #[test]
+  fn test_dynamic_builder() {
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::{DataTypeProvider, DataType, DecimalType, Field, TemporalType, TimeUnit, Ty, UnionMode};
+    use arrow::types::primitive::{I32Array, I32ArrayBuilder, F64ArrayBuilder, PrimitiveArray};
+    use arrow::types::list::ListArrayBuilder;
+    use arrow::types::structs::{StructArray, StructArrayBuilder};
+    use arrow::types::union::UnionArrayBuilder;
+    use arrow::array_builder::{ArrayBuilder, Value, make_builder, append_value};
+    use arrow::array::Array;
+
+    let ty_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+
+    let mut builder = match make_builder(ty_provider.f64(), &pool) {
+      Ok(builder) => builder,
+      Err(e) => panic!("make_builder failed: {}", e.message())
+    };
+    builder.as_any_mut().downcast_mut::<F64ArrayBuilder>().unwrap().append_value(0.5);
+    builder.append_null();
+    assert_eq!(2, builder.len());
+    assert!(builder.as_any().downcast_ref::<I32ArrayBuilder>().is_none());
+
+    let array = builder.finish().unwrap();
+    assert_eq!(Ty::DOUBLE as i32, array.ty() as i32);
+    assert_eq!(2, array.len());
+    assert!(array.is_null(1));
+    assert_eq!(0, builder.len());
+
+    // [[1, 2], null, [3]]
+    let list_type = DataType::new_list(DataType::new_primitive(Ty::INT32));
+    let mut builder = match make_builder(&list_type, &pool) {
+      Ok(builder) => builder,
+      Err(e) => panic!("make_builder failed: {}", e.message())
+    };
+    {
+      let list_builder = builder.as_any_mut().downcast_mut::<ListArrayBuilder<Box<ArrayBuilder>>>().unwrap();
+      list_builder.append(true);
+      list_builder.value_builder().as_any_mut().downcast_mut::<I32ArrayBuilder>().unwrap().append(&[1, 2], ptr::null());
+      list_builder.append_null();
+      list_builder.append(true);
+      list_builder.value_builder().as_any_mut().downcast_mut::<I32ArrayBuilder>().unwrap().append_value(3);
+    }
+    assert_eq!(3, builder.len());
+
+    let array = builder.finish().unwrap();
+    assert_eq!(list_type, array.data_type());
+    assert_eq!(1, array.null_count());
+
+    let mut expected = ListArrayBuilder::new(&pool, &list_type, I32ArrayBuilder::new(&pool, ty_provider.i32()));
+    expected.append(true);
+    expected.value_builder().append(&[1, 2], ptr::null());
+    expected.append_null();
+    expected.append(true);
+    expected.value_builder().append_value(3);
+    assert!(array == expected.finish_as_base());
+
+    // [{id: 1, tags: ["a"]}, null, {id: 2, tags: null}]
+    let f1 = Field::new(String::from("id"), ty_provider.i32(), false);
+    let f2 = Field::new(String::from("tags"), &DataType::new_list(DataType::new_string()), true);
+    let struct_type = DataType::new_struct(2, &[f1, f2]);
+    let mut builder = match make_builder(&struct_type, &pool) {
+      Ok(builder) => builder,
+      Err(e) => panic!("make_builder failed: {}", e.message())
+    };
+    assert!(append_value(&mut *builder, &struct_type, &Value::Struct(vec![Value::I32(1), Value::List(vec![Value::Str("a")])])).is_ok());
+    assert!(append_value(&mut *builder, &struct_type, &Value::Null).is_ok());
+    assert!(append_value(&mut *builder, &struct_type, &Value::Struct(vec![Value::I32(2), Value::Null])).is_ok());
+    assert!(append_value(&mut *builder, &struct_type, &Value::Struct(vec![Value::I32(3)])).is_err());
+    assert!(append_value(&mut *builder, &struct_type, &Value::Struct(vec![Value::Str("x"), Value::Null])).is_err());
+    assert_eq!(3, builder.len());
+
+    let array: StructArray = {
+      let struct_builder = builder.as_any_mut().downcast_mut::<StructArrayBuilder>().unwrap();
+      assert_eq!(3, struct_builder.typed_field_builder::<I32ArrayBuilder>(0).unwrap().len());
+      match struct_builder.finish() {
+        Ok(array) => array,
+        Err(e) => panic!("struct array creation failed: {}", e.message())
+      }
+    };
+    assert_eq!(struct_type, array.data_type());
+    assert_eq!(3, array.len());
+    assert!(array.is_null(1));
+    let ids: I32Array = array.typed_column(0).unwrap();
+    assert_eq!(1, ids.value(0));
+    assert!(ids.is_null(1));
+    assert_eq!(2, ids.value(2));
+    assert!(array.column(1).is_null(2));
+
+    // every field builder must hold one value per struct slot
+    builder.as_any_mut().downcast_mut::<StructArrayBuilder>().unwrap().append(true);
+    assert!(builder.as_any_mut().downcast_mut::<StructArrayBuilder>().unwrap().finish().is_err());
+    assert!(builder.finish().is_err());
+
+    // [-1.5, 7, null] of a dense union of int32 and decimal(5, 1)
+    let decimal_type = DecimalType::new(5, 1).unwrap().data_type();
+    let union_type = DataType::new_union(&[Field::new(String::from("i"), ty_provider.i32(), true),
+                                           Field::new(String::from("d"), &decimal_type, true)], UnionMode::DENSE);
+    let mut builder = match make_builder(&union_type, &pool) {
+      Ok(builder) => builder,
+      Err(e) => panic!("make_builder failed: {}", e.message())
+    };
+    assert!(append_value(&mut *builder, &union_type, &Value::Union(1, Box::new(Value::Decimal(-15)))).is_ok());
+    assert!(append_value(&mut *builder, &union_type, &Value::Union(0, Box::new(Value::I32(7)))).is_ok());
+    assert!(append_value(&mut *builder, &union_type, &Value::Null).is_ok());
+    assert!(append_value(&mut *builder, &union_type, &Value::Union(2, Box::new(Value::I32(7)))).is_err());
+    assert!(append_value(&mut *builder, &union_type, &Value::Union(0, Box::new(Value::Decimal(1)))).is_err());
+    assert!(append_value(&mut *builder, &union_type, &Value::Decimal(1)).is_err());
+    assert_eq!(3, builder.len());
+
+    let array = builder.finish().unwrap();
+    let mut expected = UnionArrayBuilder::new(&pool, &union_type).unwrap();
+    expected.append_value(1, &Value::Decimal(-15)).unwrap();
+    expected.append_value(0, &Value::I32(7)).unwrap();
+    expected.append_null().unwrap();
+    assert!(array == expected.finish_as_base().unwrap());
+
+    // temporal values are stored in the primitive builder of their storage type
+    let timestamp_type = TemporalType::timestamp(TimeUnit::MILLI, None).unwrap().data_type().unwrap();
+    let mut builder = match make_builder(&timestamp_type, &pool) {
+      Ok(builder) => builder,
+      Err(e) => panic!("make_builder failed: {}", e.message())
+    };
+    assert!(append_value(&mut *builder, &timestamp_type, &Value::Timestamp(1_000)).is_ok());
+    assert!(append_value(&mut *builder, &timestamp_type, &Value::Time(1_000)).is_err());
+    assert!(append_value(&mut *builder, &timestamp_type, &Value::Null).is_ok());
+    let array = builder.finish().unwrap();
+    assert_eq!(timestamp_type, array.data_type());
+    assert_eq!(2, array.len());
+    assert!(array.is_null(1));
+  }
+
+  #[test]
+  fn test_custom_mem_pools() {
+    use arrow::common::memory_pool::{MemoryPool, TrackingMemoryPool, BoundedMemoryPool};
+    use arrow::common::status::StatusCode;
+    use arrow::ty::DataTypeProvider;
+    use arrow::types::primitive::{I32ArrayBuilder, PrimitiveArray};
+    use arrow::array::Array;
+
+    let ty_provider = DataTypeProvider::new();
+    let values: Vec<i32> = (0..100).collect();
+
+    let pool = TrackingMemoryPool::new();
+    {
+      let mut builder = I32ArrayBuilder::new(&pool, ty_provider.i32());
+      builder.append(&values, ptr::null());
+      assert!(pool.len() >= 400);
+
+      let array = builder.finish();
+      assert_eq!(&values[..], array.as_slice());
+      assert!(pool.peak() >= pool.current());
+    }
+    assert_eq!(0, pool.len());
+
+    let pool = BoundedMemoryPool::new(64);
+    let mut builder = I32ArrayBuilder::new(&pool, ty_provider.i32());
+    match builder.append(&values, ptr::null()) {
+      Ok(_) => panic!("appending beyond the limit must fail"),
+      Err(e) => match *e.code() {
+        StatusCode::OutOfMemory => {},
+        _ => panic!("unexpected error: {}", e.message())
+      }
+    };
+    assert!(pool.len() <= pool.limit());
+
+    // builders and arrays keep their pool alive
+    let mut builder = {
+      let pool = TrackingMemoryPool::new();
+      I32ArrayBuilder::new(&pool, ty_provider.i32())
+    };
+    builder.append(&values, ptr::null()).unwrap();
+    let array = builder.finish();
+    drop(builder);
+    assert_eq!(&values[..], array.as_slice());
+  }
+
+  #[test]
+  fn test_binary_array() {
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::DataTypeProvider;
+    use arrow::types::string::BinaryArrayBuilder;
+    use arrow::array::Array;
+
+    let ty_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+
+    let mut builder = BinaryArrayBuilder::new(&pool, ty_provider.binary());
+    let values: [Option<&[u8]>; 3] = [Some(b"ab"), None, Some(b"cde")];
+
+    let mut builder = match builder.append_nullable(&values) {
+      Ok(builder) => builder,
+      Err(e) => panic!("append failed: {}", e.message())
+    };
+    let array = builder.finish();
+    assert_eq!(ty_provider.binary(), &array.data_type());
+    assert_eq!(3, array.len());
+    assert_eq!(1, array.null_count());
+    assert!(array.null_bitmap().is_some());
+
+    assert_eq!(b"ab", array.value(0));
+    assert!(array.is_null(1));
+    assert_eq!(b"cde", array.value(2));
+    assert_eq!(&[0, 2, 2, 5], array.value_offsets());
+    assert_eq!(2, array.value_offset(2));
+    assert_eq!(3, array.value_length(2));
+  }
+
+  #[test]
+  fn test_struct_array() {
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::{DataTypeProvider, DataType, Field};
+    use arrow::types::primitive::{I32Array, I32ArrayBuilder, F32ArrayBuilder, F64Array, PrimitiveArray};
+    use arrow::types::structs::StructArray;
+    use arrow::array::Array;
+    use arrow::bitmap::Bitmap;
+
+    let ty_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+    let f1 = Field::new(String::from("id"), ty_provider.i32(), false);
+    let f2 = Field::new(String::from("score"), ty_provider.f32(), true);
+    let struct_type = DataType::new_struct(2, &[f1, f2]);
+
+    let mut builder1 = I32ArrayBuilder::new(&pool, ty_provider.i32());
+    let mut builder2 = F32ArrayBuilder::new(&pool, ty_provider.f32());
+    builder1.append(&[1, 2, 3], ptr::null());
+    builder2.append(&[0.5, 1.5, 2.5], ptr::null());
+    let arrays = [builder1.finish_as_base(), builder2.finish_as_base()];
+
+    let validity = Bitmap::from_bools(&[true, false, true]);
+    let array = match StructArray::new(&pool, &struct_type, &arrays, Some(&validity)) {
+      Ok(array) => array,
+      Err(e) => panic!("struct array creation failed: {}", e.message())
+    };
+    assert_eq!(struct_type, array.data_type());
+    assert_eq!(3, array.len());
+    assert_eq!(1, array.null_count());
+    assert!(array.is_null(1));
+    assert_eq!(2, array.num_columns());
+    assert_eq!(arrays[1], array.column(1));
+
+    let ids: I32Array = array.typed_column(0).unwrap();
+    assert!(array.typed_column::<F64Array>(0).is_err());
+    assert_eq!(&[1, 2, 3], ids.as_slice());
+    assert!(array.column_by_name("score").unwrap() == arrays[1]);
+    assert!(array.column_by_name("missing").is_none());
+
+    builder1.append(&[4], ptr::null());
+    builder2.append(&[3.5, 4.5], ptr::null());
+    let arrays = [builder1.finish_as_base(), builder2.finish_as_base()];
+    assert!(StructArray::new(&pool, &struct_type, &arrays, None).is_err());
+
+    builder1.append(&[4], ptr::null());
+    builder2.append(&[4.5], ptr::null());
+    let arrays = [builder1.finish_as_base(), builder2.finish_as_base()];
+    assert!(StructArray::new(&pool, &struct_type, &arrays, Some(&Bitmap::new_set(2))).is_err());
+    assert!(StructArray::new(&pool, &struct_type, &arrays[..1], None).is_err());
+    assert!(StructArray::new(&pool, &ty_provider.i32(), &arrays, None).is_err());
+
+    builder1.append(&[5], ptr::null());
+    builder2.append(&[5.5], ptr::null());
+    let swapped = [builder2.finish_as_base(), builder1.finish_as_base()];
+    assert!(StructArray::new(&pool, &struct_type, &swapped, None).is_err());
+  }
+
+  #[test]
+  fn test_union_array() {
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::{DataTypeProvider, DataType, Field, Ty, UnionMode};
+    use arrow::types::primitive::{I8ArrayBuilder, I32Array, I32ArrayBuilder, PrimitiveArray};
+    use arrow::types::string::StringArray;
+    use arrow::types::union::{UnionArray, UnionArrayBuilder};
+    use arrow::array_builder::Value;
+    use arrow::array::{Array, BaseArray};
+
+    let ty_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+    let fields = [Field::new(String::from("i"), ty_provider.i32(), true),
+                  Field::new(String::from("s"), ty_provider.string(), true)];
+
+    // dense: [1, "a", null, 2, "b"]
+    let dense_type = DataType::new_union(&fields, UnionMode::DENSE);
+    let mut builder = match UnionArrayBuilder::new(&pool, &dense_type) {
+      Ok(builder) => builder,
+      Err(e) => panic!("union builder creation failed: {}", e.message())
+    };
+    builder.append_value(0, &Value::I32(1));
+    builder.append_value(1, &Value::Str("a"));
+    builder.append_null();
+    builder.append_value(0, &Value::I32(2));
+    builder.append_value(1, &Value::Str("b"));
+    assert!(builder.append_value(0, &Value::Str("c")).is_err());
+    assert!(builder.append_value(2, &Value::I32(3)).is_err());
+    assert_eq!(5, builder.len());
+
+    let array = match builder.finish() {
+      Ok(array) => array,
+      Err(e) => panic!("union array creation failed: {}", e.message())
+    };
+    assert_eq!(dense_type, array.data_type());
+    assert_eq!(Ty::DENSE_UNION as i32, array.ty() as i32);
+    assert_eq!(UnionMode::DENSE, array.mode());
+    assert_eq!(5, array.len());
+    assert_eq!(1, array.null_count());
+    assert!(array.is_null(2));
+    assert_eq!(1, array.type_id(1));
+    assert_eq!(1, array.value_offset(3));
+    assert_eq!(2, array.num_children());
+    assert_eq!(ty_provider.string(), &array.child_data_type(1));
+
+    let strs: StringArray = array.typed_child(1).unwrap();
+    assert_eq!("b", strs.value(array.value_offset(4)));
+    assert!(array.typed_child::<StringArray>(0).is_err());
+    let ints: I32Array = array.typed_child(0).unwrap();
+    assert_eq!(2, ints.value(array.value_offset(3)));
+
+    // sparse: [1, "x"], every child has a slot for every union slot
+    let sparse_type = DataType::new_union(&fields, UnionMode::SPARSE);
+    let mut builder = match UnionArrayBuilder::new(&pool, &sparse_type) {
+      Ok(builder) => builder,
+      Err(e) => panic!("union builder creation failed: {}", e.message())
+    };
+    builder.append_value(0, &Value::I32(1));
+    builder.append_value(1, &Value::Str("x"));
+
+    let array = match builder.finish() {
+      Ok(array) => array,
+      Err(e) => panic!("union array creation failed: {}", e.message())
+    };
+    assert_eq!(UnionMode::SPARSE, array.mode());
+    assert_eq!(1, array.value_offset(1));
+    assert!(array.value_offsets().is_none());
+    assert_eq!(2, array.child(0).len());
+    assert!(array.child(0).is_null(1));
+
+    let mut expected = UnionArrayBuilder::new(&pool, &sparse_type).unwrap();
+    expected.append_value(0, &Value::I32(1));
+    expected.append_value(1, &Value::Str("x"));
+    assert!(array == expected.finish().unwrap());
+
+    // children and type ids are checked against the union type
+    let mut type_ids = I8ArrayBuilder::new(&pool, ty_provider.i8());
+    type_ids.append(&[0, 0], ptr::null());
+    let type_ids = type_ids.finish();
+    let mut ints = I32ArrayBuilder::new(&pool, ty_provider.i32());
+    ints.append(&[1, 2], ptr::null());
+    let children: Vec<BaseArray> = vec![ints.finish_as_base()];
+    assert!(UnionArray::new_sparse(&sparse_type, type_ids, children).is_err());
+    assert!(UnionArrayBuilder::new(&pool, ty_provider.i32()).is_err());
+  }
+
+  #[test]
+  #[should_panic]
+  fn test_union_child_out_of_bounds() {
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::{DataTypeProvider, DataType, Field, UnionMode};
+    use arrow::types::union::UnionArrayBuilder;
+
+    let ty_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+    let union_type = DataType::new_union(&[Field::new(String::from("i"), ty_provider.i32(), true)], UnionMode::SPARSE);
+    let mut builder = UnionArrayBuilder::new(&pool, &union_type).unwrap();
+    builder.finish().unwrap().child(1);
+  }
+
+  #[test]
+  fn test_dictionary_array() {
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::DataTypeProvider;
+    use arrow::types::primitive::{I8ArrayBuilder, I32Array, I32ArrayBuilder, PrimitiveArray};
+    use arrow::types::string::{BinaryArray, StringArray, StringArrayBuilder};
+    use arrow::types::dictionary::DictionaryBuilder;
+    use arrow::array::Array;
+
+    let ty_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+
+    let keys = I8ArrayBuilder::new(&pool, ty_provider.i8());
+    let mut builder: DictionaryBuilder<I8ArrayBuilder, str> = DictionaryBuilder::new(&pool, keys);
+    for value in &["kr", "us", "kr", "jp", "us"] {
+      builder.append(value);
+    }
+    builder.append_null();
+    assert_eq!(3, builder.dictionary_len());
+
+    let array = match builder.finish() {
+      Ok(array) => array,
+      Err(e) => panic!("dictionary array creation failed: {}", e.message())
+    };
+    assert_eq!(6, array.len());
+    assert_eq!(1, array.null_count());
+    assert_eq!(&[0, 1, 0, 2, 1], &array.keys().as_slice()[0..5]);
+    assert_eq!(ty_provider.string(), &array.value_type());
+
+    let values: StringArray = array.typed_values().unwrap();
+    assert!(array.typed_values::<I32Array>().is_err());
+    assert_eq!(3, values.len());
+    assert_eq!("jp", values.value(array.key(3) as i32));
+
+    let decoded = match array.decode(&pool) {
+      Ok(decoded) => decoded,
+      Err(e) => panic!("decoding failed: {}", e.message())
+    };
+    let mut expected = StringArrayBuilder::new(&pool, ty_provider.string());
+    expected.append_nullable(&[Some("kr"), Some("us"), Some("kr"), Some("jp"), Some("us"), None]);
+    assert_eq!(expected.finish_as_base(), decoded);
+
+    let keys = I32ArrayBuilder::new(&pool, ty_provider.i32());
+    let mut builder: DictionaryBuilder<I32ArrayBuilder, f64> = DictionaryBuilder::new(&pool, keys);
+    for value in &[1.5, 2.5, 1.5] {
+      builder.append(value);
+    }
+    let array = match builder.finish() {
+      Ok(array) => array,
+      Err(e) => panic!("dictionary array creation failed: {}", e.message())
+    };
+    assert_eq!(&[0, 1, 0], array.keys().as_slice());
+    assert_eq!(2, array.values().len());
+
+    let keys = I8ArrayBuilder::new(&pool, ty_provider.i8());
+    let mut builder: DictionaryBuilder<I8ArrayBuilder, [u8]> = DictionaryBuilder::new(&pool, keys);
+    for value in &[&b"\x00\xff"[..], b"", b"\x00\xff"] {
+      builder.append(value);
+    }
+    let array = match builder.finish() {
+      Ok(array) => array,
+      Err(e) => panic!("dictionary array creation failed: {}", e.message())
+    };
+    assert_eq!(&[0, 1, 0], array.keys().as_slice());
+    assert_eq!(ty_provider.binary(), &array.value_type());
+    let values: BinaryArray = array.typed_values().unwrap();
+    assert_eq!(b"\x00\xff", values.value(0));
+
+    // i8 keys index at most 128 distinct values
+    let keys = I8ArrayBuilder::new(&pool, ty_provider.i8());
+    let mut builder: DictionaryBuilder<I8ArrayBuilder, i32> = DictionaryBuilder::new(&pool, keys);
+    for value in 0..128 {
+      assert!(builder.append(&value).is_ok());
+    }
+    assert!(builder.append(&128).is_err());
+    assert!(builder.append(&127).is_ok());
+    assert_eq!(128, builder.dictionary_len());
+  }
+
+  #[test]
+  fn test_temporal_arrays() {
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::{DataTypeProvider, TemporalType, TimeUnit, Ty};
+    use arrow::types::primitive::{I32ArrayBuilder, I64ArrayBuilder, PrimitiveArray};
+    use arrow::types::temporal::{DateArray, TimestampArray, TimeArray};
+    use arrow::array::Array;
+    use std::i64;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let ty_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+
+    let mut builder = I32ArrayBuilder::new(&pool, ty_provider.i32());
+    builder.append(&[1, -1, 0], [1, 1, 0].as_ptr());
+    let dates = DateArray::new(builder.finish());
+    assert_eq!(TemporalType::Date, dates.temporal_type().unwrap());
+    assert_eq!(TemporalType::Date.data_type().unwrap(), dates.data_type());
+    assert_eq!(3, dates.len());
+    assert!(dates.is_null(2));
+    assert_eq!(Some(UNIX_EPOCH + Duration::from_secs(86_400)), dates.value_as_system_time(0));
+    assert_eq!(Some(UNIX_EPOCH - Duration::from_secs(86_400)), dates.value_as_system_time(1));
+    assert_eq!(ty_provider.i32(), &dates.values().data_type());
+    assert_eq!(&[1, -1], &dates.values().as_slice()[0..2]);
+
+    let mut builder = I64ArrayBuilder::new(&pool, ty_provider.i64());
+    builder.append(&[1_500, -1_500, i64::MIN, i64::MAX], ptr::null());
+    let timestamps = match TimestampArray::new(builder.finish(), TimeUnit::MILLI, Some(String::from("Asia/Seoul"))) {
+      Ok(timestamps) => timestamps,
+      Err(e) => panic!("timestamp array creation failed: {}", e.message())
+    };
+    match timestamps.ty() {
+      Ty::TIMESTAMP => {},
+      _ => panic!("unexpected type")
+    }
+    assert_eq!(TimeUnit::MILLI, timestamps.unit().unwrap());
+    assert_eq!(Some(String::from("Asia/Seoul")), timestamps.timezone().unwrap());
+    assert_eq!("timestamp[ms, tz=Asia/Seoul]", timestamps.data_type().to_string());
+    assert_eq!(timestamps.temporal_type().unwrap().to_string(), timestamps.data_type().to_string());
+    assert_eq!(-1_500, timestamps.value(1));
+    assert_eq!(Some(UNIX_EPOCH + Duration::from_millis(1_500)), timestamps.value_as_system_time(0));
+    assert_eq!(Some(UNIX_EPOCH - Duration::from_millis(1_500)), timestamps.value_as_system_time(1));
+    // extreme values do not panic
+    timestamps.value_as_system_time(2);
+    timestamps.value_as_system_time(3);
+    let seconds = TemporalType::Timestamp { unit: TimeUnit::SECOND, timezone: Some(String::from("Asia/Seoul")) };
+    assert!(seconds.data_type().unwrap() != timestamps.data_type());
+
+    // timezones cannot hold NUL characters
+    assert!(TemporalType::timestamp(TimeUnit::SECOND, Some(String::from("Asia\0Seoul"))).is_err());
+    assert!(TemporalType::Timestamp { unit: TimeUnit::SECOND, timezone: Some(String::from("\0")) }.data_type().is_err());
+    let mut builder = I64ArrayBuilder::new(&pool, ty_provider.i64());
+    builder.append(&[1], ptr::null());
+    assert!(TimestampArray::new(builder.finish(), TimeUnit::SECOND, Some(String::from("\0"))).is_err());
+
+    let mut builder = I64ArrayBuilder::new(&pool, ty_provider.i64());
+    builder.append(&[3_600_000_001, -1], ptr::null());
+    let times = TimeArray::new(builder.finish(), TimeUnit::MICRO);
+    assert_eq!(TemporalType::Time { unit: TimeUnit::MICRO }, times.temporal_type().unwrap());
+    assert_eq!(Some(Duration::new(3_600, 1_000)), times.value_as_duration(0));
+    assert_eq!(None, times.value_as_duration(1));
+
+    // arrays taken as the wrong type report it instead of panicking
+    let not_times = TimeArray::from_raw( unsafe { arrow::array::arr_share(dates.values().raw_array()) } );
+    assert!(not_times.temporal_type().is_err());
+    assert!(not_times.unit().is_err());
+    assert_eq!(None, not_times.value_as_duration(0));
+  }
+
+  #[test]
+  fn test_decimal_array() {
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::{DataType, DecimalType, Ty};
+    use arrow::types::decimal::DecimalArrayBuilder;
+    use arrow::array::Array;
+
+    let pool = DefaultMemoryPool::default();
+    assert!(DecimalType::new(39, 2).is_err());
+    assert!(DecimalType::new(5, 6).is_err());
+
+    let data_type = match DecimalType::new(7, 2) {
+      Ok(data_type) => data_type,
+      Err(e) => panic!("invalid decimal type: {}", e.message())
+    };
+    assert_eq!("decimal(7, 2)", data_type.to_string());
+
+    let mut builder = DecimalArrayBuilder::new(&pool, data_type);
+    builder.append_value(1234567).unwrap();
+    builder.append_str("-0.5").unwrap();
+    builder.append_null().unwrap();
+    builder.append_str("42").unwrap();
+    assert!(builder.append_value(10_000_000).is_err());
+    assert!(builder.append_str("1.234").is_err());
+    assert!(builder.append_str("123456.7").is_err());
+    assert!(builder.append_str("1.x").is_err());
+
+    let array = builder.finish();
+    assert_eq!(data_type, array.decimal_type().unwrap());
+    assert!(data_type.data_type() == array.data_type());
+    assert!(DecimalType::new(7, 3).unwrap().data_type() != array.data_type());
+    assert_eq!("decimal(7, 2)", array.data_type().to_string());
+    assert_eq!(4, array.len());
+    assert_eq!(1, array.null_count());
+    assert!(array.is_null(2));
+    assert!(!array.is_null(3));
+    assert_eq!(-50, array.value(1));
+    assert_eq!("12345.67", array.value_as_string(0).unwrap());
+    assert_eq!("-0.50", array.value_as_string(1).unwrap());
+    assert_eq!("42.00", array.value_as_string(3).unwrap());
+
+    // decimal arrays are regular arrays, e.g. for row batches
+    let base = builder.append_value(5).unwrap().finish_as_base();
+    assert!(match base.ty() { Ty::DECIMAL => true, _ => false });
+    assert_eq!(1, base.len());
+    assert!(DecimalType::new(1, 0).unwrap().data_type().decimal_type().is_some());
+    assert!(DataType::new_primitive(Ty::INT32).decimal_type().is_none());
+  }
+
+  #[test]
+  #[should_panic]
+  fn test_is_null_out_of_bounds() {
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::DataTypeProvider;
+    use arrow::types::primitive::I32ArrayBuilder;
+    use arrow::array::Array;
+
+    let ty_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+    let mut builder = I32ArrayBuilder::new(&pool, ty_provider.i32());
+    builder.append(&[1, 2], ptr::null());
+    builder.finish().is_null(2);
+  }
+
+  #[test]
+  #[should_panic]
+  fn test_decimal_value_out_of_bounds() {
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::DecimalType;
+    use arrow::types::decimal::DecimalArrayBuilder;
+
+    let pool = DefaultMemoryPool::default();
+    let mut builder = DecimalArrayBuilder::new(&pool, DecimalType::new(7, 2).unwrap());
+    builder.append_value(1).unwrap();
+    builder.finish().value(1);
+  }
+
   #[test]
   fn test_raw_column() {
 
@@ -270,14 +1102,14 @@ mod tests {
 
   #[test]
   fn test_column() {
-    use arrow::common::memory_pool::MemoryPool;
+    use arrow::common::memory_pool::DefaultMemoryPool;
     use arrow::ty::{DataTypeProvider, DataType, Field};
     use arrow::types::primitive::{F32ArrayBuilder, PrimitiveArray};
     use arrow::array::Array;
     use arrow::column::Column;
 
     let type_provider = DataTypeProvider::new();
-    let pool = MemoryPool::default();
+    let pool = DefaultMemoryPool::default();
     let f1 = Field::new(String::from("f1"), type_provider.f32(), false);
     let values: Vec<f32> = (0..32).map(|i| i as f32).collect();
     let mut builder = F32ArrayBuilder::new(&pool, type_provider.f32());
@@ -328,14 +1160,14 @@ mod tests {
 
   #[test]
   fn test_row_batch() {
-    use arrow::common::memory_pool::MemoryPool;
+    use arrow::common::memory_pool::DefaultMemoryPool;
     use arrow::ty::{DataTypeProvider, DataType, Field, Schema};
     use arrow::types::primitive::{F32ArrayBuilder, I32ArrayBuilder, PrimitiveArray};
     use arrow::array::Array;
     use arrow::table::RowBatch;
 
     let type_provider = DataTypeProvider::new();
-    let pool = MemoryPool::default();
+    let pool = DefaultMemoryPool::default();
     let f1 = Field::new(String::from("f1"), type_provider.i32(), false);
     let f2 = Field::new(String::from("f2"), type_provider.f32(), true);
     let schema = Schema::new(&[&f1, &f2]);
@@ -357,6 +1189,72 @@ mod tests {
     assert_eq!(2, row_batch.column_num());
   }
 
+  #[test]
+  fn test_row_batch_builder() {
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::{DataTypeProvider, DataType, Field, Schema, Ty};
+    use arrow::types::primitive::{I32Array, I32ArrayBuilder, F64ArrayBuilder, PrimitiveArray};
+    use arrow::types::string::{StringArray, StringArrayBuilder};
+    use arrow::types::list::ListArray;
+    use arrow::array_builder::Value;
+    use arrow::array::Array;
+    use arrow::table::RowBatchBuilder;
+
+    let type_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+    let list_type = DataType::new_list(DataType::new_primitive(Ty::INT32));
+    let f1 = Field::new(String::from("id"), type_provider.i32(), false);
+    let f2 = Field::new(String::from("name"), type_provider.string(), true);
+    let f3 = Field::new(String::from("scores"), &list_type, true);
+    let schema = Schema::new(&[&f1, &f2, &f3]);
+
+    let mut builder = match RowBatchBuilder::new(&schema, &pool) {
+      Ok(builder) => builder,
+      Err(e) => panic!("Failed to create RowBatchBuilder: {}", e.message())
+    };
+    assert!(&schema == builder.schema());
+    assert_eq!(3, builder.column_num());
+
+    builder.append_row(&[Value::I32(1), Value::Str("a"), Value::List(vec![Value::I32(10), Value::I32(20)])]);
+    builder.append_row(&[Value::I32(2), Value::Null, Value::Null]);
+    builder.append_row(&[Value::I32(3), Value::Str("c"), Value::List(vec![])]);
+    assert_eq!(3, builder.row_num());
+
+    // rejected rows leave every column untouched
+    assert!(builder.append_row(&[Value::I32(4), Value::Str("d")]).is_err());
+    assert!(builder.append_row(&[Value::Null, Value::Str("d"), Value::Null]).is_err());
+    assert!(builder.append_row(&[Value::I32(4), Value::Str("d"), Value::List(vec![Value::F64(0.5)])]).is_err());
+    assert_eq!(3, builder.row_num());
+
+    assert!(builder.typed_column_builder::<F64ArrayBuilder>(0).is_none());
+    builder.typed_column_builder::<I32ArrayBuilder>(0).unwrap().append_value(4);
+    assert!(builder.finish().is_err());
+    builder.column_builder(1).append_null();
+    builder.column_builder(2).append_null();
+
+    let row_batch = match builder.finish() {
+      Ok(row_batch) => row_batch,
+      Err(e) => panic!("Failed to finish the row batch: {}", e.message())
+    };
+    assert_eq!(schema, row_batch.schema());
+    assert_eq!(4, row_batch.row_num());
+    assert_eq!(0, builder.row_num());
+
+    let ids: I32Array = row_batch.column(0);
+    assert_eq!(&[1, 2, 3, 4], ids.as_slice());
+
+    let names: StringArray = row_batch.column(1);
+    let mut expected = StringArrayBuilder::new(&pool, type_provider.string());
+    expected.append_nullable(&[Some("a"), None, Some("c"), None]);
+    assert!(names == expected.finish());
+
+    let scores: ListArray = row_batch.column(2);
+    assert_eq!(2, scores.null_count());
+    assert_eq!(&[0, 2, 2, 2, 2], scores.value_offsets());
+    let values: I32Array = scores.typed_values().unwrap();
+    assert_eq!(&[10, 20], values.as_slice());
+  }
+
   #[test]
   fn test_raw_table() {
     unsafe {
@@ -393,7 +1291,7 @@ mod tests {
 
   #[test]
   fn test_table() {
-    use arrow::common::memory_pool::MemoryPool;
+    use arrow::common::memory_pool::DefaultMemoryPool;
     use arrow::ty::{DataTypeProvider, DataType, Field, Schema};
     use arrow::types::primitive::{F32ArrayBuilder, I32ArrayBuilder, PrimitiveArray};
     use arrow::array::Array;
@@ -401,7 +1299,7 @@ mod tests {
     use arrow::column::Column;
 
     let type_provider = DataTypeProvider::new();
-    let pool = MemoryPool::default();
+    let pool = DefaultMemoryPool::default();
     let f1 = Field::new(String::from("f1"), type_provider.i32(), false);
     let f2 = Field::new(String::from("f2"), type_provider.f32(), true);
     let schema = Schema::new(&[&f1, &f2]);
@@ -434,8 +1332,11 @@ mod tests {
     f.sync_all().unwrap();
 
     unsafe {
+      let mut s = ptr::null();
       let src = memory::open_mmap_src(CString::new(file_name).unwrap().as_ptr(),
-                                      memory::AccessMode::READWRITE);
+                                      memory::AccessMode::READWRITE, &mut s);
+      assert!(status::ok(s));
+      status::release_status(s);
       let values: Vec<u8> = (0..32).collect();
       let origin = values.clone();
       let s = memory::write_mmap_src(src, 0, values.as_ptr(), 32);
@@ -447,8 +1348,11 @@ mod tests {
       status::release_status(s);
       memory::release_mmap_src(src);
 
+      let mut s = ptr::null();
       let src = memory::open_mmap_src(CString::new(file_name).unwrap().as_ptr(),
-                                      memory::AccessMode::READ);
+                                      memory::AccessMode::READ, &mut s);
+      assert!(status::ok(s));
+      status::release_status(s);
       let buf = memory::read_at_mmap_src(src, 0, 32);
       let v = slice::from_raw_parts(buffer::buf_data(buf), 32);
       assert_eq!(&origin, &v);
@@ -476,7 +1380,7 @@ mod tests {
     let values: Vec<u8> = (0..32).collect();
     let origin = values.clone();
 
-    let src = MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READWRITE);
+    let src = expect(MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READWRITE), "open the file");
     let src = match src.write(0, values.as_ptr(), 32) {
       Ok(src) => src,
       Err(e) => panic!("write failed: {}", e.message())
@@ -486,7 +1390,7 @@ mod tests {
       Err(e) => panic!("close failed: {}", e.message())
     };
 
-    let src = MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READ);
+    let src = expect(MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READ), "open the file");
     let buf = src.read(0, 32);
     let s = unsafe { slice::from_raw_parts(buf.data(), 32) };
     assert_eq!(&origin, &s);
@@ -499,6 +1403,491 @@ mod tests {
     fs::remove_file(file_name).unwrap();
   }
 
+  #[test]
+  fn test_schema_metadata() {
+    use arrow::io::memory::MemoryMappedSource;
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::{DataTypeProvider, DataType, Field, Schema};
+    use arrow::types::primitive::I32ArrayBuilder;
+    use arrow::table::RowBatch;
+    use arrow::ipc::metadata;
+
+    let type_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+
+    // a schema with nested fields of various nullability
+    let a = Field::new(String::from("a"), type_provider.string(), true);
+    let b = Field::new(String::from("b"), type_provider.f64(), false);
+    let item = Field::new(String::from("item"), &DataType::new_struct(2, &[a, b]), false);
+    let f1 = Field::new(String::from("f1"), type_provider.i32(), false);
+    let f2 = Field::new(String::from("f2"), &DataType::new_list_with_field(&item), true);
+    let f3 = Field::new(String::from("f3"), type_provider.u8(), true);
+    let schema = Schema::new(&[&f1, &f2, &f3]);
+
+    let message = expect(metadata::schema_to_message(&schema), "serialize the schema");
+    assert_eq!(0, message.len() % 8);
+    let read_schema = expect(metadata::schema_from_message(&message), "read the schema");
+    assert!(schema == read_schema);
+    assert!(!read_schema.field(0).nullable());
+    assert!(read_schema.field(1).nullable());
+    let read_item = read_schema.field(1).data_type().child(0);
+    assert_eq!("item", read_item.name());
+    assert!(!read_item.nullable());
+    assert!(read_item.data_type().child(0).nullable());
+    assert!(!read_item.data_type().child(1).nullable());
+
+    assert!(metadata::schema_from_message(&message[..message.len() / 2]).is_err());
+
+    // write the schema before a row batch, and read the batch with the schema from the file
+    let values: Vec<i32> = (0..10).collect();
+    let mut builder = I32ArrayBuilder::new(&pool, type_provider.i32());
+    builder.append(&values, ptr::null()).unwrap();
+    let simple_schema = Schema::new(&[&f1]);
+    let arrays = [builder.finish_as_base()];
+    let row_batch = RowBatch::new(&simple_schema, 10, &arrays);
+
+    let schema_size = adapter::get_schema_size(&simple_schema).unwrap();
+    let file_name = "test_schema_metadata.dat";
+    let f = File::create(file_name).unwrap();
+    f.set_len((schema_size + row_batch.size()) as u64).unwrap();
+    f.sync_all().unwrap();
+
+    let src = expect(MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READWRITE), "open the file");
+    match adapter::write_schema(&src, &simple_schema, 0) {
+      Ok(size) => assert_eq!(schema_size, size),
+      Err(e) => panic!("Failed to write the schema: {}", e.message())
+    }
+    let header_pos = adapter::write_row_batch(&src, &row_batch, schema_size);
+    src.close().unwrap();
+
+    let src = expect(MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READ), "open the file");
+    let read_schema = expect(adapter::read_schema(&src, 0), "read the schema");
+    assert!(simple_schema == read_schema);
+    assert!(adapter::read_schema(&src, schema_size).is_err());
+
+    let batch_reader = expect(adapter::RowBatchReader::open(&src, header_pos), "open RowBatchReader");
+    let read_batch = expect(batch_reader.read(&read_schema), "read the row batch");
+    assert_eq!(arrays[0], read_batch.column(0));
+    src.close().unwrap();
+
+    fs::remove_file(file_name).unwrap();
+  }
+
+  #[test]
+  fn test_stream_format() {
+    use std::io::Cursor;
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::{DataTypeProvider, DataType, Field, Schema, Ty};
+    use arrow::array_builder::Value;
+    use arrow::array::BaseArray;
+    use arrow::table::{RowBatch, RowBatchBuilder};
+    use arrow::ipc::writer::StreamWriter;
+    use arrow::ipc::reader::StreamReader;
+
+    let type_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+    let list_type = DataType::new_list(DataType::new_primitive(Ty::DOUBLE));
+    let f1 = Field::new(String::from("id"), type_provider.i64(), false);
+    let f2 = Field::new(String::from("name"), type_provider.string(), true);
+    let f3 = Field::new(String::from("scores"), &list_type, true);
+    let f4 = Field::new(String::from("flag"), type_provider.bool(), true);
+    let f5 = Field::new(String::from("raw"), type_provider.binary(), false);
+    let schema = Schema::new(&[&f1, &f2, &f3, &f4, &f5]);
+
+    let build = |start: i64, rows: i64| -> RowBatch {
+      build_batch(&schema, &pool, start..start + rows, |i, append_row| {
+        let name = format!("name{}", i);
+        let scores = (0..i % 3).map(|j| Value::F64(j as f64 / 2.0)).collect::<Vec<Value>>();
+        let bytes = [i as u8; 3];
+        append_row(&[Value::I64(i),
+                     if i % 4 == 1 { Value::Null } else { Value::Str(&name) },
+                     if i % 5 == 2 { Value::Null } else { Value::List(scores) },
+                     if i % 2 == 0 { Value::Bool(i % 3 == 0) } else { Value::Null },
+                     Value::Binary(&bytes[..(i % 4) as usize])]);
+      })
+    };
+    let batches = [build(0, 10), build(10, 1), build(11, 0), build(11, 37)];
+
+    let mut writer = expect(StreamWriter::new(Vec::new(), &schema), "create StreamWriter");
+    for batch in &batches {
+      expect(writer.write(batch), "write a row batch");
+    }
+    let other_schema = Schema::new(&[&f1]);
+    let other_batch = RowBatchBuilder::new(&other_schema, &pool).unwrap().finish().unwrap();
+    assert!(writer.write(&other_batch).is_err());
+    let stream = writer.into_inner().unwrap();
+    assert_eq!(&[0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0], &stream[stream.len() - 8..]);
+
+    let reader = expect(StreamReader::open(Cursor::new(&stream)), "open StreamReader");
+    assert_eq!(schema, *reader.schema());
+    let mut count = 0;
+    for (batch, expected) in reader.zip(batches.iter()) {
+      let batch = expect(batch, "read a row batch");
+      assert_eq!(expected.row_num(), batch.row_num());
+      for i in 0..5 {
+        assert!(expected.column::<BaseArray>(i) == batch.column::<BaseArray>(i));
+      }
+      count += 1;
+    }
+    assert_eq!(batches.len(), count);
+
+    // a stream cut before the end-of-stream marker ends there, but one cut in a message is an error
+    let reader = StreamReader::open(Cursor::new(&stream[..stream.len() - 8])).unwrap();
+    assert_eq!(batches.len(), reader.filter(|batch| batch.is_ok()).count());
+    let reader = StreamReader::open(Cursor::new(&stream[..stream.len() - 12])).unwrap();
+    assert!(reader.last().unwrap().is_err());
+    assert!(StreamReader::open(Cursor::new(&stream[..0])).is_err());
+  }
+
+  #[test]
+  fn test_stream_logical_types() {
+    use std::io::Cursor;
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::{DataTypeProvider, DataType, DecimalType, Field, Schema, TemporalType, TimeUnit, UnionMode};
+    use arrow::array_builder::Value;
+    use arrow::array::BaseArray;
+    use arrow::table::RowBatch;
+    use arrow::ipc::writer::StreamWriter;
+    use arrow::ipc::reader::StreamReader;
+
+    let type_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+    let timestamp_type = TemporalType::timestamp(TimeUnit::MILLI, Some(String::from("Asia/Seoul"))).unwrap();
+    let union_fields = [Field::new(String::from("i"), type_provider.i32(), true),
+                        Field::new(String::from("s"), type_provider.string(), true)];
+    let f1 = Field::new(String::from("date"), &TemporalType::Date.data_type().unwrap(), true);
+    let f2 = Field::new(String::from("timestamp"), &timestamp_type.data_type().unwrap(), true);
+    let f3 = Field::new(String::from("time"), &TemporalType::Time { unit: TimeUnit::MICRO }.data_type().unwrap(), false);
+    let f4 = Field::new(String::from("decimal"), &DecimalType::new(10, 2).unwrap().data_type(), true);
+    let f5 = Field::new(String::from("dense"), &DataType::new_union(&union_fields, UnionMode::DENSE), false);
+    let f6 = Field::new(String::from("sparse"), &DataType::new_union(&union_fields, UnionMode::SPARSE), false);
+    let schema = Schema::new(&[&f1, &f2, &f3, &f4, &f5, &f6]);
+
+    let build = |nullable_union: bool| -> RowBatch {
+      build_batch(&schema, &pool, 0..20, |i, append_row| {
+        let name = format!("name{}", i);
+        let union_value = if i % 3 == 0 { Value::Union(1, Box::new(Value::Str(&name))) } else { Value::Union(0, Box::new(Value::I32(i))) };
+        append_row(&[if i % 4 == 1 { Value::Null } else { Value::Date(i * 100) },
+                     if i % 5 == 2 { Value::Null } else { Value::Timestamp(i as i64 * 1_000_000) },
+                     Value::Time(i as i64 * 3_600_000_000),
+                     if i % 6 == 3 { Value::Null } else { Value::Decimal(-(i as i128) * 12_345) },
+                     if nullable_union && i == 7 { Value::Null } else { union_value.clone() },
+                     union_value]);
+      })
+    };
+    let batch = build(false);
+
+    let mut writer = expect(StreamWriter::new(Vec::new(), &schema), "create StreamWriter");
+    expect(writer.write(&batch), "write a row batch");
+    // unions have no validity bitmap in the IPC format
+    assert!(writer.write(&build(true)).is_err());
+    let stream = writer.into_inner().unwrap();
+
+    let mut reader = expect(StreamReader::open(Cursor::new(&stream)), "open StreamReader");
+    assert_eq!(schema, *reader.schema());
+    assert_eq!(Some(timestamp_type), reader.schema().field(1).data_type().temporal_type());
+    let read_batch = match expect(reader.read_next(), "read a row batch") {
+      Some(batch) => batch,
+      None => panic!("The stream ended early")
+    };
+    for i in 0..6 {
+      assert!(batch.column::<BaseArray>(i) == read_batch.column::<BaseArray>(i));
+    }
+    assert!(expect(reader.read_next(), "read the end of the stream").is_none());
+  }
+
+  #[test]
+  fn test_file_format() {
+    use std::io::Cursor;
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::{DataTypeProvider, Field, Schema};
+    use arrow::types::primitive::{I32Array, PrimitiveArray};
+    use arrow::types::string::StringArray;
+    use arrow::array_builder::Value;
+    use arrow::array::{Array, BaseArray};
+    use arrow::table::RowBatch;
+    use arrow::ipc::writer::FileWriter;
+    use arrow::ipc::reader::FileReader;
+
+    let type_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+    let f1 = Field::new(String::from("id"), type_provider.i32(), false);
+    let f2 = Field::new(String::from("name"), type_provider.string(), true);
+    let schema = Schema::new(&[&f1, &f2]);
+
+    let build = |start: i32, rows: i32| -> RowBatch {
+      build_batch(&schema, &pool, start..start + rows, |i, append_row| {
+        let name = format!("name{}", i);
+        append_row(&[Value::I32(i), if i % 3 == 0 { Value::Null } else { Value::Str(&name) }]);
+      })
+    };
+    let batches = [build(0, 5), build(5, 20), build(25, 3)];
+
+    let file_name = "test_file_format.arrow";
+    let mut writer = expect(FileWriter::new(File::create(file_name).unwrap(), &schema), "create FileWriter");
+    for batch in &batches {
+      expect(writer.write(batch), "write a row batch");
+    }
+    assert_eq!(3, writer.num_batches());
+    writer.finish().unwrap();
+    assert!(writer.write(&batches[0]).is_err());
+    drop(writer);
+
+    let mut reader = expect(FileReader::open(File::open(file_name).unwrap()), "open FileReader");
+    assert_eq!(schema, *reader.schema());
+    assert_eq!(3, reader.num_batches());
+
+    // batches can be read in any order
+    for i in vec![2, 0, 1, 2] {
+      let batch = expect(reader.read_batch(i), &format!("read row batch {}", i));
+      let expected = &batches[i as usize];
+      assert_eq!(expected.row_num(), batch.row_num());
+      assert!(expected.column::<BaseArray>(0) == batch.column::<BaseArray>(0));
+      assert!(expected.column::<BaseArray>(1) == batch.column::<BaseArray>(1));
+    }
+    let batch = reader.read_batch(1).unwrap();
+    let ids: I32Array = batch.column(0);
+    let names: StringArray = batch.column(1);
+    assert_eq!(5, ids.value(0));
+    assert!(names.is_null(1));
+    assert_eq!("name7", names.value(2));
+    assert!(reader.read_batch(3).is_err());
+    assert!(reader.read_batch(-1).is_err());
+
+    // files are also readable as streams after the leading magic
+    let bytes = fs::read(file_name).unwrap();
+    assert_eq!(b"ARROW1", &bytes[..6]);
+    assert_eq!(b"ARROW1", &bytes[bytes.len() - 6..]);
+    let stream = arrow::ipc::reader::StreamReader::open(Cursor::new(&bytes[8..])).unwrap();
+    assert_eq!(3, stream.count());
+
+    // a truncated file is rejected
+    assert!(FileReader::open(Cursor::new(&bytes[..bytes.len() - 1])).is_err());
+    assert!(FileReader::open(Cursor::new(&bytes[..10])).is_err());
+
+    fs::remove_file(file_name).unwrap();
+  }
+
+  #[test]
+  fn test_growing_writer() {
+    use std::io::Write;
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::{DataTypeProvider, Field, Schema};
+    use arrow::io::memory::MemoryMappedSink;
+    use arrow::array_builder::Value;
+    use arrow::array::BaseArray;
+    use arrow::ipc::writer::FileWriter;
+    use arrow::ipc::reader::FileReader;
+
+    // the sink grows by remapping, and flushing truncates it to the bytes written
+    let file_name = "test_mmap_sink.dat";
+    let mut sink = MemoryMappedSink::create(String::from(file_name)).unwrap();
+    sink.flush().unwrap();
+    assert_eq!(0, fs::metadata(file_name).unwrap().len());
+    let chunk: Vec<u8> = (0..3000).map(|i| i as u8).collect();
+    for _ in 0..5 {
+      sink.write_all(&chunk).unwrap();
+    }
+    assert_eq!(15000, sink.len());
+    assert!(sink.capacity() >= 15000);
+    sink.flush().unwrap();
+    assert_eq!(15000, sink.capacity());
+    sink.write_all(&chunk[..10]).unwrap();
+    drop(sink);
+    let bytes = fs::read(file_name).unwrap();
+    assert_eq!(15010, bytes.len());
+    assert_eq!(&chunk[..], &bytes[12000..15000]);
+    assert_eq!(&chunk[..10], &bytes[15000..]);
+    fs::remove_file(file_name).unwrap();
+
+    // row batches are written without sizing the file first
+    let type_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+    let f1 = Field::new(String::from("key"), type_provider.i32(), false);
+    let f2 = Field::new(String::from("payload"), type_provider.f64(), true);
+    let schema = Schema::new(&[&f1, &f2]);
+    let batch = build_batch(&schema, &pool, 0..1000, |i, append_row| {
+      append_row(&[Value::I32(i), if i % 7 == 0 { Value::Null } else { Value::F64(i as f64) }]);
+    });
+
+    let file_name = "test_growing_writer.arrow";
+    let mut writer = expect(FileWriter::create(String::from(file_name), &schema), "create FileWriter");
+    for _ in 0..10 {
+      expect(writer.write(&batch), "write a row batch");
+    }
+    let sink = writer.into_inner().unwrap();
+    assert_eq!(sink.len() as u64, fs::metadata(file_name).unwrap().len());
+    drop(sink);
+
+    let mut reader = FileReader::open(File::open(file_name).unwrap()).unwrap();
+    assert_eq!(10, reader.num_batches());
+    for i in 0..10 {
+      let read_batch = reader.read_batch(i).unwrap();
+      assert!(batch.column::<BaseArray>(0) == read_batch.column::<BaseArray>(0));
+      assert!(batch.column::<BaseArray>(1) == read_batch.column::<BaseArray>(1));
+    }
+
+    // the same writer appends to an in-memory sink
+    let mut writer = FileWriter::new(Vec::new(), &schema).unwrap();
+    writer.write(&batch).unwrap();
+    let bytes = writer.into_inner().unwrap();
+    assert_eq!(fs::read(file_name).unwrap()[..64], bytes[..64]);
+
+    fs::remove_file(file_name).unwrap();
+  }
+
+  #[test]
+  fn test_dictionary_batches() {
+    use std::io::Cursor;
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::{DataType, DataTypeProvider, Field, Schema};
+    use arrow::common::status::ArrowError;
+    use arrow::types::primitive::{I8Array, I8ArrayBuilder, I32Array};
+    use arrow::types::string::StringArrayBuilder;
+    use arrow::types::dictionary::{DictionaryArray, DictionaryBuilder};
+    use arrow::array_builder::Value;
+    use arrow::array::{Array, BaseArray};
+    use arrow::table::RowBatch;
+    use arrow::ipc::metadata::DictionaryField;
+    use arrow::ipc::writer::{FileWriter, StreamWriter};
+    use arrow::ipc::reader::{FileReader, StreamReader};
+
+    let type_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+    // the country column holds keys into dictionary 7
+    let f1 = Field::new(String::from("id"), type_provider.i32(), false);
+    let f2 = Field::new(String::from("country"), type_provider.i8(), true);
+    let schema = Schema::new(&[&f1, &f2]);
+    let dictionary_fields = || vec![DictionaryField { column: 1, id: 7, value_type: DataType::new_string() }];
+
+    let build = |keys: &[Option<i8>]| -> RowBatch {
+      build_batch(&schema, &pool, keys.iter().enumerate(), |(i, key), append_row| {
+        append_row(&[Value::I32(i as i32), match *key { Some(key) => Value::I8(key), None => Value::Null }]);
+      })
+    };
+    let strings = |values: &[&str]| -> BaseArray {
+      let mut builder = StringArrayBuilder::new(&pool, type_provider.string());
+      builder.append(values).unwrap();
+      builder.finish_as_base()
+    };
+    let decode = |array: Result<DictionaryArray<I8Array>, ArrowError>| -> BaseArray {
+      expect(array, "read a dictionary column").decode(&pool).unwrap()
+    };
+    let expected = |values: &[Option<&str>]| -> BaseArray {
+      let mut builder = StringArrayBuilder::new(&pool, type_provider.string());
+      builder.append_nullable(values).unwrap();
+      builder.finish_as_base()
+    };
+
+    // a delta extends the dictionary, and a dictionary batch without delta replaces it
+    let mut writer = expect(StreamWriter::with_dictionaries(Vec::new(), &schema, dictionary_fields()), "create StreamWriter");
+    assert!(writer.write(&build(&[Some(0)])).is_err());
+    assert!(writer.write_delta_dictionary(7, &strings(&["kr"])).is_err());
+    assert!(writer.write_dictionary(8, &strings(&["kr"])).is_err());
+    assert!(writer.write_dictionary(7, &build(&[Some(0)]).column::<BaseArray>(0)).is_err());
+    writer.write_dictionary(7, &strings(&["kr", "us"])).unwrap();
+    writer.write(&build(&[Some(0), Some(1), None])).unwrap();
+    writer.write_delta_dictionary(7, &strings(&["jp"])).unwrap();
+    writer.write(&build(&[Some(2), Some(0)])).unwrap();
+    writer.write_dictionary(7, &strings(&["fr"])).unwrap();
+    writer.write(&build(&[Some(0)])).unwrap();
+    let bytes = writer.into_inner().unwrap();
+
+    let mut reader = expect(StreamReader::open(Cursor::new(&bytes[..])), "open StreamReader");
+    assert_eq!(schema, *reader.schema());
+    assert_eq!(1, reader.dictionary_fields().len());
+    assert_eq!(1, reader.dictionary_fields()[0].column);
+    assert_eq!(7, reader.dictionary_fields()[0].id);
+    assert_eq!(DataType::new_string(), reader.dictionary_fields()[0].value_type);
+    assert!(reader.dictionary(7).is_none());
+
+    let expected_batches = [
+      expected(&[Some("kr"), Some("us"), None]),
+      expected(&[Some("jp"), Some("kr")]),
+      expected(&[Some("fr")])
+    ];
+    for expected_batch in &expected_batches {
+      let batch = match expect(reader.read_next(), "read a row batch") {
+        Some(batch) => batch,
+        None => panic!("The stream ended early")
+      };
+      assert!(*expected_batch == decode(reader.dictionary_column(&batch, 1)));
+    }
+    assert!(reader.dictionary_column::<I8Array>(&build(&[Some(0)]), 0).is_err());
+    // the keys of column 1 are int8
+    assert!(reader.dictionary_column::<I32Array>(&build(&[Some(0)]), 1).is_err());
+    // the last dictionary batch replaced the extended dictionary
+    assert_eq!(1, reader.dictionary(7).unwrap().len());
+    assert!(reader.read_next().unwrap().is_none());
+
+    // files extend a dictionary with deltas but never replace it
+    let mut dictionary_builder: DictionaryBuilder<I8ArrayBuilder, str> =
+      DictionaryBuilder::new(&pool, I8ArrayBuilder::new(&pool, type_provider.i8()));
+    dictionary_builder.append("kr").unwrap().append("us").unwrap().append("kr").unwrap();
+    let countries = dictionary_builder.finish().unwrap();
+    let mut writer = expect(FileWriter::with_dictionaries(Vec::new(), &schema, dictionary_fields()), "create FileWriter");
+    writer.write_dictionary_array(7, &countries).unwrap();
+    assert!(writer.write_dictionary(7, &strings(&["fr"])).is_err());
+    writer.write(&build(&[Some(0), None])).unwrap();
+    writer.write_delta_dictionary(7, &strings(&["jp"])).unwrap();
+    writer.write(&build(&[Some(2), Some(1)])).unwrap();
+    let bytes = writer.into_inner().unwrap();
+
+    let mut reader = expect(FileReader::open(Cursor::new(&bytes[..])), "open FileReader");
+    assert_eq!(schema, *reader.schema());
+    assert_eq!(2, reader.num_batches());
+    assert_eq!(3, reader.dictionary(7).unwrap().len());
+    let batch = reader.read_batch(1).unwrap();
+    assert!(expected(&[Some("jp"), Some("us")]) == decode(reader.dictionary_column(&batch, 1)));
+    let batch = reader.read_batch(0).unwrap();
+    assert!(expected(&[Some("kr"), None]) == decode(reader.dictionary_column(&batch, 1)));
+
+    // dictionary fields must refer to columns of the schema
+    let bad_fields = vec![DictionaryField { column: 2, id: 1, value_type: DataType::new_string() }];
+    assert!(StreamWriter::with_dictionaries(Vec::new(), &schema, bad_fields).is_err());
+  }
+
+  #[test]
+  fn test_offset_buffers() {
+    use arrow::buffer::Buf;
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::{DataType, Ty};
+    use arrow::types::primitive::I32ArrayBuilder;
+    use arrow::types::string::{BinaryArray, StringArray};
+    use arrow::types::list::ListArray;
+
+    let strings = match StringArray::from_buffers(2, Buf::from(vec![0i32, 2, 5]), Buf::from(b"abcde".to_vec()), 0, None) {
+      Ok(array) => array,
+      Err(e) => panic!("Failed to create a string array: {}", e.message())
+    };
+    assert_eq!("cde", strings.value(1));
+
+    // invalid UTF-8 is only accepted as binary
+    let invalid = || Buf::from(vec![0xc3u8, 0x28]);
+    assert!(StringArray::from_buffers(1, Buf::from(vec![0i32, 2]), invalid(), 0, None).is_err());
+    assert!(BinaryArray::from_buffers(1, Buf::from(vec![0i32, 2]), invalid(), 0, None).is_ok());
+
+    let data = || Buf::from(b"abcde".to_vec());
+    assert!(BinaryArray::from_buffers(2, Buf::from(vec![0i32, 2, 9]), data(), 0, None).is_err());
+    assert!(BinaryArray::from_buffers(2, Buf::from(vec![0i32, 3, 2]), data(), 0, None).is_err());
+    assert!(BinaryArray::from_buffers(3, Buf::from(vec![0i32, 2, 5]), data(), 0, None).is_err());
+    assert!(BinaryArray::from_buffers(2, Buf::from(vec![0i32, 2, 5]), data(), 1, None).is_err());
+    assert!(BinaryArray::from_buffers(2, Buf::from(vec![0i32, 2, 5]), data(), 3, Some(Buf::from(vec![1u8]))).is_err());
+
+    let pool = DefaultMemoryPool::default();
+    let mut builder = I32ArrayBuilder::new(&pool, &DataType::new_primitive(Ty::INT32));
+    builder.extend(vec![Some(1), Some(2), Some(3)]).unwrap();
+    let values = builder.finish_as_base();
+    let list_type = DataType::new_list(DataType::new_primitive(Ty::INT32));
+    let list = match ListArray::from_buffers(&list_type, 2, Buf::from(vec![0i32, 1, 3]), &values, 0, None) {
+      Ok(array) => array,
+      Err(e) => panic!("Failed to create a list array: {}", e.message())
+    };
+    assert_eq!(2, list.value_length(1));
+    assert!(ListArray::from_buffers(&list_type, 2, Buf::from(vec![0i32, 1, 4]), &values, 0, None).is_err());
+    assert!(ListArray::from_buffers(&DataType::new_string(), 2, Buf::from(vec![0i32, 1, 3]), &values, 0, None).is_err());
+  }
+
   #[test]
   fn test_raw_adapter() {
     use std::mem;
@@ -525,8 +1914,11 @@ mod tests {
       f.set_len(batch_size as u64).unwrap();
       f.sync_all().unwrap();
 
+      let mut s = ptr::null();
       let src = memory::open_mmap_src(CString::new(file_name).unwrap().as_ptr(),
-                                      memory::AccessMode::READWRITE);
+                                      memory::AccessMode::READWRITE, &mut s);
+      assert!(status::ok(s));
+      status::release_status(s);
       let header_pos = adapter::c_api::write_row_batch(src, row_batch, 0, 64);
 
       let s = memory::close_mmap_src(src);
@@ -535,8 +1927,11 @@ mod tests {
       memory::release_mmap_src(src);
       table::release_row_batch(row_batch);
 
+      let mut s = ptr::null();
       let src = memory::open_mmap_src(CString::new(file_name).unwrap().as_ptr(),
-                                      memory::AccessMode::READ);
+                                      memory::AccessMode::READ, &mut s);
+      assert!(status::ok(s));
+      status::release_status(s);
 
 //      let reader = adapter::c_api::open_row_batch_reader(src, header_pos);
       let result = adapter::c_api::open_row_batch_reader(src, header_pos);
@@ -572,7 +1967,7 @@ mod tests {
   fn test_adapter() {
     use arrow::buffer::Buffer;
     use arrow::io::memory::MemoryMappedSource;
-    use arrow::common::memory_pool::MemoryPool;
+    use arrow::common::memory_pool::DefaultMemoryPool;
     use arrow::ty::{DataTypeProvider, DataType, Field, Schema};
     use arrow::types::primitive::{F32ArrayBuilder, I32ArrayBuilder, PrimitiveArray};
     use arrow::array::Array;
@@ -582,7 +1977,7 @@ mod tests {
 
     // prepare test
     let type_provider = DataTypeProvider::new();
-    let pool = MemoryPool::default();
+    let pool = DefaultMemoryPool::default();
     let f1 = Field::new(String::from("f1"), type_provider.i32(), false);
     let f2 = Field::new(String::from("f2"), type_provider.f32(), true);
     let schema = Schema::new(&[&f1, &f2]);
@@ -605,7 +2000,7 @@ mod tests {
     f.sync_all().unwrap();
 
     // write row batch
-    let src = MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READWRITE);
+    let src = expect(MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READWRITE), "open the file");
     let header_pos = adapter::write_row_batch(&src, &row_batch, 0);
 
     let src = match src.close() {
@@ -614,12 +2009,12 @@ mod tests {
     };
 
     // read row batch
-    let src = MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READ);
+    let src = expect(MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READ), "open the file");
     let batch_reader = match adapter::RowBatchReader::open(&src, header_pos) {
       Ok(reader) => reader,
       Err(e) => panic!("Failed to open RowBatchReader: {}", e.message())
     };
-    let row_batch = batch_reader.read(&schema);
+    let row_batch = expect(batch_reader.read(&schema), "read the row batch");
     let col = row_batch.column(0);
     assert_eq!(arrays[0], col);
 
@@ -630,4 +2025,134 @@ mod tests {
 
     fs::remove_file(file_name).unwrap();
   }
-}
+
+  #[test]
+  fn test_string_adapter() {
+    use arrow::io::memory::MemoryMappedSource;
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::{DataTypeProvider, Field, Schema};
+    use arrow::types::string::{StringArray, StringArrayBuilder};
+    use arrow::array::Array;
+    use arrow::table::RowBatch;
+    use arrow::ipc::adapter;
+
+    let type_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+    let f1 = Field::new(String::from("f1"), type_provider.string(), true);
+    let schema = Schema::new(&[&f1]);
+
+    let mut builder = StringArrayBuilder::new(&pool, type_provider.string());
+    match builder.append(&["arrow", "", "columnar"]) {
+      Ok(builder) => builder.append_null(),
+      Err(e) => panic!("append failed: {}", e.message())
+    };
+    let arrays = [builder.finish_as_base()];
+    let row_batch = RowBatch::new(&schema, 4, &arrays);
+
+    let file_name = "test_string_adapter.dat";
+    let mut f = File::create(file_name).unwrap();
+    f.set_len(row_batch.size() as u64).unwrap();
+    f.sync_all().unwrap();
+
+    let src = expect(MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READWRITE), "open the file");
+    let header_pos = adapter::write_row_batch(&src, &row_batch, 0);
+    let src = match src.close() {
+      Ok(src) => src,
+      Err(e) => panic!("close failed: {}", e.message())
+    };
+
+    let src = expect(MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READ), "open the file");
+    let batch_reader = match adapter::RowBatchReader::open(&src, header_pos) {
+      Ok(reader) => reader,
+      Err(e) => panic!("Failed to open RowBatchReader: {}", e.message())
+    };
+    let row_batch = expect(batch_reader.read(&schema), "read the row batch");
+    let col: StringArray = row_batch.column(0);
+    assert_eq!(4, col.len());
+    assert_eq!("arrow", col.value(0));
+    assert_eq!("", col.value(1));
+    assert_eq!("columnar", col.value(2));
+    assert!(col.is_null(3));
+
+    let src = match src.close() {
+      Ok(src) => src,
+      Err(e) => panic!("close failed: {}", e.message())
+    };
+
+    fs::remove_file(file_name).unwrap();
+  }
+
+  #[test]
+  fn test_list_adapter() {
+    use arrow::io::memory::MemoryMappedSource;
+    use arrow::common::memory_pool::DefaultMemoryPool;
+    use arrow::ty::{DataTypeProvider, DataType, Field, Schema, Ty};
+    use arrow::types::primitive::{I32Array, I32ArrayBuilder, PrimitiveArray};
+    use arrow::types::list::{ListArray, ListArrayBuilder};
+    use arrow::types::string::StringArray;
+    use arrow::array::Array;
+    use arrow::table::RowBatch;
+    use arrow::ipc::adapter;
+
+    let type_provider = DataTypeProvider::new();
+    let pool = DefaultMemoryPool::default();
+    let list_type = DataType::new_list(DataType::new_primitive(Ty::INT32));
+    let f1 = Field::new(String::from("events"), &list_type, true);
+    let schema = Schema::new(&[&f1]);
+
+    // [[0, 1, 2], [3], null, []]
+    let value_builder = I32ArrayBuilder::new(&pool, type_provider.i32());
+    let mut builder = ListArrayBuilder::new(&pool, &list_type, value_builder);
+    builder.append(true);
+    builder.value_builder().append(&[0, 1, 2], ptr::null());
+    builder.append(true);
+    builder.value_builder().append(&[3], ptr::null());
+    builder.append_null();
+    builder.append(true);
+
+    let array = builder.finish();
+    assert_eq!(list_type, array.data_type());
+    assert_eq!(4, array.len());
+    assert_eq!(1, array.null_count());
+    assert_eq!(&[0, 3, 4, 4, 4], array.value_offsets());
+    assert_eq!(3, array.value_length(0));
+    assert_eq!(0, array.value_length(3));
+    assert_eq!(type_provider.i32(), &array.value_type());
+
+    let values: I32Array = array.typed_values().unwrap();
+    assert_eq!(&[0, 1, 2, 3], values.as_slice());
+    assert!(array.typed_values::<StringArray>().is_err());
+
+    let row_batch = RowBatch::new(&schema, 4, &[array]);
+
+    let file_name = "test_list_adapter.dat";
+    let mut f = File::create(file_name).unwrap();
+    f.set_len(row_batch.size() as u64).unwrap();
+    f.sync_all().unwrap();
+
+    let src = expect(MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READWRITE), "open the file");
+    let header_pos = adapter::write_row_batch(&src, &row_batch, 0);
+    let src = match src.close() {
+      Ok(src) => src,
+      Err(e) => panic!("close failed: {}", e.message())
+    };
+
+    let src = expect(MemoryMappedSource::open(String::from(file_name), memory::AccessMode::READ), "open the file");
+    let batch_reader = match adapter::RowBatchReader::open(&src, header_pos) {
+      Ok(reader) => reader,
+      Err(e) => panic!("Failed to open RowBatchReader: {}", e.message())
+    };
+    let row_batch = expect(batch_reader.read(&schema), "read the row batch");
+    let col: ListArray = row_batch.column(0);
+    assert_eq!(&[0, 3, 4, 4, 4], col.value_offsets());
+    assert!(col.is_null(2));
+    assert!(values == col.typed_values::<I32Array>().unwrap());
+
+    let src = match src.close() {
+      Ok(src) => src,
+      Err(e) => panic!("close failed: {}", e.message())
+    };
+
+    fs::remove_file(file_name).unwrap();
+  }
+}
\ No newline at end of file